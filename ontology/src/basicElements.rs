@@ -1,628 +1,2193 @@
-/**
-    Contains the different basic elements
-*/
-extern crate serde;
-extern crate serde_json;
-
-use std::fs::File;
-use std::io::Read;
-use std::cmp::Ordering;
-use std;
-
-
-use serde_json::{Value, Error};
-
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum Magnitude {
-    pico,
-    nano,
-    micro,
-    milli,
-    none,
-    kilo,
-    mega,
-    giga,
-    tera,
-    peta
-}
-
-impl Default for Magnitude {
-    fn default() -> Magnitude { Magnitude::none }
-}
-
-impl Magnitude {
-    fn to_float(&self) -> f64 {
-        match self {
-            &Magnitude::pico => 10_f64.powf(-12.0),
-            &Magnitude::nano => 10_f64.powf(-9.0),
-            &Magnitude::micro => 10_f64.powf(-6.0),
-            &Magnitude::milli => 10_f64.powf(-3.0),
-            &Magnitude::none => 1_f64,
-            &Magnitude::kilo => 10_f64.powf(3.0),
-            &Magnitude::mega => 10_f64.powf(6.0),
-            &Magnitude::giga => 10_f64.powf(9.0),
-            &Magnitude::tera => 10_f64.powf(12.0),
-            &Magnitude::peta => 10_f64.powf(15.0),
-        }
-    }
-}
-
-impl PartialEq for Magnitude {
-    fn eq(&self, other: &Magnitude) -> bool {
-        match (self, other) {
-            (&Magnitude::pico, &Magnitude::pico) => true,
-            (&Magnitude::nano, &Magnitude::nano) => true,
-            (&Magnitude::micro, &Magnitude::micro) => true,
-            (&Magnitude::milli, &Magnitude::milli) => true,
-            (&Magnitude::none, &Magnitude::none) => true,
-            (&Magnitude::kilo, &Magnitude::kilo) => true,
-            (&Magnitude::mega, &Magnitude::mega) => true,
-            (&Magnitude::giga, &Magnitude::giga) => true,
-            (&Magnitude::tera, &Magnitude::tera) => true,
-            (&Magnitude::peta, &Magnitude::peta) => true,
-            (_, _) => false,
-        }
-    }
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct MagnitudeElement {
-    id: Option<i16>,
-    magnitude: Option<Magnitude>,
-}
-
-impl Default for MagnitudeElement {
-    fn default() -> MagnitudeElement {
-        MagnitudeElement { id: None, magnitude: None }
-    }
-}
-
-impl MagnitudeElement {
-    pub fn get_id(&self) -> &Option<i16> { &self.id }
-
-    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
-
-    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
-
-    pub fn get_magnitude(&self) -> &Option<Magnitude> {
-        &self.magnitude
-    }
-    pub fn get_magnitude_mut(&mut self) -> &mut Option<Magnitude> {
-        &mut self.magnitude
-    }
-
-    pub fn set_magnitude(&mut self, mag: Option<Magnitude>) {
-        self.magnitude = mag;
-    }
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct BasicElement<T> {
-    id: Option<i16>,
-    value: Option<T>,
-}
-
-
-
-impl <T: PartialEq> BasicElement<T> {
-
-
-     pub fn compare(&self, b: &BasicElement<T>) -> bool {
-         self.eq(b)
-    }
-
-    pub fn get_id(&self) -> &Option<i16> { &self.id }
-    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
-    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
-
-    pub fn get_value(&self) -> &Option<T> {
-        &self.value
-    }
-    pub fn get_value_mut(&mut self) -> &mut Option<T> {
-        &mut self.value
-    }
-    pub fn set_value(&mut self, val: Option<T>) {
-        self.value = val;
-    }
-
-}
-
-
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct StringElement {
-    id: Option<i16>,
-    value: Option<String>,
-    #[serde(default)]
-    compareOperator: StringOperator,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum StringOperator {
-    eq,
-    lowerCaseEq,
-}
-
-impl std::str::FromStr for StringOperator {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<StringOperator, ()> {
-        match s {
-            "eq" => Ok(StringOperator::eq),
-            "lowerCaseEq" => Ok(StringOperator::lowerCaseEq),
-            _ => {
-                println!("Error in String to StringOperator parsing");
-                Ok(StringOperator::eq)
-            }
-        }
-    }
-}
-
-
-impl Default for StringOperator {
-    fn default() -> StringOperator { StringOperator::eq }
-}
-
-impl StringElement {
-    pub fn get_id(&self) -> &Option<i16> { &self.id }
-    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
-    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
-
-    pub fn get_value(&self) -> &Option<String> {
-        &self.value
-    }
-    pub fn get_value_mut(&mut self) -> &mut Option<String> {
-        &mut self.value
-    }
-    pub fn set_value(&mut self, str: Option<String>) {
-        self.value = str;
-    }
-
-    pub fn get_compare_operator(&self) -> &StringOperator {
-        &self.compareOperator
-    }
-    pub fn get_compare_operator_mut(&mut self) -> &mut StringOperator {
-        &mut self.compareOperator
-    }
-    pub fn set_compare_operator(&mut self, op: StringOperator) {
-        self.compareOperator = op;
-    }
-
-    fn lower_case_eq(&self, b: &StringElement) -> bool {
-        self.value.clone().unwrap().to_lowercase() == b.value.clone().unwrap().to_lowercase()
-    }
-
-
-    fn eq(&self, b: &StringElement) -> bool {
-        self.value == b.value
-    }
-
-    pub fn compare(&self, b: &StringElement) -> bool {
-        match self.compareOperator {
-            StringOperator::lowerCaseEq => self.lower_case_eq(b),
-            StringOperator::eq => self.eq(b),
-        }
-    }
-}
-
-
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct IntElement {
-    id: Option<i16>,
-    value: Option<u32>,
-    #[serde(default)]
-    magnitude: MagnitudeElement,
-    #[serde(default)]
-    compareOperator: IntOperator,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum IntOperator {
-    leq,
-    geq,
-    eq,
-    le,
-    ge,
-}
-
-impl std::str::FromStr for IntOperator {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<IntOperator, ()> {
-        match s {
-            "leq" => Ok(IntOperator::leq),
-            "geq" => Ok(IntOperator::geq),
-            "eq" => Ok(IntOperator::eq),
-            "le" => Ok(IntOperator::le),
-            "ge" => Ok(IntOperator::ge),
-            _ => {
-                println!("Error in String to StringOperator parsing");
-                Ok(IntOperator::eq)
-            }
-        }
-    }
-}
-
-
-impl IntElement {
-    fn leq(&self, b: &IntElement) -> bool {
-         match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() <= b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() <= b.get_value().unwrap().clone() as f64,
-            (&None, &Some(ref magnitude_b)) => self.get_value().unwrap().clone() as f64 <= b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&None, &None) => self.get_value().unwrap().clone() as f64 <= b.get_value().unwrap().clone() as f64
-        }
-    }
-
-    fn geq(&self, b: &IntElement) -> bool {
-         match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() >= b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() >= b.get_value().unwrap().clone() as f64,
-            (&None, &Some(ref magnitude_b)) => self.get_value().unwrap().clone() as f64 >= b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&None, &None) => self.get_value().unwrap().clone() as f64 >= b.get_value().unwrap().clone() as f64
-        }
-    }
-
-    fn eq(&self, b: &IntElement) -> bool {
-         match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() == b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() == b.get_value().unwrap().clone() as f64,
-            (&None, &Some(ref magnitude_b)) => (self.get_value().unwrap().clone() as f64) == b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&None, &None) => (self.get_value().unwrap().clone() as f64) == b.get_value().unwrap().clone() as f64
-        }
-    }
-
-    fn le(&self, b: &IntElement) -> bool {
-         match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() < b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() < b.get_value().unwrap().clone() as f64,
-             (&None, &Some(ref magnitude_b)) => (self.get_value().unwrap().clone() as f64) < (b.get_value().unwrap().clone() as f64 * magnitude_b.to_float()),
-            (&None, &None) => (self.get_value().unwrap().clone() as f64) < (b.get_value().unwrap().clone() as f64)
-        }
-    }
-
-    fn ge(&self, b: &IntElement) -> bool {
-         match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() > b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone() as f64 * magnitude_a.to_float() > b.get_value().unwrap().clone() as f64,
-            (&None, &Some(ref magnitude_b)) => (self.get_value().unwrap().clone() as f64) > b.get_value().unwrap().clone() as f64 * magnitude_b.to_float(),
-            (&None, &None) => (self.get_value().unwrap().clone() as f64) > b.get_value().unwrap().clone() as f64
-        }
-    }
-
-
-    pub fn get_id(&self) -> &Option<i16> { &self.id }
-    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
-    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
-
-    pub fn get_value(&self) -> &Option<u32> {
-        &self.value
-    }
-    pub fn get_value_mut(&mut self) -> &mut Option<u32> {
-        &mut self.value
-    }
-    pub fn set_value(&mut self, val: Option<u32>) {
-        self.value = val;
-    }
-
-    pub fn get_compare_operator(&self) -> &IntOperator {
-        &self.compareOperator
-    }
-    pub fn get_compare_operator_mut(&mut self) -> &mut IntOperator {
-        &mut self.compareOperator
-    }
-    pub fn set_compare_operator(&mut self, op: IntOperator) {
-        self.compareOperator = op;
-    }
-
-    pub fn get_magnitude(&self) -> &MagnitudeElement {
-        &self.magnitude
-    }
-    pub fn get_magnitude_mut(&mut self) -> &mut MagnitudeElement {
-        &mut self.magnitude
-    }
-    pub fn set_magnitude(&mut self, val: MagnitudeElement) {
-        self.magnitude = val;
-    }
-
-    pub fn compare(&self, b: &IntElement) -> bool {
-        match self.compareOperator {
-            IntOperator::leq => self.leq(b),
-            IntOperator::geq => self.geq(b),
-            IntOperator::eq => self.eq(b),
-            IntOperator::le => self.le(b),
-            IntOperator::ge => self.ge(b)
-        }
-    }
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct FloatElement {
-    id: Option<i16>,
-    value: Option<f64>,
-    #[serde(default)]
-    magnitude: MagnitudeElement,
-    #[serde(default)]
-    compareOperator: FloatOperator,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum FloatOperator {
-    leq,
-    geq,
-    eq,
-    le,
-    ge,
-}
-
-impl Default for FloatOperator {
-    fn default() -> FloatOperator { FloatOperator::eq }
-}
-
-impl Default for IntOperator {
-    fn default() -> IntOperator { IntOperator::eq }
-}
-
-
-impl std::str::FromStr for FloatOperator {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<FloatOperator, ()> {
-        match s {
-            "leq" => Ok(FloatOperator::leq),
-            "geq" => Ok(FloatOperator::geq),
-            "eq" => Ok(FloatOperator::eq),
-            "le" => Ok(FloatOperator::le),
-            "ge" => Ok(FloatOperator::ge),
-            _ => {
-                println!("Error in String to StringOperator parsing");
-                Ok(FloatOperator::eq)
-            }
-        }
-    }
-}
-
-impl FloatElement {
-    fn leq(&self, b: &FloatElement) -> bool {
-        match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => (&self.get_value().unwrap()) * &magnitude_a.to_float() <= b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone() * magnitude_a.to_float() <= b.get_value().unwrap().clone(), 
-            (&None, &Some(ref magnitude_b)) => self.get_value().unwrap().clone() <= b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&None, &None) => self.get_value().unwrap().clone()<= b.get_value().unwrap().clone()
-        }
-    }
-
-    fn geq(&self, b: &FloatElement) -> bool {
-        match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone()* magnitude_a.to_float() >= b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone()* magnitude_a.to_float() >= b.get_value().unwrap().clone(),
-            (&None, &Some(ref magnitude_b)) => self.get_value().unwrap().clone()>= b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&None, &None) => self.get_value().unwrap().clone()>= b.get_value().unwrap().clone()
-        }
-    }
-
-    fn eq(&self, b: &FloatElement) -> bool {
-         match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone()* magnitude_a.to_float() == b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone()* magnitude_a.to_float() == b.get_value().unwrap().clone(),
-            (&None, &Some(ref magnitude_b)) => self.get_value().unwrap().clone()== b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&None, &None) => self.get_value().unwrap().clone()== b.get_value().unwrap().clone()
-        }
-    }
-
-    fn le(&self, b: &FloatElement) -> bool {
-          match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone()* magnitude_a.to_float() < b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone()* magnitude_a.to_float() < b.get_value().unwrap().clone(),
-            (&None, &Some(ref magnitude_b)) => self.get_value().unwrap().clone()< b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&None, &None) => self.get_value().unwrap().clone()< b.get_value().unwrap().clone()
-        }
-    }
-
-    fn ge(&self, b: &FloatElement) -> bool {
-          match (self.get_magnitude().get_magnitude(), b.get_magnitude().get_magnitude()) {
-            (&Some(ref magnitude_a), &Some(ref magnitude_b)) => self.get_value().unwrap().clone()* magnitude_a.to_float() > b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&Some(ref magnitude_a), &None) => self.get_value().unwrap().clone()* magnitude_a.to_float() > b.get_value().unwrap().clone(),
-            (&None, &Some(ref magnitude_b)) => self.get_value().unwrap().clone()> b.get_value().unwrap().clone() * magnitude_b.to_float(),
-            (&None, &None) => self.get_value().unwrap().clone() > b.get_value().unwrap().clone()
-        }
-    }
-    pub fn compare(&self, b: &FloatElement) -> bool {
-        match self.compareOperator {
-            FloatOperator::leq => self.leq(b),
-            FloatOperator::geq => self.geq(b),
-            FloatOperator::eq => self.eq(b),
-            FloatOperator::le => self.le(b),
-            FloatOperator::ge => self.ge(b)
-        }
-    }
-
-
-    pub fn get_id(&self) -> &Option<i16> { &self.id }
-
-    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
-
-    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
-
-    pub fn get_value(&self) -> &Option<f64> {
-        &self.value
-    }
-
-    pub fn get_value_mut(&mut self) -> &mut Option<f64> {
-        &mut self.value
-    }
-
-    pub fn set_value(&mut self, val: Option<f64>) {
-        self.value = val;
-    }
-
-    pub fn get_compare_operator(&self) -> &FloatOperator {
-        &self.compareOperator
-    }
-
-    pub fn get_compare_operator_mut(&mut self) -> &mut FloatOperator {
-        &mut self.compareOperator
-    }
-
-    pub fn set_compare_operator(&mut self, op: FloatOperator) {
-        self.compareOperator = op;
-    }
-
-    pub fn get_magnitude(&self) -> &MagnitudeElement {
-        &self.magnitude
-    }
-
-    pub fn get_magnitude_mut(&mut self) -> &mut MagnitudeElement {
-        &mut self.magnitude
-    }
-
-    pub fn set_magnitude(&mut self, val: MagnitudeElement) {
-        self.magnitude = val;
-    }
-}
-
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum DiskType {
-    HDD,
-    SSD
-}
-
-impl std::str::FromStr for DiskType {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<DiskType, ()> {
-        match s {
-            "HDD" => Ok(DiskType::HDD),
-            "SSD" => Ok(DiskType::SSD),
-            _ => {
-                println!("Error in String to DiskType parsing");
-                Ok(DiskType::HDD)
-            }
-        }
-    }
-}
-
-impl PartialEq for DiskType {
-    fn eq(&self, other: &DiskType) -> bool {
-        match (self, other) {
-            (&DiskType::HDD, &DiskType::HDD) => true,
-            (&DiskType::SSD, &DiskType::SSD) => true,
-            (_, _) => false,
-        }
-    }
-}
-
-
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum InstructionSet {
-    MMX,
-    SSE,
-    SSE2,
-    SSE3,
-    SSSE3,
-    SSE4a,
-    SSE4_1,
-    SSE4_2,
-    AVX,
-    AVX2,
-    FMA3,
-    F16C,
-    AES,
-    AES_NI,
-    BMI1,
-    BMI2
-}
-
-impl std::str::FromStr for InstructionSet {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<InstructionSet, ()> {
-        match s {
-            "MMX" => Ok(InstructionSet::MMX),
-            "SSE" => Ok(InstructionSet::SSE),
-            "SSE2" => Ok(InstructionSet::SSE2),
-            "SSE3" => Ok(InstructionSet::SSE3),
-            "SSSE3" => Ok(InstructionSet::SSSE3),
-            "SSE4a" => Ok(InstructionSet::SSE4a),
-            "SSE4.1" => Ok(InstructionSet::SSE4_1),
-            "SSE4.2" => Ok(InstructionSet::SSE4_2),
-            "AVX" => Ok(InstructionSet::AVX),
-            "AVX2" => Ok(InstructionSet::AVX2),
-            "FMA3" => Ok(InstructionSet::FMA3),
-            "F16C" => Ok(InstructionSet::F16C),
-            "AES" => Ok(InstructionSet::AES),
-            "AES-NI" => Ok(InstructionSet::AES_NI),
-            "BMI1" => Ok(InstructionSet::BMI1),
-            "BMI2" => Ok(InstructionSet::BMI2),
-            _ => {
-                println!("Error in String to MemoryGeneration parsing");
-                Ok(InstructionSet::MMX)
-            }
-        }
-    }
-}
-
-impl PartialEq for InstructionSet {
-    fn eq(&self, other: &InstructionSet) -> bool {
-        match (self, other) {
-            (&InstructionSet::MMX, &InstructionSet::MMX) => true,
-            (&InstructionSet::SSE, &InstructionSet::SSE) => true,
-            (&InstructionSet::SSE2, &InstructionSet::SSE2) => true,
-            (&InstructionSet::SSE3, &InstructionSet::SSE3) => true,
-            (&InstructionSet::SSSE3, &InstructionSet::SSSE3) => true,
-            (&InstructionSet::SSE4a, &InstructionSet::SSE4a) => true,
-            (&InstructionSet::SSE4_1, &InstructionSet::SSE4_1) => true,
-            (&InstructionSet::SSE4_2, &InstructionSet::SSE4_2) => true,
-            (&InstructionSet::AVX, &InstructionSet::AVX) => true,
-            (&InstructionSet::AVX2, &InstructionSet::AVX2) => true,
-            (&InstructionSet::FMA3, &InstructionSet::FMA3) => true,
-            (&InstructionSet::F16C, &InstructionSet::F16C) => true,
-            (&InstructionSet::AES, &InstructionSet::AES) => true,
-            (&InstructionSet::AES_NI, &InstructionSet::AES_NI) => true,
-            (&InstructionSet::BMI1, &InstructionSet::BMI1) => true,
-            (&InstructionSet::BMI2, &InstructionSet::BMI2) => true,
-            (_, _) => false,
-        }
-    }
-}
-
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum MemoryGeneration {
-    DDRRAM,
-    DDR2RAM,
-    DDR3RAM,
-    DDR4RAM
-}
-
-impl PartialEq for MemoryGeneration {
-    fn eq(&self, other: &MemoryGeneration) -> bool {
-        match (self, other) {
-            (&MemoryGeneration::DDRRAM, &MemoryGeneration::DDRRAM) => true,
-            (&MemoryGeneration::DDR2RAM, &MemoryGeneration::DDR2RAM) => true,
-            (&MemoryGeneration::DDR3RAM, &MemoryGeneration::DDR3RAM) => true,
-            (&MemoryGeneration::DDR4RAM, &MemoryGeneration::DDR4RAM) => true,
-            (_, _) => false,
-        }
-    }
-}
-
-impl PartialOrd for MemoryGeneration {
-    fn partial_cmp(&self, other: &MemoryGeneration) -> Option<Ordering> {
-        match (self, other) {
-            (&MemoryGeneration::DDRRAM, &MemoryGeneration::DDR2RAM) => Some(Ordering::Less),
-            (&MemoryGeneration::DDRRAM, &MemoryGeneration::DDR3RAM) => Some(Ordering::Less),
-            (&MemoryGeneration::DDRRAM, &MemoryGeneration::DDR4RAM) => Some(Ordering::Less),
-            (&MemoryGeneration::DDR2RAM, &MemoryGeneration::DDR3RAM) => Some(Ordering::Less),
-            (&MemoryGeneration::DDR3RAM, &MemoryGeneration::DDR4RAM) => Some(Ordering::Less),
-            (&MemoryGeneration::DDRRAM, &MemoryGeneration::DDRRAM) => Some(Ordering::Equal),
-            (&MemoryGeneration::DDR2RAM, &MemoryGeneration::DDR2RAM) => Some(Ordering::Equal),
-            (&MemoryGeneration::DDR3RAM, &MemoryGeneration::DDR3RAM) => Some(Ordering::Equal),
-            (&MemoryGeneration::DDR4RAM, &MemoryGeneration::DDR4RAM) => Some(Ordering::Equal),
-            (_, _) => None,
-        }
-    }
-}
\ No newline at end of file
+/**
+    Contains the different basic elements
+*/
+extern crate serde;
+extern crate serde_json;
+extern crate regex;
+
+use std::fs::File;
+use std::io::Read;
+use std::cmp::Ordering;
+use std;
+
+
+use serde_json::{Value, Error};
+
+use error::OntologyError;
+use optional;
+use regex::Regex;
+
+/**
+Implemented by all elements which can be compressed by replacing
+their value with the ID of an equal entry in the repository.
+*/
+pub trait Compressible {
+    type Value: PartialEq + Clone;
+
+    fn get_repository_id(&self) -> &Option<i16>;
+    fn set_repository_id(&mut self, id: Option<i16>);
+
+    fn get_repository_value(&self) -> &Option<Self::Value>;
+    fn set_repository_value(&mut self, value: Option<Self::Value>);
+
+    /**
+    Returns further elements of the same type held by this element,
+    e.g. the value list of a `oneOf` request, which are compressed alongside it.
+    */
+    fn get_alternatives_mut(&mut self) -> Vec<&mut Self> { Vec::new() }
+
+    /**
+    Checks if the element holds the value of a repository entry. Quantities compare
+    their values in base units, so that normalized elements find their entry as well.
+    */
+    fn holds_value_of(&self, entry: &Self) -> bool {
+        self.get_repository_value().is_some() && self.get_repository_value() == entry.get_repository_value()
+    }
+
+    /**
+    Sets the value of the element to the value of a repository entry. Quantities
+    express it in the magnitude and unit of the element.
+    */
+    fn restore_value_of(&mut self, entry: &Self) {
+        self.set_repository_value(entry.get_repository_value().clone());
+    }
+}
+
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum Magnitude {
+    pico,
+    nano,
+    micro,
+    milli,
+    none,
+    kilo,
+    mega,
+    giga,
+    tera,
+    peta,
+    kibi,
+    mebi,
+    gibi,
+    tebi,
+    pebi,
+}
+
+impl Default for Magnitude {
+    fn default() -> Magnitude { Magnitude::none }
+}
+
+impl Magnitude {
+    /**
+    Returns the factor of the magnitude as fraction (numerator, denominator),
+    so that quantities can be converted between magnitudes without rounding.
+    */
+    pub fn ratio(&self) -> (u64, u64) {
+        match *self {
+            Magnitude::pico => (1, 1_000_000_000_000),
+            Magnitude::nano => (1, 1_000_000_000),
+            Magnitude::micro => (1, 1_000_000),
+            Magnitude::milli => (1, 1_000),
+            Magnitude::none => (1, 1),
+            Magnitude::kilo => (1_000, 1),
+            Magnitude::mega => (1_000_000, 1),
+            Magnitude::giga => (1_000_000_000, 1),
+            Magnitude::tera => (1_000_000_000_000, 1),
+            Magnitude::peta => (1_000_000_000_000_000, 1),
+            Magnitude::kibi => (1 << 10, 1),
+            Magnitude::mebi => (1 << 20, 1),
+            Magnitude::gibi => (1 << 30, 1),
+            Magnitude::tebi => (1 << 40, 1),
+            Magnitude::pebi => (1 << 50, 1),
+        }
+    }
+
+    pub fn to_float(&self) -> f64 {
+        let (numerator, denominator) = self.ratio();
+        numerator as f64 / denominator as f64
+    }
+}
+
+/**
+Magnitudes a value in base units is expressed in when humanizing it, largest first.
+*/
+const HUMANIZED_MAGNITUDES: [Magnitude; 10] = [Magnitude::peta, Magnitude::tera, Magnitude::giga, Magnitude::mega, Magnitude::kilo,
+                                               Magnitude::none, Magnitude::milli, Magnitude::micro, Magnitude::nano, Magnitude::pico];
+
+/**
+Binary magnitudes additionally considered when humanizing a number of bytes or bits, largest first.
+*/
+const BINARY_MAGNITUDES: [Magnitude; 5] = [Magnitude::pebi, Magnitude::tebi, Magnitude::gibi, Magnitude::mebi, Magnitude::kibi];
+
+/// Every magnitude, each of which needs an entry in the repository.
+pub const MAGNITUDES: [Magnitude; 15] = [Magnitude::pico, Magnitude::nano, Magnitude::micro, Magnitude::milli, Magnitude::none,
+                                         Magnitude::kilo, Magnitude::mega, Magnitude::giga, Magnitude::tera, Magnitude::peta,
+                                         Magnitude::kibi, Magnitude::mebi, Magnitude::gibi, Magnitude::tebi, Magnitude::pebi];
+
+/**
+Units of the quantities held by `IntElement` and `FloatElement`.
+Quantities of the same dimension are converted into each other when
+compared, e.g. bits into bytes, whereas quantities of different
+dimensions, e.g. bytes and Hz, do not satisfy each other.
+A quantity without unit is taken to be given in the base unit of
+the dimension of the quantity it is compared with.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    bytes,
+    bits,
+    Hz,
+    #[serde(rename = "bit/s")]
+    bits_per_second,
+    #[serde(rename = "bytes/s")]
+    bytes_per_second,
+    seconds,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dimension {
+    data,
+    data_rate,
+    frequency,
+    time,
+}
+
+impl Unit {
+    pub fn get_dimension(&self) -> Dimension {
+        match *self {
+            Unit::bytes | Unit::bits => Dimension::data,
+            Unit::bits_per_second | Unit::bytes_per_second => Dimension::data_rate,
+            Unit::Hz => Dimension::frequency,
+            Unit::seconds => Dimension::time,
+        }
+    }
+
+    /**
+    Returns the factor converting the unit into the base unit of its dimension
+    as fraction (numerator, denominator). The base units are bytes, bit/s, Hz and seconds.
+    */
+    pub fn ratio(&self) -> (u64, u64) {
+        match *self {
+            Unit::bits => (1, 8),
+            Unit::bytes_per_second => (8, 1),
+            Unit::bytes | Unit::bits_per_second | Unit::Hz | Unit::seconds => (1, 1),
+        }
+    }
+
+    pub fn to_float(&self) -> f64 {
+        let (numerator, denominator) = self.ratio();
+        numerator as f64 / denominator as f64
+    }
+
+    pub fn is_compatible(&self, other: &Unit) -> bool {
+        self.get_dimension() == other.get_dimension()
+    }
+
+    /**
+    Checks if quantities of the unit are conventionally given with binary magnitudes.
+    */
+    fn is_binary(&self) -> bool {
+        self.get_dimension() == Dimension::data
+    }
+}
+
+impl std::str::FromStr for Unit {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<Unit, OntologyError> {
+        match s {
+            "bytes" => Ok(Unit::bytes),
+            "bits" => Ok(Unit::bits),
+            "Hz" => Ok(Unit::Hz),
+            "bit/s" => Ok(Unit::bits_per_second),
+            "bytes/s" => Ok(Unit::bytes_per_second),
+            "seconds" => Ok(Unit::seconds),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid Unit", s)))
+        }
+    }
+}
+
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Unit::bits_per_second => write!(f, "bit/s"),
+            Unit::bytes_per_second => write!(f, "bytes/s"),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/**
+Checks if two quantities can be compared, i.e. their units are of the same
+dimension or at least one of them has no unit.
+*/
+fn compatible_units(a: &Option<Unit>, b: &Option<Unit>) -> bool {
+    match (a, b) {
+        (&Some(ref unit_a), &Some(ref unit_b)) => unit_a.is_compatible(unit_b),
+        (_, _) => true,
+    }
+}
+
+/**
+Returns the factor converting a quantity given in the magnitude and unit into
+the base unit as fraction (numerator, denominator).
+*/
+fn quantity_ratio(magnitude: &Option<Magnitude>, unit: &Option<Unit>) -> (u64, u64) {
+    let (numerator_m, denominator_m) = magnitude.map(|magnitude| magnitude.ratio()).unwrap_or((1, 1));
+    let (numerator_u, denominator_u) = unit.map(|unit| unit.ratio()).unwrap_or((1, 1));
+    (numerator_m * numerator_u, denominator_m * denominator_u)
+}
+
+impl std::fmt::Display for Magnitude {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl PartialEq for Magnitude {
+    fn eq(&self, other: &Magnitude) -> bool {
+        match (self, other) {
+            (&Magnitude::pico, &Magnitude::pico) => true,
+            (&Magnitude::nano, &Magnitude::nano) => true,
+            (&Magnitude::micro, &Magnitude::micro) => true,
+            (&Magnitude::milli, &Magnitude::milli) => true,
+            (&Magnitude::none, &Magnitude::none) => true,
+            (&Magnitude::kilo, &Magnitude::kilo) => true,
+            (&Magnitude::mega, &Magnitude::mega) => true,
+            (&Magnitude::giga, &Magnitude::giga) => true,
+            (&Magnitude::tera, &Magnitude::tera) => true,
+            (&Magnitude::peta, &Magnitude::peta) => true,
+            (&Magnitude::kibi, &Magnitude::kibi) => true,
+            (&Magnitude::mebi, &Magnitude::mebi) => true,
+            (&Magnitude::gibi, &Magnitude::gibi) => true,
+            (&Magnitude::tebi, &Magnitude::tebi) => true,
+            (&Magnitude::pebi, &Magnitude::pebi) => true,
+            (_, _) => false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MagnitudeElement {
+    id: Option<i16>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    magnitude: Option<Magnitude>,
+}
+
+impl Default for MagnitudeElement {
+    fn default() -> MagnitudeElement {
+        MagnitudeElement { id: None, magnitude: None }
+    }
+}
+
+impl MagnitudeElement {
+    pub fn new(magnitude: Magnitude) -> MagnitudeElement {
+        MagnitudeElement { id: None, magnitude: Some(magnitude) }
+    }
+
+    pub fn get_id(&self) -> &Option<i16> { &self.id }
+
+    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
+
+    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
+
+    pub fn get_magnitude(&self) -> &Option<Magnitude> {
+        &self.magnitude
+    }
+    pub fn get_magnitude_mut(&mut self) -> &mut Option<Magnitude> {
+        &mut self.magnitude
+    }
+
+    pub fn set_magnitude(&mut self, mag: Option<Magnitude>) {
+        self.magnitude = mag;
+    }
+}
+
+impl Compressible for MagnitudeElement {
+    type Value = Magnitude;
+
+    fn get_repository_id(&self) -> &Option<i16> { &self.id }
+    fn set_repository_id(&mut self, id: Option<i16>) { self.id = id; }
+
+    fn get_repository_value(&self) -> &Option<Magnitude> { &self.magnitude }
+    fn set_repository_value(&mut self, value: Option<Magnitude>) { self.magnitude = value; }
+}
+
+impl std::fmt::Display for MagnitudeElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.magnitude, &self.id) {
+            (&Some(ref magnitude), _) => write!(f, "{}", magnitude),
+            (&None, &Some(ref id)) => write!(f, "#{}", id),
+            (&None, &None) => Ok(()),
+        }
+    }
+}
+
+/**
+Measures how close two non-negative quantities are to each other.
+`Return`
+  The ratio of the smaller to the larger value, which is 1 if both are equal,
+  or None if a value is negative or only one of them is zero.
+*/
+fn tightness(a: f64, b: f64) -> Option<f64> {
+    if a == b {
+        Some(1.0)
+    } else if a < 0.0 || b < 0.0 {
+        None
+    } else {
+        Some(a.min(b) / a.max(b))
+    }
+}
+
+/**
+Writes the value of an element, or its repository ID if the element is compressed.
+Used by the Display implementations of the elements.
+*/
+fn fmt_value<T: std::fmt::Display>(f: &mut std::fmt::Formatter, value: &Option<T>, id: &Option<i16>) -> std::fmt::Result {
+    match (value, id) {
+        (&Some(ref value), _) => write!(f, "{}", value),
+        (&None, &Some(ref id)) => write!(f, "#{}", id),
+        (&None, &None) => write!(f, "-"),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BasicElement<T> {
+    id: Option<i16>,
+    #[serde(default = "Option::default", deserialize_with = "optional::deserialize",
+            bound(deserialize = "T: ::serde::Deserialize<'de>"))]
+    value: Option<T>,
+}
+
+
+
+impl <T: std::fmt::Debug> std::fmt::Display for BasicElement<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.value, &self.id) {
+            (&Some(ref value), _) => write!(f, "{:?}", value),
+            (&None, &Some(ref id)) => write!(f, "#{}", id),
+            (&None, &None) => write!(f, "-"),
+        }
+    }
+}
+
+impl <T: PartialEq + Clone> Compressible for BasicElement<T> {
+    type Value = T;
+
+    fn get_repository_id(&self) -> &Option<i16> { &self.id }
+    fn set_repository_id(&mut self, id: Option<i16>) { self.id = id; }
+
+    fn get_repository_value(&self) -> &Option<T> { &self.value }
+    fn set_repository_value(&mut self, value: Option<T>) { self.value = value; }
+}
+
+impl <T: PartialEq> BasicElement<T> {
+    pub fn new(value: T) -> BasicElement<T> {
+        BasicElement { id: None, value: Some(value) }
+    }
+
+
+     pub fn compare(&self, b: &BasicElement<T>) -> bool {
+         self.eq(b)
+    }
+
+    pub fn get_id(&self) -> &Option<i16> { &self.id }
+    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
+    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
+
+    pub fn get_value(&self) -> &Option<T> {
+        &self.value
+    }
+    pub fn get_value_mut(&mut self) -> &mut Option<T> {
+        &mut self.value
+    }
+    pub fn set_value(&mut self, val: Option<T>) {
+        self.value = val;
+    }
+
+}
+
+
+
+/**
+Numeric types the value of an `IntElement`, a `FloatElement` or a range bound can have.
+*/
+pub trait Scalar: Copy {
+    fn to_float(self) -> f64;
+
+    /**
+    Returns the value as integer if it is one, so that it can be compared exactly.
+    */
+    fn to_exact(self) -> Option<u64>;
+
+    /**
+    Converts a value given in the magnitude to base units,
+    None if the result cannot be represented by the type.
+    */
+    fn to_base(self, magnitude: &Magnitude) -> Option<Self>;
+
+    /**
+    Expresses a value given in base units in the largest magnitude which keeps it
+    readable, i.e. integral for integers and at least 1 for floating point numbers.
+    Binary magnitudes are considered as well if `binary` is set, and chosen if
+    they express an integral value by a smaller number.
+    */
+    fn humanize(self, binary: bool) -> (Self, Magnitude);
+
+    /**
+    Converts a value given with the ratio `from` into one given with the ratio `to`,
+    see `quantity_ratio`. None if the result cannot be represented by the type.
+    */
+    fn rescale(self, from: (u64, u64), to: (u64, u64)) -> Option<Self>;
+}
+
+impl Scalar for u64 {
+    fn to_float(self) -> f64 { self as f64 }
+
+    fn to_exact(self) -> Option<u64> { Some(self) }
+
+    fn to_base(self, magnitude: &Magnitude) -> Option<u64> {
+        let (numerator, denominator) = magnitude.ratio();
+        self.checked_mul(numerator).and_then(|value| if value % denominator == 0 { Some(value / denominator) } else { None })
+    }
+
+    fn humanize(self, binary: bool) -> (u64, Magnitude) {
+        let decimal = HUMANIZED_MAGNITUDES.iter().take_while(|magnitude| **magnitude != Magnitude::none);
+        let binary = BINARY_MAGNITUDES.iter().filter(|_| binary);
+        decimal.chain(binary)
+            .map(|magnitude| (magnitude.ratio().0, magnitude))
+            .filter(|&(numerator, _)| self != 0 && self % numerator == 0)
+            .max_by_key(|&(numerator, _)| numerator)
+            .map(|(numerator, magnitude)| (self / numerator, *magnitude))
+            .unwrap_or((self, Magnitude::none))
+    }
+
+    fn rescale(self, from: (u64, u64), to: (u64, u64)) -> Option<u64> {
+        let numerator = (self as u128).checked_mul(from.0 as u128).and_then(|value| value.checked_mul(to.1 as u128));
+        let denominator = from.1 as u128 * to.0 as u128;
+        match numerator {
+            Some(numerator) if numerator % denominator == 0 && numerator / denominator <= u64::max_value() as u128 =>
+                Some((numerator / denominator) as u64),
+            _ => None,
+        }
+    }
+}
+
+impl Scalar for f64 {
+    fn to_float(self) -> f64 { self }
+
+    fn to_exact(self) -> Option<u64> {
+        if self.fract() == 0.0 && self >= 0.0 && self < u64::max_value() as f64 { Some(self as u64) } else { None }
+    }
+
+    fn to_base(self, magnitude: &Magnitude) -> Option<f64> {
+        let (numerator, denominator) = magnitude.ratio();
+        Some(self * numerator as f64 / denominator as f64)
+    }
+
+    fn humanize(self, binary: bool) -> (f64, Magnitude) {
+        if self == 0.0 {
+            return (self, Magnitude::none);
+        }
+        if let Some(exact) = self.to_exact() {
+            let (humanized, magnitude) = exact.humanize(binary);
+            if magnitude != Magnitude::none {
+                return (humanized as f64, magnitude);
+            }
+        }
+        for magnitude in HUMANIZED_MAGNITUDES.iter() {
+            if self.abs() >= magnitude.to_float() {
+                let (numerator, denominator) = magnitude.ratio();
+                return (self * denominator as f64 / numerator as f64, *magnitude);
+            }
+        }
+        (self, Magnitude::none)
+    }
+
+    fn rescale(self, from: (u64, u64), to: (u64, u64)) -> Option<f64> {
+        if from == to {
+            return Some(self);
+        }
+        Some(self * from.0 as f64 / from.1 as f64 * to.1 as f64 / to.0 as f64)
+    }
+}
+
+/**
+Compares two quantities given as value and the ratio converting it to base units.
+Integral values are compared exactly by bringing both to a common denominator,
+so that e.g. 4096 mega equals 4096000 kilo; all other values and products too
+large for 128 bits are compared as floating point numbers.
+*/
+fn compare_quantities<T: Scalar>(a: T, ratio_a: (u64, u64), b: T, ratio_b: (u64, u64)) -> Option<Ordering> {
+    let (numerator_a, denominator_a) = ratio_a;
+    let (numerator_b, denominator_b) = ratio_b;
+    if let (Some(a), Some(b)) = (a.to_exact(), b.to_exact()) {
+        let exact_a = (a as u128).checked_mul(numerator_a as u128).and_then(|a| a.checked_mul(denominator_b as u128));
+        let exact_b = (b as u128).checked_mul(numerator_b as u128).and_then(|b| b.checked_mul(denominator_a as u128));
+        if let (Some(exact_a), Some(exact_b)) = (exact_a, exact_b) {
+            return Some(exact_a.cmp(&exact_b));
+        }
+    }
+    let absolute_a = a.to_float() * numerator_a as f64 / denominator_a as f64;
+    let absolute_b = b.to_float() * numerator_b as f64 / denominator_b as f64;
+    absolute_a.partial_cmp(&absolute_b)
+}
+
+/**
+Checks if two quantities given as value, magnitude and unit are equal, see `compare_quantities`.
+Quantities of incompatible units or without a value are never equal.
+*/
+fn same_quantity<T: Scalar>(a: &Option<T>, magnitude_a: &MagnitudeElement, unit_a: &Option<Unit>,
+                            b: &Option<T>, magnitude_b: &MagnitudeElement, unit_b: &Option<Unit>) -> bool {
+    match (*a, *b) {
+        (Some(a), Some(b)) if compatible_units(unit_a, unit_b) =>
+            compare_quantities(a, quantity_ratio(magnitude_a.get_magnitude(), unit_a),
+                               b, quantity_ratio(magnitude_b.get_magnitude(), unit_b)) == Some(Ordering::Equal),
+        (_, _) => false,
+    }
+}
+
+/**
+Sets a quantity to the value of another one expressed in its own magnitude and unit.
+If the value cannot be expressed that way, the quantity takes the magnitude and unit
+of the other one instead.
+*/
+fn restore_quantity<T: Scalar>(value: &mut Option<T>, magnitude: &mut MagnitudeElement, unit: &mut Option<Unit>,
+                               source: &Option<T>, source_magnitude: &MagnitudeElement, source_unit: &Option<Unit>) {
+    let rescaled = source.and_then(|source| source.rescale(quantity_ratio(source_magnitude.get_magnitude(), source_unit),
+                                                           quantity_ratio(magnitude.get_magnitude(), unit)));
+    match rescaled {
+        Some(rescaled) => *value = Some(rescaled),
+        None => {
+            *value = *source;
+            *magnitude = source_magnitude.clone();
+            *unit = *source_unit;
+        }
+    }
+}
+
+/**
+Converts a value to base units and drops its magnitude. Values with a compressed
+magnitude or which cannot be represented in base units are kept as they are.
+*/
+fn normalize_value<T: Scalar>(value: &mut Option<T>, magnitude: &mut MagnitudeElement) {
+    let normalized = match (*value, magnitude.get_magnitude()) {
+        (Some(value), &Some(ref magnitude)) => value.to_base(magnitude),
+        (_, _) => None,
+    };
+    if let Some(normalized) = normalized {
+        *value = Some(normalized);
+        *magnitude = MagnitudeElement::default();
+    }
+}
+
+/**
+Expresses a value in base units in the largest fitting magnitude.
+Values which are not in base units are kept as they are.
+*/
+fn humanize_value<T: Scalar>(value: &mut Option<T>, magnitude: &mut MagnitudeElement, unit: &Option<Unit>) {
+    let in_base_units = match (magnitude.get_magnitude(), magnitude.get_id()) {
+        (&Some(Magnitude::none), _) | (&None, &None) => true,
+        (_, _) => false,
+    };
+    if let (Some(base), true) = (*value, in_base_units) {
+        let (humanized, humanized_magnitude) = base.humanize(unit.map(|unit| unit.is_binary()).unwrap_or(false));
+        *value = Some(humanized);
+        *magnitude = if humanized_magnitude == Magnitude::none { MagnitudeElement::default() } else { MagnitudeElement::new(humanized_magnitude) };
+    }
+}
+
+/**
+Implemented by the elements holding a quantity, so that all quantities of an
+offer can be converted to base units and back to a readable form.
+*/
+pub trait Quantity {
+    /**
+    Converts the quantity, including the bounds of a range, to base units without magnitude.
+    */
+    fn normalize(&mut self);
+
+    /**
+    Expresses the quantity, given in base units, in the largest fitting magnitude, e.g. for display.
+    */
+    fn humanize(&mut self);
+}
+
+/**
+One end of a range. Bounds are inclusive unless stated otherwise.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Bound<T> {
+    value: T,
+    #[serde(default)]
+    magnitude: MagnitudeElement,
+    #[serde(default = "default_inclusive")]
+    inclusive: bool,
+}
+
+fn default_inclusive() -> bool { true }
+
+impl <T: Scalar> Bound<T> {
+    pub fn new(value: T, magnitude: Option<Magnitude>, inclusive: bool) -> Bound<T> {
+        Bound { value: value, magnitude: MagnitudeElement { id: None, magnitude: magnitude }, inclusive: inclusive }
+    }
+
+    pub fn get_value(&self) -> T { self.value }
+    pub fn set_value(&mut self, value: T) { self.value = value; }
+
+    pub fn get_magnitude(&self) -> &MagnitudeElement { &self.magnitude }
+    pub fn get_magnitude_mut(&mut self) -> &mut MagnitudeElement { &mut self.magnitude }
+
+    pub fn is_inclusive(&self) -> bool { self.inclusive }
+    pub fn set_inclusive(&mut self, inclusive: bool) { self.inclusive = inclusive; }
+
+    /**
+    Returns the value of the bound multiplied by its magnitude.
+    */
+    pub fn get_absolute_value(&self) -> f64 {
+        match *self.magnitude.get_magnitude() {
+            Some(ref magnitude) => self.value.to_float() * magnitude.to_float(),
+            None => self.value.to_float(),
+        }
+    }
+
+    fn normalize(&mut self) {
+        let mut value = Some(self.value);
+        normalize_value(&mut value, &mut self.magnitude);
+        self.value = value.unwrap_or(self.value);
+    }
+
+    fn humanize(&mut self, unit: &Option<Unit>) {
+        let mut value = Some(self.value);
+        humanize_value(&mut value, &mut self.magnitude, unit);
+        self.value = value.unwrap_or(self.value);
+    }
+}
+
+/**
+An interval of values, used by the `range` operator of `IntElement` and `FloatElement`.
+A missing bound leaves the range open on that side.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Range<T> {
+    lower: Option<Bound<T>>,
+    upper: Option<Bound<T>>,
+}
+
+impl <T: Scalar> Range<T> {
+    pub fn new(lower: Option<Bound<T>>, upper: Option<Bound<T>>) -> Range<T> {
+        Range { lower: lower, upper: upper }
+    }
+
+    pub fn get_lower(&self) -> &Option<Bound<T>> { &self.lower }
+    pub fn get_lower_mut(&mut self) -> &mut Option<Bound<T>> { &mut self.lower }
+    pub fn set_lower(&mut self, lower: Option<Bound<T>>) { self.lower = lower; }
+
+    pub fn get_upper(&self) -> &Option<Bound<T>> { &self.upper }
+    pub fn get_upper_mut(&mut self) -> &mut Option<Bound<T>> { &mut self.upper }
+    pub fn set_upper(&mut self, upper: Option<Bound<T>>) { self.upper = upper; }
+
+    /**
+    Checks if a value lies within the range. Values and bounds are compared
+    exactly where possible, see `compare_quantities`.
+    `Parameters`
+    * value:T: Value to check
+    * ratio:(u64, u64): Factor converting the value into the base unit
+    * unit:&Option<Unit>: Unit of the element the range belongs to, i.e. of the bounds
+    */
+    pub fn contains(&self, value: T, ratio: (u64, u64), unit: &Option<Unit>) -> bool {
+        let compare = |bound: &Bound<T>|
+            compare_quantities(value, ratio, bound.value, quantity_ratio(bound.magnitude.get_magnitude(), unit));
+        let above_lower = match self.lower {
+            Some(ref lower) if lower.inclusive => compare(lower).map(|ordering| ordering != Ordering::Less).unwrap_or(false),
+            Some(ref lower) => compare(lower) == Some(Ordering::Greater),
+            None => true,
+        };
+        let below_upper = match self.upper {
+            Some(ref upper) if upper.inclusive => compare(upper).map(|ordering| ordering != Ordering::Greater).unwrap_or(false),
+            Some(ref upper) => compare(upper) == Some(Ordering::Less),
+            None => true,
+        };
+        above_lower && below_upper
+    }
+
+    /**
+    Measures how tightly an absolute value fits the range. The tightest fit is
+    the lower bound, i.e. the least capacity satisfying the request.
+    */
+    fn fitness(&self, value: f64) -> Option<f64> {
+        match (&self.lower, &self.upper) {
+            (&Some(ref lower), _) => tightness(lower.get_absolute_value(), value),
+            (&None, &Some(ref upper)) => tightness(upper.get_absolute_value(), value),
+            (&None, &None) => Some(1.0),
+        }
+    }
+
+    fn get_bounds_mut(&mut self) -> Vec<&mut Bound<T>> {
+        let mut bounds = Vec::new();
+        if let Some(ref mut lower) = self.lower {
+            bounds.push(lower);
+        }
+        if let Some(ref mut upper) = self.upper {
+            bounds.push(upper);
+        }
+        bounds
+    }
+
+    fn get_magnitudes_mut(&mut self) -> Vec<&mut MagnitudeElement> {
+        let mut magnitudes = Vec::new();
+        if let Some(ref mut lower) = self.lower {
+            magnitudes.push(&mut lower.magnitude);
+        }
+        if let Some(ref mut upper) = self.upper {
+            magnitudes.push(&mut upper.magnitude);
+        }
+        magnitudes
+    }
+}
+
+impl <T: std::fmt::Display> std::fmt::Display for Bound<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.value)?;
+        match (self.magnitude.get_magnitude(), self.magnitude.get_id()) {
+            (&None, &None) => Ok(()),
+            (_, _) => write!(f, " {}", self.magnitude),
+        }
+    }
+}
+
+impl <T: std::fmt::Display> std::fmt::Display for Range<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.lower {
+            Some(ref lower) => write!(f, "{}{}", if lower.inclusive { "[" } else { "(" }, lower)?,
+            None => write!(f, "(-inf")?,
+        }
+        match self.upper {
+            Some(ref upper) => write!(f, ", {}{}", upper, if upper.inclusive { "]" } else { ")" }),
+            None => write!(f, ", inf)"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StringElement {
+    id: Option<i16>,
+    value: Option<String>,
+    #[serde(default)]
+    compareOperator: StringOperator,
+    #[serde(default)]
+    values: Vec<StringElement>,
+}
+
+/**
+Operators to compare the value of a request with the value of an offer.
+`oneOf` uses the `values` list of the request instead of its `value`,
+`prefix`, `contains` and `regex` interpret the value of the request as a pattern.
+A `regex` has to match the whole value of the offer.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum StringOperator {
+    eq,
+    lowerCaseEq,
+    notEq,
+    oneOf,
+    prefix,
+    contains,
+    regex,
+}
+
+impl std::str::FromStr for StringOperator {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<StringOperator, OntologyError> {
+        match s {
+            "eq" => Ok(StringOperator::eq),
+            "lowerCaseEq" => Ok(StringOperator::lowerCaseEq),
+            "notEq" => Ok(StringOperator::notEq),
+            "oneOf" => Ok(StringOperator::oneOf),
+            "prefix" => Ok(StringOperator::prefix),
+            "contains" => Ok(StringOperator::contains),
+            "regex" => Ok(StringOperator::regex),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid StringOperator", s)))
+        }
+    }
+}
+
+
+impl std::fmt::Display for StringOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Compressible for StringElement {
+    type Value = String;
+
+    fn get_repository_id(&self) -> &Option<i16> { &self.id }
+    fn set_repository_id(&mut self, id: Option<i16>) { self.id = id; }
+
+    fn get_repository_value(&self) -> &Option<String> { &self.value }
+    fn set_repository_value(&mut self, value: Option<String>) { self.value = value; }
+
+    fn get_alternatives_mut(&mut self) -> Vec<&mut StringElement> { self.values.iter_mut().collect() }
+}
+
+impl std::fmt::Display for StringElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.compareOperator == StringOperator::oneOf {
+            let values: Vec<String> = self.values.iter().map(|value| value.to_string()).collect();
+            return write!(f, "{{{}}}", values.join(", "));
+        }
+        fmt_value(f, &self.value, &self.id)
+    }
+}
+
+impl Default for StringOperator {
+    fn default() -> StringOperator { StringOperator::eq }
+}
+
+impl StringElement {
+    pub fn new(value: &str, op: StringOperator) -> StringElement {
+        StringElement { id: None, value: Some(value.to_string()), compareOperator: op, values: Vec::new() }
+    }
+
+    /**
+    Creates a request element which is satisfied by any of the given values.
+    */
+    pub fn any_of(values: &[&str]) -> StringElement {
+        StringElement {
+            id: None,
+            value: None,
+            compareOperator: StringOperator::oneOf,
+            values: values.iter().map(|value| StringElement::new(value, StringOperator::eq)).collect(),
+        }
+    }
+
+    pub fn get_id(&self) -> &Option<i16> { &self.id }
+    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
+    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
+
+    pub fn get_value(&self) -> &Option<String> {
+        &self.value
+    }
+    pub fn get_value_mut(&mut self) -> &mut Option<String> {
+        &mut self.value
+    }
+    pub fn set_value(&mut self, str: Option<String>) {
+        self.value = str;
+    }
+
+    pub fn get_compare_operator(&self) -> &StringOperator {
+        &self.compareOperator
+    }
+    pub fn get_compare_operator_mut(&mut self) -> &mut StringOperator {
+        &mut self.compareOperator
+    }
+    pub fn set_compare_operator(&mut self, op: StringOperator) {
+        self.compareOperator = op;
+    }
+
+    pub fn get_values(&self) -> &Vec<StringElement> {
+        &self.values
+    }
+    pub fn get_values_mut(&mut self) -> &mut Vec<StringElement> {
+        &mut self.values
+    }
+    pub fn set_values(&mut self, values: Vec<StringElement>) {
+        self.values = values;
+    }
+
+    fn lower_case_eq(&self, b: &StringElement) -> bool {
+        self.value.clone().unwrap().to_lowercase() == b.value.clone().unwrap().to_lowercase()
+    }
+
+
+    fn eq(&self, b: &StringElement) -> bool {
+        self.value == b.value
+    }
+
+    fn one_of(&self, b: &StringElement) -> bool {
+        self.values.iter().any(|value| value.eq(b))
+    }
+
+    fn prefix(&self, b: &StringElement) -> bool {
+        match (&self.value, &b.value) {
+            (&Some(ref prefix), &Some(ref value_b)) => value_b.starts_with(prefix.as_str()),
+            (_, _) => false,
+        }
+    }
+
+    fn contains(&self, b: &StringElement) -> bool {
+        match (&self.value, &b.value) {
+            (&Some(ref part), &Some(ref value_b)) => value_b.contains(part.as_str()),
+            (_, _) => false,
+        }
+    }
+
+    fn regex(&self, b: &StringElement) -> bool {
+        match (&self.value, &b.value) {
+            (&Some(ref pattern), &Some(ref value_b)) => match Regex::new(&format!("^(?:{})$", pattern)) {
+                Ok(regex) => regex.is_match(value_b),
+                Err(_) => false,
+            },
+            (_, _) => false,
+        }
+    }
+
+    pub fn compare(&self, b: &StringElement) -> bool {
+        match self.compareOperator {
+            StringOperator::lowerCaseEq => self.lower_case_eq(b),
+            StringOperator::eq => self.eq(b),
+            StringOperator::notEq => !self.eq(b),
+            StringOperator::oneOf => self.one_of(b),
+            StringOperator::prefix => self.prefix(b),
+            StringOperator::contains => self.contains(b),
+            StringOperator::regex => self.regex(b),
+        }
+    }
+}
+
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct IntElement {
+    id: Option<i16>,
+    value: Option<u64>,
+    #[serde(default)]
+    magnitude: MagnitudeElement,
+    #[serde(default)]
+    compareOperator: IntOperator,
+    #[serde(default)]
+    range: Option<Range<u64>>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    unit: Option<Unit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum IntOperator {
+    leq,
+    geq,
+    eq,
+    le,
+    ge,
+    range,
+}
+
+impl std::str::FromStr for IntOperator {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<IntOperator, OntologyError> {
+        match s {
+            "leq" => Ok(IntOperator::leq),
+            "geq" => Ok(IntOperator::geq),
+            "eq" => Ok(IntOperator::eq),
+            "le" => Ok(IntOperator::le),
+            "ge" => Ok(IntOperator::ge),
+            "range" => Ok(IntOperator::range),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid IntOperator", s)))
+        }
+    }
+}
+
+
+impl IntOperator {
+    /**
+    Checks if the operator holds for the value of a request ordered relative
+    to the value of an offer. The range operator does not order values.
+    */
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match *self {
+            IntOperator::leq => ordering != Ordering::Greater,
+            IntOperator::geq => ordering != Ordering::Less,
+            IntOperator::eq => ordering == Ordering::Equal,
+            IntOperator::le => ordering == Ordering::Less,
+            IntOperator::ge => ordering == Ordering::Greater,
+            IntOperator::range => false,
+        }
+    }
+}
+
+impl std::fmt::Display for IntOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Compressible for IntElement {
+    type Value = u64;
+
+    fn get_repository_id(&self) -> &Option<i16> { &self.id }
+    fn set_repository_id(&mut self, id: Option<i16>) { self.id = id; }
+
+    fn get_repository_value(&self) -> &Option<u64> { &self.value }
+    fn set_repository_value(&mut self, value: Option<u64>) { self.value = value; }
+
+    fn holds_value_of(&self, entry: &Self) -> bool {
+        same_quantity(&self.value, &self.magnitude, &self.unit, &entry.value, &entry.magnitude, &entry.unit)
+    }
+
+    fn restore_value_of(&mut self, entry: &Self) {
+        restore_quantity(&mut self.value, &mut self.magnitude, &mut self.unit, &entry.value, &entry.magnitude, &entry.unit);
+    }
+}
+
+impl Quantity for IntElement {
+    fn normalize(&mut self) {
+        normalize_value(&mut self.value, &mut self.magnitude);
+        if let Some(ref mut range) = self.range {
+            for bound in range.get_bounds_mut() {
+                bound.normalize();
+            }
+        }
+    }
+
+    fn humanize(&mut self) {
+        humanize_value(&mut self.value, &mut self.magnitude, &self.unit);
+        if let Some(ref mut range) = self.range {
+            for bound in range.get_bounds_mut() {
+                bound.humanize(&self.unit);
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for IntElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(ref range) = self.range {
+            write!(f, "{}", range)?;
+        } else {
+            fmt_value(f, &self.value, &self.id)?;
+            match (self.magnitude.get_magnitude(), self.magnitude.get_id()) {
+                (&None, &None) => (),
+                (_, _) => write!(f, " {}", self.magnitude)?,
+            }
+        }
+        match self.unit {
+            Some(ref unit) => write!(f, " {}", unit),
+            None => Ok(()),
+        }
+    }
+}
+
+impl IntElement {
+    pub fn new(value: u64, magnitude: Option<Magnitude>, op: IntOperator) -> IntElement {
+        IntElement { id: None, value: Some(value), magnitude: MagnitudeElement { id: None, magnitude: magnitude }, compareOperator: op, range: None, unit: None }
+    }
+
+    /**
+    Creates a request element which is satisfied by any value within the range.
+    */
+    pub fn with_range(range: Range<u64>) -> IntElement {
+        IntElement { range: Some(range), compareOperator: IntOperator::range, ..IntElement::default() }
+    }
+
+    /**
+    Orders the value of self relative to the value of b, None if a value is missing.
+    */
+    fn ordering(&self, b: &IntElement) -> Option<Ordering> {
+        match (self.value, b.value) {
+            (Some(value_a), Some(value_b)) if compatible_units(&self.unit, &b.unit) =>
+                compare_quantities(value_a, quantity_ratio(self.magnitude.get_magnitude(), &self.unit),
+                                   value_b, quantity_ratio(b.magnitude.get_magnitude(), &b.unit)),
+            (_, _) => None,
+        }
+    }
+
+    pub fn get_unit(&self) -> &Option<Unit> { &self.unit }
+    pub fn set_unit(&mut self, unit: Option<Unit>) { self.unit = unit; }
+    pub fn with_unit(mut self, unit: Unit) -> IntElement { self.unit = Some(unit); self }
+
+    /**
+    Returns the factor converting the unit of the element into the base unit of its dimension.
+    The bounds of a range are given in the unit of the element.
+    */
+    pub fn get_unit_factor(&self) -> f64 {
+        self.unit.map(|unit| unit.to_float()).unwrap_or(1.0)
+    }
+
+    pub fn get_id(&self) -> &Option<i16> { &self.id }
+    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
+    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
+
+    pub fn get_value(&self) -> &Option<u64> {
+        &self.value
+    }
+    pub fn get_value_mut(&mut self) -> &mut Option<u64> {
+        &mut self.value
+    }
+    pub fn set_value(&mut self, val: Option<u64>) {
+        self.value = val;
+    }
+
+    pub fn get_compare_operator(&self) -> &IntOperator {
+        &self.compareOperator
+    }
+    pub fn get_compare_operator_mut(&mut self) -> &mut IntOperator {
+        &mut self.compareOperator
+    }
+    pub fn set_compare_operator(&mut self, op: IntOperator) {
+        self.compareOperator = op;
+    }
+
+    pub fn get_magnitude(&self) -> &MagnitudeElement {
+        &self.magnitude
+    }
+    pub fn get_magnitude_mut(&mut self) -> &mut MagnitudeElement {
+        &mut self.magnitude
+    }
+    pub fn set_magnitude(&mut self, val: MagnitudeElement) {
+        self.magnitude = val;
+    }
+
+    /**
+    Returns the value in the base unit of its dimension, i.e. multiplied by its
+    magnitude and unit factor, or None if the element has no value.
+    */
+    pub fn get_absolute_value(&self) -> Option<f64> {
+        let (numerator, denominator) = quantity_ratio(self.magnitude.get_magnitude(), &self.unit);
+        self.value.map(|value| value.to_float() * numerator as f64 / denominator as f64)
+    }
+
+    /**
+    Returns a copy of the element holding the given absolute value, expressed
+    in the magnitude and unit of the element and rounded down to a whole number.
+    */
+    pub fn with_absolute_value(&self, absolute: f64) -> IntElement {
+        let (numerator, denominator) = quantity_ratio(self.magnitude.get_magnitude(), &self.unit);
+        let mut element = self.clone();
+        element.value = Some((absolute * denominator as f64 / numerator as f64).floor().max(0.0) as u64);
+        element
+    }
+
+    /**
+    Measures how tightly the value offered in b fits the value requested by self.
+    `Parameters`
+    * b:&IntElement: Element of the offer
+    `Return`
+      A value in [0, 1], where 1 means the offer matches the request exactly,
+      or None if a value is missing, e.g. because the element is compressed,
+      or the units of both elements are incompatible.
+    */
+    pub fn fitness(&self, b: &IntElement) -> Option<f64> {
+        if !compatible_units(&self.unit, &b.unit) {
+            return None;
+        }
+        if let Some(ref range) = self.range {
+            return b.get_absolute_value().and_then(|value_b| range.fitness(value_b / self.get_unit_factor()));
+        }
+        match (self.get_absolute_value(), b.get_absolute_value()) {
+            (Some(value_a), Some(value_b)) => tightness(value_a, value_b),
+            (_, _) => None,
+        }
+    }
+
+    /**
+    Checks if the value offered in b lies within the range requested by self.
+    */
+    fn in_range(&self, b: &IntElement) -> bool {
+        match (&self.range, b.value) {
+            (&Some(ref range), Some(value_b)) if compatible_units(&self.unit, &b.unit) =>
+                range.contains(value_b, quantity_ratio(b.magnitude.get_magnitude(), &b.unit), &self.unit),
+            (_, _) => false,
+        }
+    }
+
+    pub fn get_range(&self) -> &Option<Range<u64>> {
+        &self.range
+    }
+    pub fn get_range_mut(&mut self) -> &mut Option<Range<u64>> {
+        &mut self.range
+    }
+    pub fn set_range(&mut self, range: Option<Range<u64>>) {
+        self.range = range;
+    }
+
+    /**
+    Returns the magnitude of the value together with the magnitudes of the range bounds,
+    so that all of them can be compressed or decompressed.
+    */
+    pub fn get_magnitudes_mut(&mut self) -> Vec<&mut MagnitudeElement> {
+        let mut magnitudes = vec![&mut self.magnitude];
+        if let Some(ref mut range) = self.range {
+            magnitudes.extend(range.get_magnitudes_mut());
+        }
+        magnitudes
+    }
+
+    pub fn compare(&self, b: &IntElement) -> bool {
+        match self.compareOperator {
+            IntOperator::range => self.in_range(b),
+            ref operator => self.ordering(b).map(|ordering| operator.accepts(ordering)).unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FloatElement {
+    id: Option<i16>,
+    value: Option<f64>,
+    #[serde(default)]
+    magnitude: MagnitudeElement,
+    #[serde(default)]
+    compareOperator: FloatOperator,
+    #[serde(default)]
+    range: Option<Range<f64>>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    unit: Option<Unit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum FloatOperator {
+    leq,
+    geq,
+    eq,
+    le,
+    ge,
+    range,
+}
+
+impl Default for FloatOperator {
+    fn default() -> FloatOperator { FloatOperator::eq }
+}
+
+impl Default for IntOperator {
+    fn default() -> IntOperator { IntOperator::eq }
+}
+
+
+impl std::str::FromStr for FloatOperator {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<FloatOperator, OntologyError> {
+        match s {
+            "leq" => Ok(FloatOperator::leq),
+            "geq" => Ok(FloatOperator::geq),
+            "eq" => Ok(FloatOperator::eq),
+            "le" => Ok(FloatOperator::le),
+            "ge" => Ok(FloatOperator::ge),
+            "range" => Ok(FloatOperator::range),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid FloatOperator", s)))
+        }
+    }
+}
+
+impl FloatOperator {
+    /**
+    Checks if the operator holds for the value of a request ordered relative
+    to the value of an offer. The range operator does not order values.
+    */
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match *self {
+            FloatOperator::leq => ordering != Ordering::Greater,
+            FloatOperator::geq => ordering != Ordering::Less,
+            FloatOperator::eq => ordering == Ordering::Equal,
+            FloatOperator::le => ordering == Ordering::Less,
+            FloatOperator::ge => ordering == Ordering::Greater,
+            FloatOperator::range => false,
+        }
+    }
+}
+
+impl std::fmt::Display for FloatOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Compressible for FloatElement {
+    type Value = f64;
+
+    fn get_repository_id(&self) -> &Option<i16> { &self.id }
+    fn set_repository_id(&mut self, id: Option<i16>) { self.id = id; }
+
+    fn get_repository_value(&self) -> &Option<f64> { &self.value }
+    fn set_repository_value(&mut self, value: Option<f64>) { self.value = value; }
+
+    fn holds_value_of(&self, entry: &Self) -> bool {
+        same_quantity(&self.value, &self.magnitude, &self.unit, &entry.value, &entry.magnitude, &entry.unit)
+    }
+
+    fn restore_value_of(&mut self, entry: &Self) {
+        restore_quantity(&mut self.value, &mut self.magnitude, &mut self.unit, &entry.value, &entry.magnitude, &entry.unit);
+    }
+}
+
+impl Quantity for FloatElement {
+    fn normalize(&mut self) {
+        normalize_value(&mut self.value, &mut self.magnitude);
+        if let Some(ref mut range) = self.range {
+            for bound in range.get_bounds_mut() {
+                bound.normalize();
+            }
+        }
+    }
+
+    fn humanize(&mut self) {
+        humanize_value(&mut self.value, &mut self.magnitude, &self.unit);
+        if let Some(ref mut range) = self.range {
+            for bound in range.get_bounds_mut() {
+                bound.humanize(&self.unit);
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for FloatElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(ref range) = self.range {
+            write!(f, "{}", range)?;
+        } else {
+            fmt_value(f, &self.value, &self.id)?;
+            match (self.magnitude.get_magnitude(), self.magnitude.get_id()) {
+                (&None, &None) => (),
+                (_, _) => write!(f, " {}", self.magnitude)?,
+            }
+        }
+        match self.unit {
+            Some(ref unit) => write!(f, " {}", unit),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FloatElement {
+    pub fn new(value: f64, magnitude: Option<Magnitude>, op: FloatOperator) -> FloatElement {
+        FloatElement { id: None, value: Some(value), magnitude: MagnitudeElement { id: None, magnitude: magnitude }, compareOperator: op, range: None, unit: None }
+    }
+
+    /**
+    Creates a request element which is satisfied by any value within the range.
+    */
+    pub fn with_range(range: Range<f64>) -> FloatElement {
+        FloatElement { range: Some(range), compareOperator: FloatOperator::range, ..FloatElement::default() }
+    }
+
+    /**
+    Orders the value of self relative to the value of b, None if a value is missing.
+    */
+    fn ordering(&self, b: &FloatElement) -> Option<Ordering> {
+        match (self.value, b.value) {
+            (Some(value_a), Some(value_b)) if compatible_units(&self.unit, &b.unit) =>
+                compare_quantities(value_a, quantity_ratio(self.magnitude.get_magnitude(), &self.unit),
+                                   value_b, quantity_ratio(b.magnitude.get_magnitude(), &b.unit)),
+            (_, _) => None,
+        }
+    }
+
+    pub fn get_unit(&self) -> &Option<Unit> { &self.unit }
+    pub fn set_unit(&mut self, unit: Option<Unit>) { self.unit = unit; }
+    pub fn with_unit(mut self, unit: Unit) -> FloatElement { self.unit = Some(unit); self }
+
+    /**
+    Returns the factor converting the unit of the element into the base unit of its dimension.
+    The bounds of a range are given in the unit of the element.
+    */
+    pub fn get_unit_factor(&self) -> f64 {
+        self.unit.map(|unit| unit.to_float()).unwrap_or(1.0)
+    }
+
+    /**
+    Returns the value in the base unit of its dimension, i.e. multiplied by its
+    magnitude and unit factor, or None if the element has no value.
+    */
+    pub fn get_absolute_value(&self) -> Option<f64> {
+        let (numerator, denominator) = quantity_ratio(self.magnitude.get_magnitude(), &self.unit);
+        self.value.map(|value| value.to_float() * numerator as f64 / denominator as f64)
+    }
+
+    /**
+    Returns a copy of the element holding the given absolute value,
+    expressed in the magnitude and unit of the element.
+    */
+    pub fn with_absolute_value(&self, absolute: f64) -> FloatElement {
+        let (numerator, denominator) = quantity_ratio(self.magnitude.get_magnitude(), &self.unit);
+        let mut element = self.clone();
+        element.value = Some((absolute * denominator as f64 / numerator as f64));
+        element
+    }
+
+    /**
+    Measures how tightly the value offered in b fits the value requested by self.
+    `Parameters`
+    * b:&FloatElement: Element of the offer
+    `Return`
+      A value in [0, 1], where 1 means the offer matches the request exactly,
+      or None if a value is missing, e.g. because the element is compressed,
+      or the units of both elements are incompatible.
+    */
+    pub fn fitness(&self, b: &FloatElement) -> Option<f64> {
+        if !compatible_units(&self.unit, &b.unit) {
+            return None;
+        }
+        if let Some(ref range) = self.range {
+            return b.get_absolute_value().and_then(|value_b| range.fitness(value_b / self.get_unit_factor()));
+        }
+        match (self.get_absolute_value(), b.get_absolute_value()) {
+            (Some(value_a), Some(value_b)) => tightness(value_a, value_b),
+            (_, _) => None,
+        }
+    }
+
+    /**
+    Checks if the value offered in b lies within the range requested by self.
+    */
+    fn in_range(&self, b: &FloatElement) -> bool {
+        match (&self.range, b.value) {
+            (&Some(ref range), Some(value_b)) if compatible_units(&self.unit, &b.unit) =>
+                range.contains(value_b, quantity_ratio(b.magnitude.get_magnitude(), &b.unit), &self.unit),
+            (_, _) => false,
+        }
+    }
+
+    pub fn get_range(&self) -> &Option<Range<f64>> {
+        &self.range
+    }
+    pub fn get_range_mut(&mut self) -> &mut Option<Range<f64>> {
+        &mut self.range
+    }
+    pub fn set_range(&mut self, range: Option<Range<f64>>) {
+        self.range = range;
+    }
+
+    /**
+    Returns the magnitude of the value together with the magnitudes of the range bounds,
+    so that all of them can be compressed or decompressed.
+    */
+    pub fn get_magnitudes_mut(&mut self) -> Vec<&mut MagnitudeElement> {
+        let mut magnitudes = vec![&mut self.magnitude];
+        if let Some(ref mut range) = self.range {
+            magnitudes.extend(range.get_magnitudes_mut());
+        }
+        magnitudes
+    }
+
+    pub fn compare(&self, b: &FloatElement) -> bool {
+        match self.compareOperator {
+            FloatOperator::range => self.in_range(b),
+            ref operator => self.ordering(b).map(|ordering| operator.accepts(ordering)).unwrap_or(false),
+        }
+    }
+
+
+    pub fn get_id(&self) -> &Option<i16> { &self.id }
+
+    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
+
+    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
+
+    pub fn get_value(&self) -> &Option<f64> {
+        &self.value
+    }
+
+    pub fn get_value_mut(&mut self) -> &mut Option<f64> {
+        &mut self.value
+    }
+
+    pub fn set_value(&mut self, val: Option<f64>) {
+        self.value = val;
+    }
+
+    pub fn get_compare_operator(&self) -> &FloatOperator {
+        &self.compareOperator
+    }
+
+    pub fn get_compare_operator_mut(&mut self) -> &mut FloatOperator {
+        &mut self.compareOperator
+    }
+
+    pub fn set_compare_operator(&mut self, op: FloatOperator) {
+        self.compareOperator = op;
+    }
+
+    pub fn get_magnitude(&self) -> &MagnitudeElement {
+        &self.magnitude
+    }
+
+    pub fn get_magnitude_mut(&mut self) -> &mut MagnitudeElement {
+        &mut self.magnitude
+    }
+
+    pub fn set_magnitude(&mut self, val: MagnitudeElement) {
+        self.magnitude = val;
+    }
+}
+
+
+/**
+A parsed dotted version number such as "16.04" or "1.2.3-rc1".
+Missing components count as zero, so "16.4" and "16.04.0" are equal.
+A version with pre-release identifiers precedes the same version without them,
+build metadata after a "+" is ignored.
+*/
+#[derive(Debug, Clone)]
+pub struct Version {
+    components: Vec<u64>,
+    pre_release: Vec<String>,
+}
+
+impl std::str::FromStr for Version {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<Version, OntologyError> {
+        let trimmed = s.trim().trim_start_matches(|c| c == 'v' || c == 'V');
+        let without_build = trimmed.splitn(2, '+').next().unwrap_or("");
+        let mut parts = without_build.splitn(2, '-');
+        let core = parts.next().unwrap_or("");
+
+        let mut components = Vec::new();
+        for component in core.split('.') {
+            match component.parse::<u64>() {
+                Ok(number) => components.push(number),
+                Err(_) => return Err(OntologyError::InvalidVersion(s.to_string())),
+            }
+        }
+        let pre_release = match parts.next() {
+            Some(pre_release) => pre_release.split('.').map(|identifier| identifier.to_string()).collect(),
+            None => Vec::new(),
+        };
+        Ok(Version { components: components, pre_release: pre_release })
+    }
+}
+
+impl Version {
+    fn component(&self, i: usize) -> u64 {
+        *self.components.get(i).unwrap_or(&0)
+    }
+
+    /**
+    Returns the version following the given component,
+    e.g. bumping component 1 of "2.1.3" yields "2.2".
+    */
+    fn bump(&self, i: usize) -> Version {
+        let mut components: Vec<u64> = (0..i).map(|j| self.component(j)).collect();
+        components.push(self.component(i) + 1);
+        Version { components: components, pre_release: Vec::new() }
+    }
+
+    /**
+    Returns the version following this one in its last component,
+    e.g. "2.1" becomes "2.2" and "2.9" becomes "2.10".
+    */
+    pub fn next(&self) -> Version {
+        self.bump(self.components.len() - 1)
+    }
+
+    /**
+    Returns the exclusive upper bound of a caret requirement: the first
+    non-zero component may not change, e.g. ^2.1 allows [2.1, 3) and ^0.2 allows [0.2, 0.3).
+    */
+    pub fn caret_upper_bound(&self) -> Version {
+        match self.components.iter().position(|&component| component != 0) {
+            Some(i) => self.bump(i),
+            None => self.bump(self.components.len() - 1),
+        }
+    }
+
+    /**
+    Returns the exclusive upper bound of a tilde requirement: the minor version
+    may not change if given, e.g. ~2.1.3 allows [2.1.3, 2.2) and ~2 allows [2, 3).
+    */
+    pub fn tilde_upper_bound(&self) -> Version {
+        if self.components.len() > 1 { self.bump(1) } else { self.bump(0) }
+    }
+}
+
+/**
+Compares two pre-release identifiers: numeric identifiers numerically and
+before alphanumeric ones, which are compared as strings.
+*/
+fn cmp_identifier(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        let length = std::cmp::max(self.components.len(), other.components.len());
+        for i in 0..length {
+            match self.component(i).cmp(&other.component(i)) {
+                Ordering::Equal => (),
+                ordering => return ordering,
+            }
+        }
+        match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                for (a, b) in self.pre_release.iter().zip(other.pre_release.iter()) {
+                    match cmp_identifier(a, b) {
+                        Ordering::Equal => (),
+                        ordering => return ordering,
+                    }
+                }
+                self.pre_release.len().cmp(&other.pre_release.len())
+            }
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for Version {}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let components: Vec<String> = self.components.iter().map(|component| component.to_string()).collect();
+        write!(f, "{}", components.join("."))?;
+        if !self.pre_release.is_empty() {
+            write!(f, "-{}", self.pre_release.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl serde::Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/**
+Deserializes a version given either as string or, as older documents did, as number.
+*/
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Version, D::Error> {
+        struct VersionVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for VersionVisitor {
+            type Value = Version;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a version string or number")
+            }
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Version, E> { value.parse().map_err(E::custom) }
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Version, E> { self.visit_str(&value.to_string()) }
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Version, E> { self.visit_str(&value.to_string()) }
+            fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Version, E> { self.visit_str(&value.to_string()) }
+        }
+
+        deserializer.deserialize_any(VersionVisitor)
+    }
+}
+
+/**
+Deserializes a version given either as string or, as older offers did, as number.
+*/
+fn deserialize_version<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+    where D: serde::Deserializer<'de> {
+    struct VersionVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for VersionVisitor {
+        type Value = Option<String>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "a version string or number")
+        }
+        fn visit_none<E: serde::de::Error>(self) -> Result<Option<String>, E> { Ok(None) }
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Option<String>, E> { Ok(None) }
+        fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Option<String>, D::Error> {
+            deserializer.deserialize_any(VersionVisitor)
+        }
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Option<String>, E> { Ok(Some(value.to_string())) }
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Option<String>, E> { Ok(Some(value.to_string())) }
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Option<String>, E> { Ok(Some(value.to_string())) }
+        fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Option<String>, E> { Ok(Some(value.to_string())) }
+    }
+
+    deserializer.deserialize_option(VersionVisitor)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VersionElement {
+    #[serde(default, deserialize_with = "deserialize_version")]
+    value: Option<String>,
+    #[serde(default)]
+    compareOperator: VersionOperator,
+}
+
+/**
+Operators to compare the version of a request with the version of an offer.
+As for the numeric elements, `leq` means the requested version is lower than or
+equal to the offered one, i.e. "offer >= request". `caret` and `tilde` accept
+the offered version if it lies in the caret or tilde range of the requested one.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum VersionOperator {
+    leq,
+    geq,
+    eq,
+    le,
+    ge,
+    caret,
+    tilde,
+}
+
+impl Default for VersionOperator {
+    fn default() -> VersionOperator { VersionOperator::eq }
+}
+
+impl std::str::FromStr for VersionOperator {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<VersionOperator, OntologyError> {
+        match s {
+            "leq" => Ok(VersionOperator::leq),
+            "geq" => Ok(VersionOperator::geq),
+            "eq" => Ok(VersionOperator::eq),
+            "le" => Ok(VersionOperator::le),
+            "ge" => Ok(VersionOperator::ge),
+            "caret" => Ok(VersionOperator::caret),
+            "tilde" => Ok(VersionOperator::tilde),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid VersionOperator", s)))
+        }
+    }
+}
+
+impl std::fmt::Display for VersionOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::fmt::Display for VersionElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt_value(f, &self.value, &None)
+    }
+}
+
+impl VersionElement {
+    pub fn new(value: &str, op: VersionOperator) -> VersionElement {
+        VersionElement { value: Some(value.to_string()), compareOperator: op }
+    }
+
+    pub fn get_value(&self) -> &Option<String> {
+        &self.value
+    }
+    pub fn get_value_mut(&mut self) -> &mut Option<String> {
+        &mut self.value
+    }
+    pub fn set_value(&mut self, val: Option<String>) {
+        self.value = val;
+    }
+
+    pub fn get_compare_operator(&self) -> &VersionOperator {
+        &self.compareOperator
+    }
+    pub fn get_compare_operator_mut(&mut self) -> &mut VersionOperator {
+        &mut self.compareOperator
+    }
+    pub fn set_compare_operator(&mut self, op: VersionOperator) {
+        self.compareOperator = op;
+    }
+
+    /**
+    Parses the value of the element.
+    `Return`
+      The parsed version, Err(InvalidVersion) if the value is no version
+      or None if the element has no value.
+    */
+    pub fn get_version(&self) -> Option<Result<Version, OntologyError>> {
+        self.value.as_ref().map(|value| value.parse::<Version>())
+    }
+
+    /**
+    Checks if the version offered in b satisfies the version requested by self.
+    Elements without a value or with an unparsable value never match.
+    */
+    pub fn compare(&self, b: &VersionElement) -> bool {
+        let (version_a, version_b) = match (self.get_version(), b.get_version()) {
+            (Some(Ok(version_a)), Some(Ok(version_b))) => (version_a, version_b),
+            (_, _) => return false,
+        };
+        match self.compareOperator {
+            VersionOperator::leq => version_a <= version_b,
+            VersionOperator::geq => version_a >= version_b,
+            VersionOperator::eq => version_a == version_b,
+            VersionOperator::le => version_a < version_b,
+            VersionOperator::ge => version_a > version_b,
+            VersionOperator::caret => version_b >= version_a && version_b < version_a.caret_upper_bound(),
+            VersionOperator::tilde => version_b >= version_a && version_b < version_a.tilde_upper_bound(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum DiskType {
+    HDD,
+    SSD
+}
+
+/// Every disk type, each of which needs an entry in the repository.
+pub const DISK_TYPES: [DiskType; 2] = [DiskType::HDD, DiskType::SSD];
+
+impl std::str::FromStr for DiskType {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<DiskType, OntologyError> {
+        match s {
+            "HDD" => Ok(DiskType::HDD),
+            "SSD" => Ok(DiskType::SSD),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid DiskType", s)))
+        }
+    }
+}
+
+impl PartialEq for DiskType {
+    fn eq(&self, other: &DiskType) -> bool {
+        match (self, other) {
+            (&DiskType::HDD, &DiskType::HDD) => true,
+            (&DiskType::SSD, &DiskType::SSD) => true,
+            (_, _) => false,
+        }
+    }
+}
+
+
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum InstructionSet {
+    MMX,
+    SSE,
+    SSE2,
+    SSE3,
+    SSSE3,
+    SSE4a,
+    SSE4_1,
+    SSE4_2,
+    AVX,
+    AVX2,
+    FMA3,
+    F16C,
+    AES,
+    AES_NI,
+    BMI1,
+    BMI2,
+    AVX512F,
+    AVX512CD,
+    AVX512BW,
+    AVX512DQ,
+    AVX512VL,
+    SHA,
+    NEON,
+    SVE,
+    SVE2,
+    RVV
+}
+
+/// Every instruction set, each of which needs an entry in the repository.
+pub const INSTRUCTION_SETS: [InstructionSet; 26] = [
+    InstructionSet::MMX, InstructionSet::SSE, InstructionSet::SSE2, InstructionSet::SSE3,
+    InstructionSet::SSSE3, InstructionSet::SSE4a, InstructionSet::SSE4_1, InstructionSet::SSE4_2,
+    InstructionSet::AVX, InstructionSet::AVX2, InstructionSet::FMA3, InstructionSet::F16C,
+    InstructionSet::AES, InstructionSet::AES_NI, InstructionSet::BMI1, InstructionSet::BMI2,
+    InstructionSet::AVX512F, InstructionSet::AVX512CD, InstructionSet::AVX512BW, InstructionSet::AVX512DQ,
+    InstructionSet::AVX512VL, InstructionSet::SHA, InstructionSet::NEON, InstructionSet::SVE,
+    InstructionSet::SVE2, InstructionSet::RVV
+];
+
+impl InstructionSet {
+    /**
+    Returns the instruction sets which every CPU supporting this set supports as well,
+    e.g. AVX2 implies AVX. Only the direct implications are returned, see `implies_set`.
+    */
+    pub fn implies(&self) -> Vec<InstructionSet> {
+        match *self {
+            InstructionSet::MMX => vec![],
+            InstructionSet::SSE => vec![InstructionSet::MMX],
+            InstructionSet::SSE2 => vec![InstructionSet::SSE],
+            InstructionSet::SSE3 => vec![InstructionSet::SSE2],
+            InstructionSet::SSSE3 => vec![InstructionSet::SSE3],
+            InstructionSet::SSE4a => vec![InstructionSet::SSE3],
+            InstructionSet::SSE4_1 => vec![InstructionSet::SSSE3],
+            InstructionSet::SSE4_2 => vec![InstructionSet::SSE4_1],
+            InstructionSet::AVX => vec![InstructionSet::SSE4_2],
+            InstructionSet::AVX2 => vec![InstructionSet::AVX],
+            InstructionSet::FMA3 => vec![InstructionSet::AVX],
+            InstructionSet::F16C => vec![InstructionSet::AVX],
+            InstructionSet::AES => vec![],
+            InstructionSet::AES_NI => vec![InstructionSet::AES, InstructionSet::SSE2],
+            InstructionSet::BMI1 => vec![],
+            InstructionSet::BMI2 => vec![InstructionSet::BMI1],
+            InstructionSet::AVX512F => vec![InstructionSet::AVX2, InstructionSet::FMA3, InstructionSet::F16C],
+            InstructionSet::AVX512CD => vec![InstructionSet::AVX512F],
+            InstructionSet::AVX512BW => vec![InstructionSet::AVX512F],
+            InstructionSet::AVX512DQ => vec![InstructionSet::AVX512F],
+            InstructionSet::AVX512VL => vec![InstructionSet::AVX512F],
+            InstructionSet::SHA => vec![],
+            InstructionSet::NEON => vec![],
+            InstructionSet::SVE => vec![InstructionSet::NEON],
+            InstructionSet::SVE2 => vec![InstructionSet::SVE],
+            InstructionSet::RVV => vec![],
+        }
+    }
+
+    /**
+    Checks whether a CPU supporting this instruction set also supports the other one,
+    either because both are the same or because this set implies it transitively.
+    `Parameters`
+    * other:&InstructionSet: Requested instruction set
+    */
+    pub fn implies_set(&self, other: &InstructionSet) -> bool {
+        self == other || self.implies().iter().any(|implied| implied.implies_set(other))
+    }
+}
+
+impl std::str::FromStr for InstructionSet {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<InstructionSet, OntologyError> {
+        match s {
+            "MMX" => Ok(InstructionSet::MMX),
+            "SSE" => Ok(InstructionSet::SSE),
+            "SSE2" => Ok(InstructionSet::SSE2),
+            "SSE3" => Ok(InstructionSet::SSE3),
+            "SSSE3" => Ok(InstructionSet::SSSE3),
+            "SSE4a" => Ok(InstructionSet::SSE4a),
+            "SSE4.1" => Ok(InstructionSet::SSE4_1),
+            "SSE4.2" => Ok(InstructionSet::SSE4_2),
+            "AVX" => Ok(InstructionSet::AVX),
+            "AVX2" => Ok(InstructionSet::AVX2),
+            "FMA3" => Ok(InstructionSet::FMA3),
+            "F16C" => Ok(InstructionSet::F16C),
+            "AES" => Ok(InstructionSet::AES),
+            "AES-NI" => Ok(InstructionSet::AES_NI),
+            "BMI1" => Ok(InstructionSet::BMI1),
+            "BMI2" => Ok(InstructionSet::BMI2),
+            "AVX512F" => Ok(InstructionSet::AVX512F),
+            "AVX512CD" => Ok(InstructionSet::AVX512CD),
+            "AVX512BW" => Ok(InstructionSet::AVX512BW),
+            "AVX512DQ" => Ok(InstructionSet::AVX512DQ),
+            "AVX512VL" => Ok(InstructionSet::AVX512VL),
+            "SHA" => Ok(InstructionSet::SHA),
+            "NEON" => Ok(InstructionSet::NEON),
+            "SVE" => Ok(InstructionSet::SVE),
+            "SVE2" => Ok(InstructionSet::SVE2),
+            "RVV" => Ok(InstructionSet::RVV),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid InstructionSet", s)))
+        }
+    }
+}
+
+impl PartialEq for InstructionSet {
+    fn eq(&self, other: &InstructionSet) -> bool {
+        match (self, other) {
+            (&InstructionSet::MMX, &InstructionSet::MMX) => true,
+            (&InstructionSet::SSE, &InstructionSet::SSE) => true,
+            (&InstructionSet::SSE2, &InstructionSet::SSE2) => true,
+            (&InstructionSet::SSE3, &InstructionSet::SSE3) => true,
+            (&InstructionSet::SSSE3, &InstructionSet::SSSE3) => true,
+            (&InstructionSet::SSE4a, &InstructionSet::SSE4a) => true,
+            (&InstructionSet::SSE4_1, &InstructionSet::SSE4_1) => true,
+            (&InstructionSet::SSE4_2, &InstructionSet::SSE4_2) => true,
+            (&InstructionSet::AVX, &InstructionSet::AVX) => true,
+            (&InstructionSet::AVX2, &InstructionSet::AVX2) => true,
+            (&InstructionSet::FMA3, &InstructionSet::FMA3) => true,
+            (&InstructionSet::F16C, &InstructionSet::F16C) => true,
+            (&InstructionSet::AES, &InstructionSet::AES) => true,
+            (&InstructionSet::AES_NI, &InstructionSet::AES_NI) => true,
+            (&InstructionSet::BMI1, &InstructionSet::BMI1) => true,
+            (&InstructionSet::BMI2, &InstructionSet::BMI2) => true,
+            (&InstructionSet::AVX512F, &InstructionSet::AVX512F) => true,
+            (&InstructionSet::AVX512CD, &InstructionSet::AVX512CD) => true,
+            (&InstructionSet::AVX512BW, &InstructionSet::AVX512BW) => true,
+            (&InstructionSet::AVX512DQ, &InstructionSet::AVX512DQ) => true,
+            (&InstructionSet::AVX512VL, &InstructionSet::AVX512VL) => true,
+            (&InstructionSet::SHA, &InstructionSet::SHA) => true,
+            (&InstructionSet::NEON, &InstructionSet::NEON) => true,
+            (&InstructionSet::SVE, &InstructionSet::SVE) => true,
+            (&InstructionSet::SVE2, &InstructionSet::SVE2) => true,
+            (&InstructionSet::RVV, &InstructionSet::RVV) => true,
+            (_, _) => false,
+        }
+    }
+}
+
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum MemoryGeneration {
+    DDRRAM,
+    DDR2RAM,
+    DDR3RAM,
+    DDR4RAM,
+    DDR5RAM,
+    LPDDR4RAM,
+    LPDDR5RAM,
+    HBM,
+    HBM2,
+    HBM3
+}
+
+/// Every memory generation, each of which needs an entry in the repository.
+pub const MEMORY_GENERATIONS: [MemoryGeneration; 10] = [
+    MemoryGeneration::DDRRAM, MemoryGeneration::DDR2RAM, MemoryGeneration::DDR3RAM, MemoryGeneration::DDR4RAM,
+    MemoryGeneration::DDR5RAM, MemoryGeneration::LPDDR4RAM, MemoryGeneration::LPDDR5RAM,
+    MemoryGeneration::HBM, MemoryGeneration::HBM2, MemoryGeneration::HBM3
+];
+
+/**
+Families of memory generations. Generations of different families, e.g. DDR DIMMs
+and soldered LPDDR or stacked HBM, are not interchangeable.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MemoryFamily {
+    DDR,
+    LPDDR,
+    HBM,
+}
+
+impl MemoryGeneration {
+    /**
+    Returns the family of the generation and its number within the family.
+    */
+    fn family_generation(&self) -> (MemoryFamily, u8) {
+        match *self {
+            MemoryGeneration::DDRRAM => (MemoryFamily::DDR, 1),
+            MemoryGeneration::DDR2RAM => (MemoryFamily::DDR, 2),
+            MemoryGeneration::DDR3RAM => (MemoryFamily::DDR, 3),
+            MemoryGeneration::DDR4RAM => (MemoryFamily::DDR, 4),
+            MemoryGeneration::DDR5RAM => (MemoryFamily::DDR, 5),
+            MemoryGeneration::LPDDR4RAM => (MemoryFamily::LPDDR, 4),
+            MemoryGeneration::LPDDR5RAM => (MemoryFamily::LPDDR, 5),
+            MemoryGeneration::HBM => (MemoryFamily::HBM, 1),
+            MemoryGeneration::HBM2 => (MemoryFamily::HBM, 2),
+            MemoryGeneration::HBM3 => (MemoryFamily::HBM, 3),
+        }
+    }
+
+    pub fn get_family(&self) -> MemoryFamily {
+        self.family_generation().0
+    }
+}
+
+impl PartialEq for MemoryGeneration {
+    fn eq(&self, other: &MemoryGeneration) -> bool {
+        self.family_generation() == other.family_generation()
+    }
+}
+
+impl Eq for MemoryGeneration {}
+
+/**
+Generations are ordered by family first and by generation within the family,
+so that e.g. every DDR generation precedes every LPDDR generation. Only the
+order within a family says which generation is newer, see `GenerationOperator::leq`.
+*/
+impl Ord for MemoryGeneration {
+    fn cmp(&self, other: &MemoryGeneration) -> Ordering {
+        self.family_generation().cmp(&other.family_generation())
+    }
+}
+
+impl PartialOrd for MemoryGeneration {
+    fn partial_cmp(&self, other: &MemoryGeneration) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+/**
+Operators of a `GenerationElement`. `eq` requests exactly the generation,
+`leq` the generation or a newer one of the same family.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GenerationOperator {
+    eq,
+    leq,
+}
+
+impl Default for GenerationOperator {
+    fn default() -> GenerationOperator { GenerationOperator::leq }
+}
+
+impl std::str::FromStr for GenerationOperator {
+    type Err = OntologyError;
+
+    fn from_str(s: &str) -> Result<GenerationOperator, OntologyError> {
+        match s {
+            "eq" => Ok(GenerationOperator::eq),
+            "leq" => Ok(GenerationOperator::leq),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid GenerationOperator", s)))
+        }
+    }
+}
+
+impl std::fmt::Display for GenerationOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct GenerationElement {
+    id: Option<i16>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    value: Option<MemoryGeneration>,
+    #[serde(default)]
+    compareOperator: GenerationOperator,
+}
+
+impl std::fmt::Display for GenerationElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt_value(f, &self.value, &self.id)
+    }
+}
+
+impl Compressible for GenerationElement {
+    type Value = MemoryGeneration;
+
+    fn get_repository_id(&self) -> &Option<i16> { &self.id }
+    fn set_repository_id(&mut self, id: Option<i16>) { self.id = id; }
+
+    fn get_repository_value(&self) -> &Option<MemoryGeneration> { &self.value }
+    fn set_repository_value(&mut self, value: Option<MemoryGeneration>) { self.value = value; }
+}
+
+impl GenerationElement {
+    pub fn new(value: MemoryGeneration, op: GenerationOperator) -> GenerationElement {
+        GenerationElement { id: None, value: Some(value), compareOperator: op }
+    }
+
+    pub fn get_id(&self) -> &Option<i16> { &self.id }
+    pub fn get_id_mut(&mut self) -> &mut Option<i16> { &mut self.id }
+    pub fn set_id(&mut self, id: i16) { self.id = Some(id); }
+
+    pub fn get_value(&self) -> &Option<MemoryGeneration> {
+        &self.value
+    }
+    pub fn get_value_mut(&mut self) -> &mut Option<MemoryGeneration> {
+        &mut self.value
+    }
+    pub fn set_value(&mut self, val: Option<MemoryGeneration>) {
+        self.value = val;
+    }
+
+    pub fn get_compare_operator(&self) -> &GenerationOperator {
+        &self.compareOperator
+    }
+    pub fn set_compare_operator(&mut self, op: GenerationOperator) {
+        self.compareOperator = op;
+    }
+
+    /**
+    Compares the generation requested by self with the generation offered in b.
+    `Return`
+      true if b offers exactly the requested generation or, for `leq`, a newer one of the
+      same family, false if a value is missing, e.g. because the element is compressed.
+    */
+    pub fn compare(&self, b: &GenerationElement) -> bool {
+        match (&self.value, &b.value) {
+            (&Some(ref value_a), &Some(ref value_b)) => match self.compareOperator {
+                GenerationOperator::eq => value_a == value_b,
+                GenerationOperator::leq => value_a.get_family() == value_b.get_family() && value_a <= value_b,
+            },
+            (_, _) => false,
+        }
+    }
+}
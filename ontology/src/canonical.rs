@@ -0,0 +1,219 @@
+/**
+Contains the canonical, deterministic encoding of offers and the
+content hash derived from it, which identifies an offer across peers.
+
+The canonical form of an offer is derived from its serialized fields:
+* fields are ordered by name, fields without value and empty elements are left out
+* quantities with a magnitude are given in base units without magnitude,
+  e.g. 3 giga becomes 3000000000
+* numbers are rounded to 15 significant digits, integral numbers are
+  encoded as integers, so `2`, `2.0` and `0.002 kilo` are the same
+* repository IDs are left out wherever the element also has a value, so an
+  offer and its decompressed form are equal. A compressed offer is only equal
+  to its decompressed form if it is decompressed before being canonicalized.
+* the signature is left out, since it is computed over the canonical form
+
+The canonical form is encoded as MsgPack.
+*/
+
+use serde_json::{self, Map, Number, Value};
+use sha2::{Digest, Sha256};
+use rmps;
+
+use basicElements::Magnitude;
+use ontology::{Offers, Offer};
+use error::OntologyError;
+
+/**
+Rounds a number to 15 significant digits, the precision an f64 keeps
+through the conversions between magnitudes.
+*/
+fn canonical_number(number: f64) -> Value {
+    let rounded: f64 = format!("{:.14e}", number).parse().unwrap_or(number);
+    if rounded.fract() == 0.0 && rounded.abs() < 9007199254740992.0 {
+        if rounded >= 0.0 {
+            Value::Number(Number::from(rounded as u64))
+        } else {
+            Value::Number(Number::from(rounded as i64))
+        }
+    } else {
+        Number::from_f64(rounded).map(Value::Number).unwrap_or(Value::Null)
+    }
+}
+
+/**
+Returns the factor of the magnitude of an element, None if the element
+has no magnitude or it is only given as repository ID.
+*/
+fn magnitude_factor(object: &Map<String, Value>) -> Option<f64> {
+    let magnitude = match object.get("magnitude") {
+        Some(&Value::Object(ref magnitude)) => magnitude,
+        _ => return None,
+    };
+    match magnitude.get("magnitude") {
+        Some(name @ &Value::String(_)) => serde_json::from_value::<Magnitude>(name.clone()).ok().map(|m| m.to_float()),
+        _ => None,
+    }
+}
+
+/**
+Multiplies the value of an element or range bound by the factor of its magnitude.
+The bounds of a range carry their own magnitude and are scaled on their own.
+*/
+fn canonical_object(mut object: Map<String, Value>) -> Value {
+    if let Some(factor) = magnitude_factor(&object) {
+        if let Some(&mut Value::Number(ref mut number)) = object.get_mut("value") {
+            if let Some(scaled) = number.as_f64().and_then(|n| Number::from_f64(n * factor)) {
+                *number = scaled;
+            }
+        }
+        object.remove("magnitude");
+    }
+
+    let mut fields: Vec<(String, Value)> = object.into_iter()
+        .map(|(name, value)| (name, canonical_value(value)))
+        .filter(|&(_, ref value)| !value.is_null() && value.as_object().map(|object| !object.is_empty()).unwrap_or(true))
+        .collect();
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let has_value = fields.iter().any(|&(ref name, ref value)| match name.as_str() {
+        "value" | "magnitude" => true,
+        "values" => value.as_array().map(|values| !values.is_empty()).unwrap_or(false),
+        _ => false,
+    });
+    let mut canonical = Map::new();
+    for (name, value) in fields {
+        if !(has_value && name == "id") {
+            canonical.insert(name, value);
+        }
+    }
+    Value::Object(canonical)
+}
+
+fn canonical_value(value: Value) -> Value {
+    match value {
+        Value::Object(object) => canonical_object(object),
+        Value::Array(values) => Value::Array(values.into_iter().map(canonical_value).collect()),
+        Value::Number(number) => number.as_f64().map(canonical_number).unwrap_or(Value::Number(number)),
+        other => other,
+    }
+}
+
+/**
+Returns the canonical form of an offer as described in the module documentation.
+*/
+pub fn canonical_form(offer: &Offer) -> Result<Value, OntologyError> {
+    let mut value = canonical_value(serde_json::to_value(offer)?);
+    if let Value::Object(ref mut object) = value {
+        object.remove("signature");
+    }
+    Ok(value)
+}
+
+/**
+Returns the canonical encoding of an offer. Two offers which only differ
+in the representation of their quantities, their repository IDs or their
+signature have the same encoding.
+*/
+pub fn canonical_bytes(offer: &Offer) -> Result<Vec<u8>, OntologyError> {
+    Ok(rmps::encode::to_vec(&canonical_form(offer)?)?)
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/**
+Returns the ID of an offer, the hex encoded SHA-256 hash of its canonical
+encoding without the creation time. Renewing an offer keeps its ID,
+so peers advertising the same offer at different times agree on it.
+*/
+pub fn offer_id(offer: &Offer) -> Result<String, OntologyError> {
+    let mut value = canonical_form(offer)?;
+    if let Value::Object(ref mut object) = value {
+        object.remove("created");
+    }
+    Ok(hex(&Sha256::digest(&rmps::encode::to_vec(&value)?)))
+}
+
+/**
+Returns the canonical encoding of an offer list, its canonical offers
+ordered by their encoding, so the order the offers were received in
+does not matter.
+*/
+pub fn canonical_offers_bytes(offers: &Offers) -> Result<Vec<u8>, OntologyError> {
+    let mut encoded = Vec::new();
+    for offer in offers.get_offers() {
+        encoded.push((canonical_bytes(offer)?, canonical_form(offer)?));
+    }
+    encoded.sort_by(|a, b| a.0.cmp(&b.0));
+    let values: Vec<Value> = encoded.into_iter().map(|(_, value)| value).collect();
+    Ok(rmps::encode::to_vec(&values)?)
+}
+
+/**
+Returns the hex encoded SHA-256 hash of the canonical encoding of an offer list.
+*/
+pub fn offers_id(offers: &Offers) -> Result<String, OntologyError> {
+    Ok(hex(&Sha256::digest(&canonical_offers_bytes(offers)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use repository::Repository;
+
+    fn offer(json: &str) -> Offer {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn equivalent_offers_have_the_same_id() {
+        let a = offer(r#"{
+            "host": {"value": "TU KL", "compareOperator": "eq"},
+            "ontology_version": {"value": 1.1},
+            "repository_version": {"value": 2.1},
+            "timeout": {"value": 1000},
+            "cpu": {"amount": {"value": 4}, "frequency": {"value": 3.0, "magnitude": {"magnitude": "giga"}}, "instruction_set": []},
+            "created": 100
+        }"#);
+        let b = offer(r#"{
+            "created": 200,
+            "timeout": {"value": 1000},
+            "cpu": {"instruction_set": [], "frequency": {"value": 3000, "magnitude": {"magnitude": "mega"}}, "amount": {"value": 4}},
+            "repository_version": {"value": 2.1},
+            "ontology_version": {"value": 1.1},
+            "host": {"value": "TU KL"}
+        }"#);
+        assert_eq!(offer_id(&a).unwrap(), offer_id(&b).unwrap());
+        assert!(canonical_bytes(&a).unwrap() != canonical_bytes(&b).unwrap());
+        assert_eq!(offer_id(&a).unwrap().len(), 64);
+
+        let c = offer(&serde_json::to_string(&a).unwrap().replace("1000", "2000"));
+        assert!(offer_id(&a).unwrap() != offer_id(&c).unwrap());
+
+        let timeout = |json: &str| offer(&format!(r#"{{
+            "host": {{"value": "TU KL"}},
+            "ontology_version": {{"value": 1.1}},
+            "repository_version": {{"value": 2.1}},
+            "timeout": {}
+        }}"#, json));
+        assert_eq!(offer_id(&timeout(r#"{"magnitude": {"magnitude": "milli"}, "range": {"lower": {"value": 1, "magnitude": {"magnitude": "kilo"}}}}"#)).unwrap(),
+                   offer_id(&timeout(r#"{"range": {"lower": {"value": 1000}}}"#)).unwrap());
+    }
+
+    #[test]
+    fn compressing_keeps_the_canonical_form_after_decompressing() {
+        let repo = Repository::from_json_file("repository.json").unwrap();
+        let mut offers = Offers::from_json_file("offerlist.json").unwrap();
+        let original = offers_id(&offers).unwrap();
+
+        offers.compress(&repo).unwrap();
+        assert!(offers_id(&offers).unwrap() != original);
+        offers.decompress(&repo).unwrap();
+        assert_eq!(offers_id(&offers).unwrap(), original);
+
+        offers.get_offers_mut().reverse();
+        assert_eq!(offers_id(&offers).unwrap(), original);
+    }
+}
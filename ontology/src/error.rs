@@ -0,0 +1,115 @@
+/**
+Contains the error type returned when loading, saving,
+compressing or decompressing offers and repositories.
+*/
+
+extern crate rmp_serde as rmps;
+
+use std;
+use std::io;
+
+use serde_json;
+
+#[derive(Debug)]
+pub enum OntologyError {
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// A JSON document could not be parsed or written.
+    Json(serde_json::Error),
+    /// An instance could not be encoded as MsgPack.
+    MsgPackEncode(rmps::encode::Error),
+    /// A MsgPack document could not be decoded.
+    MsgPackDecode(rmps::decode::Error),
+    /// An element or repository entry refers to an ID the repository does not contain,
+    /// or a repository entry has no ID at all.
+    MissingRepositoryId(String),
+    /// A string does not name a variant of one of the ontology enums.
+    UnknownEnumValue(String),
+    /// A string is not a dotted version number.
+    InvalidVersion(String),
+    /// The remaining capacity of an offer does not satisfy a reservation.
+    InsufficientCapacity(String),
+    /// No offer exists at the given position.
+    UnknownOffer(usize),
+    /// No reservation with the given ID exists.
+    UnknownReservation(u64),
+    /// An offer carries no signature although one is required.
+    UnsignedOffer(String),
+    /// No public key is known for the host of a signed offer.
+    UnknownHostKey(String),
+    /// The signature of an offer is malformed or does not match its content.
+    InvalidSignature(String),
+    /// A peer sent a message which is not valid at this point of the protocol.
+    UnexpectedMessage(String),
+    /// A peer rejected a request or the acceptance of an offer.
+    Rejected(String),
+    /// An offer uses another repository version than the repository it is compressed
+    /// or decompressed with, or two peers have no repository version in common.
+    RepositoryMismatch(String),
+    /// Two repositories use an ID for different entries, or a patch does not fit a repository.
+    RepositoryConflict(String),
+}
+
+impl std::fmt::Display for OntologyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            OntologyError::Io(ref e) => write!(f, "I/O error: {}", e),
+            OntologyError::Json(ref e) => write!(f, "JSON error: {}", e),
+            OntologyError::MsgPackEncode(ref e) => write!(f, "MsgPack encoding error: {}", e),
+            OntologyError::MsgPackDecode(ref e) => write!(f, "MsgPack decoding error: {}", e),
+            OntologyError::MissingRepositoryId(ref entry) => write!(f, "Missing repository ID: {}", entry),
+            OntologyError::UnknownEnumValue(ref value) => write!(f, "Unknown enum value: {}", value),
+            OntologyError::InvalidVersion(ref value) => write!(f, "Invalid version: {}", value),
+            OntologyError::InsufficientCapacity(ref reason) => write!(f, "Insufficient capacity: {}", reason),
+            OntologyError::UnknownOffer(position) => write!(f, "Unknown offer: {}", position),
+            OntologyError::UnknownReservation(id) => write!(f, "Unknown reservation: {}", id),
+            OntologyError::UnsignedOffer(ref host) => write!(f, "Unsigned offer: {}", host),
+            OntologyError::UnknownHostKey(ref host) => write!(f, "Unknown host key: {}", host),
+            OntologyError::InvalidSignature(ref host) => write!(f, "Invalid signature: {}", host),
+            OntologyError::UnexpectedMessage(ref message) => write!(f, "Unexpected message: {}", message),
+            OntologyError::Rejected(ref reason) => write!(f, "Rejected: {}", reason),
+            OntologyError::RepositoryMismatch(ref reason) => write!(f, "Repository mismatch: {}", reason),
+            OntologyError::RepositoryConflict(ref conflict) => write!(f, "Repository conflict: {}", conflict),
+        }
+    }
+}
+
+impl std::error::Error for OntologyError {
+    fn description(&self) -> &str {
+        match *self {
+            OntologyError::Io(_) => "I/O error",
+            OntologyError::Json(_) => "JSON error",
+            OntologyError::MsgPackEncode(_) => "MsgPack encoding error",
+            OntologyError::MsgPackDecode(_) => "MsgPack decoding error",
+            OntologyError::MissingRepositoryId(_) => "missing repository ID",
+            OntologyError::UnknownEnumValue(_) => "unknown enum value",
+            OntologyError::InvalidVersion(_) => "invalid version",
+            OntologyError::InsufficientCapacity(_) => "insufficient capacity",
+            OntologyError::UnknownOffer(_) => "unknown offer",
+            OntologyError::UnknownReservation(_) => "unknown reservation",
+            OntologyError::UnsignedOffer(_) => "unsigned offer",
+            OntologyError::UnknownHostKey(_) => "unknown host key",
+            OntologyError::InvalidSignature(_) => "invalid signature",
+            OntologyError::UnexpectedMessage(_) => "unexpected message",
+            OntologyError::Rejected(_) => "rejected",
+            OntologyError::RepositoryMismatch(_) => "repository mismatch",
+            OntologyError::RepositoryConflict(_) => "repository conflict",
+        }
+    }
+}
+
+impl From<io::Error> for OntologyError {
+    fn from(e: io::Error) -> OntologyError { OntologyError::Io(e) }
+}
+
+impl From<serde_json::Error> for OntologyError {
+    fn from(e: serde_json::Error) -> OntologyError { OntologyError::Json(e) }
+}
+
+impl From<rmps::encode::Error> for OntologyError {
+    fn from(e: rmps::encode::Error) -> OntologyError { OntologyError::MsgPackEncode(e) }
+}
+
+impl From<rmps::decode::Error> for OntologyError {
+    fn from(e: rmps::decode::Error) -> OntologyError { OntologyError::MsgPackDecode(e) }
+}
@@ -0,0 +1,46 @@
+/**
+Contains the framing of the messages exchanged between nodes, shared by the
+gossip protocol, the offer protocol and the repository synchronization.
+Each message is encoded as MsgPack and preceded by its length as 32 bit
+big endian integer.
+*/
+
+use std::io::{Read, Write};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use rmps;
+
+use error::OntologyError;
+
+/// Largest message accepted from a peer, in bytes.
+const MAX_FRAME_LENGTH: usize = 64 * 1024 * 1024;
+
+/**
+Writes a message preceded by its length.
+*/
+pub(crate) fn write_frame<W: Write, T: Serialize>(writer: &mut W, message: &T) -> Result<(), OntologyError> {
+    let data = rmps::encode::to_vec(message)?;
+    let length = data.len() as u32;
+    writer.write_all(&[(length >> 24) as u8, (length >> 16) as u8, (length >> 8) as u8, length as u8])?;
+    writer.write_all(&data)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/**
+Reads a message written by `write_frame`.
+`Return`
+  Err(UnexpectedMessage) if the announced length exceeds the limit of 64 MiB
+*/
+pub(crate) fn read_frame<R: Read, T: DeserializeOwned>(reader: &mut R) -> Result<T, OntologyError> {
+    let mut header = [0u8; 4];
+    reader.read_exact(&mut header)?;
+    let length = header.iter().fold(0usize, |length, byte| length << 8 | *byte as usize);
+    if length > MAX_FRAME_LENGTH {
+        return Err(OntologyError::UnexpectedMessage(format!("message of {} bytes exceeds the limit", length)));
+    }
+    let mut data = vec![0u8; length];
+    reader.read_exact(&mut data)?;
+    Ok(rmps::decode::from_slice(&data)?)
+}
@@ -0,0 +1,584 @@
+/**
+Contains the gossip protocol which distributes the offers of a swarm between its nodes.
+
+Every node keeps a view of all current offers, identified by their `offer_id`.
+Periodically a node connects to a random peer via TCP and both exchange their
+views in three messages:
+1. the node sends a digest of its view, the ID and creation time of every offer
+2. the peer replies with the offers the node is missing or only knows in an older
+   version, and with the IDs of the offers it is missing or only knows in an older version
+3. the node sends the requested offers
+
+A renewed offer, i.e. one with a later creation time, replaces its older version.
+Only offers signed by their host, see `signature`, are accepted. Expired offers and
+offers created further in the future than the allowed clock skew are neither sent nor accepted, so an offer disappears from the swarm
+once its timeout has passed unless its host advertises a renewed version.
+Offers are sent compressed with the repository of the node. The messages are framed
+as described in `frame`.
+*/
+
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Sender, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use ed25519_dalek::SigningKey;
+
+use canonical::offer_id;
+use frame::{read_frame, write_frame};
+use ontology::{Offers, Offer, unix_time};
+use repository::Repository;
+use signature::{self, HostKeys};
+use error::OntologyError;
+
+/// Time after which an exchange with an unresponsive peer is given up.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time in seconds the clock of a host may be ahead of the own clock.
+const MAX_CLOCK_SKEW: u64 = 60;
+
+/**
+Identifies the version of an offer a node knows.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DigestEntry {
+    id: String,
+    created: Option<u64>,
+}
+
+impl DigestEntry {
+    pub fn get_id(&self) -> &String { &self.id }
+    pub fn get_created(&self) -> &Option<u64> { &self.created }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GossipMessage {
+    /// The offers known to the sender.
+    digest(Vec<DigestEntry>),
+    /// The offers the receiver is missing and the IDs of the offers the sender is missing.
+    reply(Vec<Offer>, Vec<String>),
+    /// The offers requested by the receiver.
+    offers(Vec<Offer>),
+}
+
+/**
+The current offers known to a node, keyed by their offer ID.
+*/
+#[derive(Debug, Clone)]
+pub struct OfferView {
+    offers: HashMap<String, Offer>,
+    keys: HostKeys,
+}
+
+impl OfferView {
+    /**
+    Creates an empty view which accepts the offers signed by one of the given hosts.
+    */
+    pub fn new(keys: HostKeys) -> OfferView {
+        OfferView { offers: HashMap::new(), keys: keys }
+    }
+
+    pub fn get_keys(&self) -> &HostKeys { &self.keys }
+
+    pub fn len(&self) -> usize { self.offers.len() }
+    pub fn is_empty(&self) -> bool { self.offers.is_empty() }
+
+    pub fn get(&self, id: &str) -> Option<&Offer> {
+        self.offers.get(id)
+    }
+
+    /**
+    Adds an offer to the view unless it is expired, created further in the future
+    than the allowed clock skew, or the view already contains the same or a more
+    recent version of it.
+    `Parameters`
+    * offer:Offer: Decompressed offer
+    * now:u64: Current time in seconds since the Unix epoch
+    `Return`
+      true if the offer was added or replaced an older version,
+      Err(UnsignedOffer), Err(UnknownHostKey) or Err(InvalidSignature) if the
+      offer is not signed by its host, see `signature::verify`
+    */
+    pub fn insert(&mut self, offer: Offer, now: u64) -> Result<bool, OntologyError> {
+        signature::verify(&offer, &self.keys)?;
+        let ahead = match *offer.get_created() {
+            Some(created) => created > now + MAX_CLOCK_SKEW,
+            None => false,
+        };
+        if offer.is_expired(now) || ahead {
+            return Ok(false);
+        }
+        let id = offer_id(&offer)?;
+        match self.offers.get(&id) {
+            Some(known) if known.get_created() >= offer.get_created() => return Ok(false),
+            _ => (),
+        }
+        self.offers.insert(id, offer);
+        Ok(true)
+    }
+
+    /**
+    Drops all offers which are stale at the given time.
+    `Return`
+      The dropped offers
+    */
+    pub fn remove_expired(&mut self, now: u64) -> Vec<Offer> {
+        let expired: Vec<String> = self.offers.iter()
+            .filter(|&(_, offer)| offer.is_expired(now))
+            .map(|(id, _)| id.clone())
+            .collect();
+        expired.iter().filter_map(|id| self.offers.remove(id)).collect()
+    }
+
+    /**
+    Returns the ID and creation time of every current offer, ordered by ID.
+    */
+    pub fn digest(&self, now: u64) -> Vec<DigestEntry> {
+        let mut digest: Vec<DigestEntry> = self.offers.iter()
+            .filter(|&(_, offer)| !offer.is_expired(now))
+            .map(|(id, offer)| DigestEntry { id: id.clone(), created: *offer.get_created() })
+            .collect();
+        digest.sort_by(|a, b| a.id.cmp(&b.id));
+        digest
+    }
+
+    /**
+    Returns the current offers which the owner of the digest is missing or only knows in an older version.
+    */
+    pub fn missing_from(&self, digest: &[DigestEntry], now: u64) -> Vec<Offer> {
+        let known: HashMap<&String, &Option<u64>> = digest.iter().map(|entry| (&entry.id, &entry.created)).collect();
+        self.offers.iter()
+            .filter(|&(id, offer)| !offer.is_expired(now) && match known.get(id) {
+                Some(created) => *created < offer.get_created(),
+                None => true,
+            })
+            .map(|(_, offer)| offer.clone())
+            .collect()
+    }
+
+    /**
+    Returns the IDs of the digest whose offers this view is missing or only knows in an older version.
+    */
+    pub fn wanted(&self, digest: &[DigestEntry]) -> Vec<String> {
+        digest.iter()
+            .filter(|entry| match self.offers.get(&entry.id) {
+                Some(offer) => offer.get_created() < &entry.created,
+                None => true,
+            })
+            .map(|entry| entry.id.clone())
+            .collect()
+    }
+
+    /**
+    Returns the current offers ordered by their ID.
+    */
+    pub fn get_offers(&self, now: u64) -> Offers {
+        let mut offers: Vec<(&String, &Offer)> = self.offers.iter().filter(|&(_, offer)| !offer.is_expired(now)).collect();
+        offers.sort_by(|a, b| a.0.cmp(b.0));
+        Offers::new(offers.into_iter().map(|(_, offer)| offer.clone()).collect())
+    }
+}
+
+/**
+Returns a random index below `len`, which has to be positive.
+*/
+fn random_index(len: usize) -> usize {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(unix_time());
+    (hasher.finish() % len as u64) as usize
+}
+
+struct NodeState {
+    repository: Repository,
+    view: Mutex<OfferView>,
+    peers: Mutex<Vec<SocketAddr>>,
+    running: AtomicBool,
+}
+
+impl NodeState {
+    fn compress(&self, offers: Vec<Offer>) -> Result<Vec<Offer>, OntologyError> {
+        let mut offers = Offers::new(offers);
+        offers.compress(&self.repository)?;
+        Ok(offers.get_offers().clone())
+    }
+
+    /**
+    Decompresses the received offers and adds them to the view. Offers which are
+    not signed by their host are dropped, so that they do not spoil the others.
+    `Return`
+      The number of offers which were added or replaced an older version
+    */
+    fn merge(&self, offers: Vec<Offer>) -> Result<usize, OntologyError> {
+        let mut offers = Offers::new(offers);
+        offers.decompress(&self.repository)?;
+        offers.normalize();
+        let now = unix_time();
+        let mut view = self.view.lock().unwrap();
+        let mut merged = 0;
+        for offer in offers.get_offers() {
+            match view.insert(offer.clone(), now) {
+                Ok(true) => merged += 1,
+                Ok(false) => (),
+                Err(OntologyError::UnsignedOffer(_)) | Err(OntologyError::UnknownHostKey(_)) |
+                Err(OntologyError::InvalidSignature(_)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(merged)
+    }
+
+    /**
+    Answers an exchange started by a peer.
+    */
+    fn serve(&self, mut stream: TcpStream) -> Result<(), OntologyError> {
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        let digest = match read_frame(&mut stream)? {
+            GossipMessage::digest(digest) => digest,
+            _ => return Err(OntologyError::UnexpectedMessage("expected a digest".to_string())),
+        };
+        let (missing, wanted) = {
+            let now = unix_time();
+            let mut view = self.view.lock().unwrap();
+            view.remove_expired(now);
+            (view.missing_from(&digest, now), view.wanted(&digest))
+        };
+        write_frame(&mut stream, &GossipMessage::reply(self.compress(missing)?, wanted))?;
+        match read_frame(&mut stream)? {
+            GossipMessage::offers(offers) => self.merge(offers).map(|_| ()),
+            _ => Err(OntologyError::UnexpectedMessage("expected the requested offers".to_string())),
+        }
+    }
+
+    fn exchange(&self, peer: &SocketAddr) -> Result<usize, OntologyError> {
+        let mut stream = TcpStream::connect_timeout(peer, TIMEOUT)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        let digest = {
+            let now = unix_time();
+            let mut view = self.view.lock().unwrap();
+            view.remove_expired(now);
+            view.digest(now)
+        };
+        write_frame(&mut stream, &GossipMessage::digest(digest))?;
+        let (offers, wanted) = match read_frame(&mut stream)? {
+            GossipMessage::reply(offers, wanted) => (offers, wanted),
+            _ => return Err(OntologyError::UnexpectedMessage("expected a reply to the digest".to_string())),
+        };
+        let merged = self.merge(offers)?;
+        let requested: Vec<Offer> = {
+            let now = unix_time();
+            let view = self.view.lock().unwrap();
+            wanted.iter()
+                .filter_map(|id| view.get(id))
+                .filter(|offer| !offer.is_expired(now))
+                .cloned()
+                .collect()
+        };
+        write_frame(&mut stream, &GossipMessage::offers(self.compress(requested)?))?;
+        Ok(merged)
+    }
+
+    fn round(&self) -> Result<Option<SocketAddr>, OntologyError> {
+        let peer = {
+            let peers = self.peers.lock().unwrap();
+            if peers.is_empty() {
+                return Ok(None);
+            }
+            peers[random_index(peers.len())]
+        };
+        self.exchange(&peer)?;
+        Ok(Some(peer))
+    }
+}
+
+/**
+A node of the swarm, which listens for exchanges started by its peers
+and gossips with them to converge to a shared view of the offers.
+The node stops listening and gossiping when it is dropped.
+*/
+pub struct GossipNode {
+    address: SocketAddr,
+    state: Arc<NodeState>,
+    listener: Option<JoinHandle<()>>,
+    timer: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+impl GossipNode {
+    /**
+    Creates a node listening on the given address.
+    `Parameters`
+    * address:&str: Address to listen on, e.g. "127.0.0.1:0" to pick a free port
+    * repository:Repository: Repository the offers are compressed with on the wire
+    * keys:HostKeys: Public keys of the hosts whose offers the node accepts
+    */
+    pub fn bind(address: &str, repository: Repository, keys: HostKeys) -> Result<GossipNode, OntologyError> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(NodeState {
+            repository: repository,
+            view: Mutex::new(OfferView::new(keys)),
+            peers: Mutex::new(Vec::new()),
+            running: AtomicBool::new(true),
+        });
+        let listening = state.clone();
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if !listening.running.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let serving = listening.clone();
+                    thread::spawn(move || {
+                        let _ = serving.serve(stream);
+                    });
+                }
+            }
+        });
+        Ok(GossipNode { address: address, state: state, listener: Some(handle), timer: None })
+    }
+
+    pub fn get_address(&self) -> &SocketAddr { &self.address }
+
+    /**
+    Adds a peer the node gossips with. The own address and known peers are ignored.
+    */
+    pub fn add_peer(&self, peer: SocketAddr) {
+        let mut peers = self.state.peers.lock().unwrap();
+        if peer != self.address && !peers.contains(&peer) {
+            peers.push(peer);
+        }
+    }
+
+    pub fn get_peers(&self) -> Vec<SocketAddr> {
+        self.state.peers.lock().unwrap().clone()
+    }
+
+    /**
+    Publishes an offer of this node. An offer without creation time is created now.
+    To keep the offer in the swarm beyond its timeout, the node has to advertise a
+    renewed version before it expires.
+    `Parameters`
+    * offer:Offer: Offer of the host
+    * key:&SigningKey: Private key of the host, which signs the offer
+    `Return`
+      true if the offer was added or replaced an older version
+    */
+    pub fn advertise(&self, mut offer: Offer, key: &SigningKey) -> Result<bool, OntologyError> {
+        let now = unix_time();
+        if offer.get_created().is_none() {
+            offer.renew(now);
+        }
+        offer.normalize();
+        signature::sign(&mut offer, key)?;
+        self.state.view.lock().unwrap().insert(offer, now)
+    }
+
+    /**
+    Returns the current offers known to the node, ordered by their offer ID.
+    */
+    pub fn get_offers(&self) -> Offers {
+        self.state.view.lock().unwrap().get_offers(unix_time())
+    }
+
+    /**
+    Exchanges the offers with a random peer.
+    `Return`
+      Ok(Some(peer)) with the peer the offers were exchanged with,
+      Ok(None) if the node has no peers, or the error which ended the exchange
+    */
+    pub fn round(&self) -> Result<Option<SocketAddr>, OntologyError> {
+        self.state.round()
+    }
+
+    /**
+    Starts gossiping with a random peer in every interval until the node is dropped.
+    Failed exchanges, e.g. with peers which left the swarm, are retried with
+    another random peer in the next interval. Does nothing if already started.
+    */
+    pub fn start(&mut self, interval: Duration) {
+        if self.timer.is_some() {
+            return;
+        }
+        let (stop, stopped) = channel();
+        let state = self.state.clone();
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let _ = state.round();
+            }
+        });
+        self.timer = Some((stop, handle));
+    }
+}
+
+impl Drop for GossipNode {
+    fn drop(&mut self) {
+        if let Some((stop, handle)) = self.timer.take() {
+            drop(stop);
+            let _ = handle.join();
+        }
+        self.state.running.store(false, Ordering::SeqCst);
+        // Wakes the listener up so that it notices it was stopped
+        let _ = TcpStream::connect_timeout(&self.address, TIMEOUT);
+        if let Some(handle) = self.listener.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn repository() -> Repository {
+        Repository::from_json_file("repository.json").unwrap()
+    }
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[1; 32])
+    }
+
+    /**
+    Returns the keys of the host of the example offers.
+    */
+    fn keys() -> HostKeys {
+        HostKeys::new().with_key("TU KL", &key().verifying_key())
+    }
+
+    fn offers(created: u64) -> Vec<Offer> {
+        let mut offers = Offers::from_json_file("offerlist.json").unwrap().get_offers().clone();
+        for offer in offers.iter_mut() {
+            offer.renew(created);
+            signature::sign(offer, &key()).unwrap();
+        }
+        offers
+    }
+
+    #[test]
+    fn views_keep_the_latest_current_version_of_each_offer() {
+        let offer = offers(100).remove(0);
+        let renewed = offers(500).remove(0);
+
+        let mut view = OfferView::new(keys());
+        assert!(view.insert(offer.clone(), 200).unwrap());
+        assert!(!view.insert(offer.clone(), 200).unwrap());
+
+        let mut other = OfferView::new(keys());
+        assert!(other.insert(renewed.clone(), 600).unwrap());
+        assert_eq!(view.wanted(&other.digest(600)), vec![offer_id(&offer).unwrap()]);
+        assert_eq!(other.missing_from(&view.digest(600), 600), vec![renewed.clone()]);
+        assert!(view.missing_from(&other.digest(600), 600).is_empty());
+
+        assert!(view.insert(renewed.clone(), 600).unwrap());
+        assert!(!view.insert(offer.clone(), 600).unwrap());
+        assert_eq!(view.get_offers(600).get_offers(), &vec![renewed.clone()]);
+
+        assert_eq!(view.remove_expired(1500), vec![renewed.clone()]);
+        assert!(view.is_empty());
+        assert!(!view.insert(renewed, 1500).unwrap());
+    }
+
+    #[test]
+    fn views_only_accept_signed_offers_of_the_present() {
+        let now = unix_time();
+        let mut view = OfferView::new(keys());
+
+        let mut unsigned = offers(now).remove(0);
+        unsigned.set_signature(None);
+        match view.insert(unsigned, now) {
+            Err(OntologyError::UnsignedOffer(_)) => (),
+            other => panic!("expected an unsigned offer, got {:?}", other),
+        }
+        let mut forged = offers(now).remove(0);
+        signature::sign(&mut forged, &SigningKey::from_bytes(&[2; 32])).unwrap();
+        match view.insert(forged, now) {
+            Err(OntologyError::InvalidSignature(_)) => (),
+            other => panic!("expected an invalid signature, got {:?}", other),
+        }
+        match OfferView::new(HostKeys::new()).insert(offers(now).remove(0), now) {
+            Err(OntologyError::UnknownHostKey(_)) => (),
+            other => panic!("expected an unknown host key, got {:?}", other),
+        }
+
+        assert!(!view.insert(offers(now + MAX_CLOCK_SKEW + 1).remove(0), now).unwrap());
+        assert!(view.insert(offers(now + MAX_CLOCK_SKEW).remove(0), now).unwrap());
+        assert_eq!(view.len(), 1);
+    }
+
+    #[test]
+    fn advertised_offers_are_created_and_signed_by_the_node() {
+        let node = GossipNode::bind("127.0.0.1:0", repository(), keys()).unwrap();
+        let mut offer = offers(0).remove(0);
+        offer.set_created(None);
+        offer.set_signature(None);
+
+        let before = unix_time();
+        assert!(node.advertise(offer, &key()).unwrap());
+        let advertised = node.get_offers().get_offers()[0].clone();
+        assert!(advertised.get_created().unwrap() >= before);
+        signature::verify(&advertised, &keys()).unwrap();
+    }
+
+    #[test]
+    fn a_silent_peer_does_not_hold_up_other_exchanges() {
+        let nodes: Vec<GossipNode> = (0..2).map(|_| GossipNode::bind("127.0.0.1:0", repository(), keys()).unwrap()).collect();
+        nodes[1].advertise(offers(unix_time()).remove(0), &key()).unwrap();
+        nodes[0].add_peer(*nodes[1].get_address());
+
+        let _silent = TcpStream::connect(nodes[1].get_address()).unwrap();
+        let started = Instant::now();
+        assert_eq!(nodes[0].round().unwrap(), Some(*nodes[1].get_address()));
+        assert!(started.elapsed() < TIMEOUT);
+        assert_eq!(nodes[0].get_offers().get_offers().len(), 1);
+    }
+
+    #[test]
+    fn nodes_converge_to_the_same_offers() {
+        let now = unix_time();
+        let mut nodes: Vec<GossipNode> = (0..3).map(|_| GossipNode::bind("127.0.0.1:0", repository(), keys()).unwrap()).collect();
+        for (node, offer) in nodes.iter().zip(offers(now)) {
+            node.advertise(offer, &key()).unwrap();
+        }
+        let stale = offers(now - 2000).remove(0);
+        assert!(!nodes[0].advertise(stale, &key()).unwrap());
+
+        // The outer nodes only know the middle one
+        nodes[0].add_peer(*nodes[1].get_address());
+        nodes[1].add_peer(*nodes[0].get_address());
+        nodes[1].add_peer(*nodes[2].get_address());
+        nodes[2].add_peer(*nodes[1].get_address());
+        assert_eq!(nodes[0].round().unwrap(), Some(*nodes[1].get_address()));
+        assert_eq!(nodes[0].get_offers().get_offers().len(), 2);
+
+        for node in nodes.iter_mut() {
+            node.start(Duration::from_millis(10));
+        }
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while nodes.iter().any(|node| node.get_offers().get_offers().len() < 3) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let mut expected: Vec<String> = offers(now).iter().map(|offer| offer_id(offer).unwrap()).collect();
+        expected.sort();
+        for node in &nodes {
+            let mut ids: Vec<String> = node.get_offers().get_offers().iter().map(|offer| offer_id(offer).unwrap()).collect();
+            ids.sort();
+            assert_eq!(ids, expected);
+        }
+
+        let mut renewed = offers(now).remove(2);
+        renewed.renew(now + 10);
+        nodes[2].advertise(renewed, &key()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while nodes[0].get_offers().get_offers().iter().all(|offer| offer.get_created() != &Some(now + 10)) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(nodes[0].get_offers().get_offers().iter().any(|offer| offer.get_created() == &Some(now + 10)));
+        assert_eq!(nodes[0].get_offers().get_offers().len(), 3);
+    }
+}
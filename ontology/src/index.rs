@@ -0,0 +1,640 @@
+/**
+Contains an offer store with per-attribute indexes, which finds
+the offers sufficient for a request without comparing it to every offer.
+*/
+
+use std;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::Bound;
+
+
+use basicElements::{IntElement, IntOperator, FloatElement, FloatOperator, StringElement, StringOperator, Range, Scalar};
+use ontology::{Offers, Offer, comparing};
+
+/**
+The absolute values an offer may have to satisfy a requested numeric element.
+A bound is given as value and whether it is inclusive, a missing bound leaves
+the interval open on that side.
+*/
+struct Interval {
+    lower: Option<(f64, bool)>,
+    upper: Option<(f64, bool)>,
+}
+
+impl Interval {
+    fn at_least(value: f64, inclusive: bool) -> Interval {
+        Interval { lower: Some((value, inclusive)), upper: None }
+    }
+
+    fn at_most(value: f64, inclusive: bool) -> Interval {
+        Interval { lower: None, upper: Some((value, inclusive)) }
+    }
+
+    fn exactly(value: f64) -> Interval {
+        Interval { lower: Some((value, true)), upper: Some((value, true)) }
+    }
+
+    /**
+    Returns the interval of a range whose bounds are given in a unit with the given factor.
+    */
+    fn from_range<T: Scalar>(range: &Range<T>, unit_factor: f64) -> Interval {
+        Interval {
+            lower: range.get_lower().as_ref().map(|bound| (bound.get_absolute_value() * unit_factor, bound.is_inclusive())),
+            upper: range.get_upper().as_ref().map(|bound| (bound.get_absolute_value() * unit_factor, bound.is_inclusive())),
+        }
+    }
+
+    /**
+    Returns the interval of offered values satisfying a requested IntElement,
+    or None if the request cannot be used to narrow down the offers.
+    As in `IntElement::compare`, `leq` means request <= offer.
+    */
+    fn of_int(element: &IntElement) -> Option<Interval> {
+        let value = element.get_absolute_value();
+        match *element.get_compare_operator() {
+            IntOperator::leq => value.map(|value| Interval::at_least(value, true)),
+            IntOperator::geq => value.map(|value| Interval::at_most(value, true)),
+            IntOperator::eq => value.map(Interval::exactly),
+            IntOperator::le => value.map(|value| Interval::at_least(value, false)),
+            IntOperator::ge => value.map(|value| Interval::at_most(value, false)),
+            IntOperator::range => element.get_range().as_ref().map(|range| Interval::from_range(range, element.get_unit_factor())),
+        }
+    }
+
+    /**
+    Returns the interval of offered values satisfying a requested FloatElement,
+    or None if the request cannot be used to narrow down the offers.
+    */
+    fn of_float(element: &FloatElement) -> Option<Interval> {
+        let value = element.get_absolute_value();
+        match *element.get_compare_operator() {
+            FloatOperator::leq => value.map(|value| Interval::at_least(value, true)),
+            FloatOperator::geq => value.map(|value| Interval::at_most(value, true)),
+            FloatOperator::eq => value.map(Interval::exactly),
+            FloatOperator::le => value.map(|value| Interval::at_least(value, false)),
+            FloatOperator::ge => value.map(|value| Interval::at_most(value, false)),
+            FloatOperator::range => element.get_range().as_ref().map(|range| Interval::from_range(range, element.get_unit_factor())),
+        }
+    }
+}
+
+/**
+Absolute value of a numeric attribute, ordered totally since NaN is never indexed.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Key(f64);
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Key) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Key) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/**
+Index over a numeric attribute, sorted by the absolute value of the attribute.
+Offers which do not state the attribute can satisfy every request and
+are kept separately.
+The entries are a `BTreeSet` of value and offer, so that both a lookup and an
+insertion take O(log n).
+*/
+#[derive(Debug, Clone, Default)]
+struct NumericIndex {
+    entries: BTreeSet<(Key, usize)>,
+    unindexed: BTreeSet<usize>,
+}
+
+impl NumericIndex {
+    fn insert(&mut self, value: Option<f64>, offer: usize) {
+        match value {
+            Some(value) if !value.is_nan() => self.entries.insert((Key(value), offer)),
+            _ => self.unindexed.insert(offer),
+        };
+    }
+
+    /**
+    Removes an offer inserted with the given value.
+    */
+    fn remove(&mut self, value: Option<f64>, offer: usize) {
+        match value {
+            Some(value) if !value.is_nan() => self.entries.remove(&(Key(value), offer)),
+            _ => self.unindexed.remove(&offer),
+        };
+    }
+
+    /**
+    Returns the offers whose value lies within the interval, plus all unindexed offers.
+    */
+    fn select(&self, interval: &Interval) -> Vec<usize> {
+        let mut offers: Vec<usize> = self.unindexed.iter().cloned().collect();
+        let empty = match (interval.lower, interval.upper) {
+            (Some((lower, lower_inclusive)), Some((upper, upper_inclusive))) =>
+                lower > upper || (lower == upper && !(lower_inclusive && upper_inclusive)),
+            (_, _) => false,
+        };
+        if !empty {
+            let start = match interval.lower {
+                Some((lower, true)) => Bound::Included((Key(lower), 0)),
+                Some((lower, false)) => Bound::Excluded((Key(lower), usize::max_value())),
+                None => Bound::Unbounded,
+            };
+            let end = match interval.upper {
+                Some((upper, true)) => Bound::Included((Key(upper), usize::max_value())),
+                Some((upper, false)) => Bound::Excluded((Key(upper), 0)),
+                None => Bound::Unbounded,
+            };
+            offers.extend(self.entries.range((start, end)).map(|&(_, offer)| offer));
+        }
+        offers.sort();
+        offers.dedup();
+        offers
+    }
+}
+
+/**
+Index over an attribute compared by equality, keyed by the lower case value
+so that it serves `eq`, `lowerCaseEq` and `oneOf` requests.
+*/
+#[derive(Debug, Clone, Default)]
+struct HashIndex {
+    entries: HashMap<String, BTreeSet<usize>>,
+    unindexed: BTreeSet<usize>,
+}
+
+impl HashIndex {
+    fn insert(&mut self, key: Option<String>, offer: usize) {
+        match key {
+            Some(key) => self.entries.entry(key.to_lowercase()).or_insert_with(BTreeSet::new).insert(offer),
+            None => self.unindexed.insert(offer),
+        };
+    }
+
+    /**
+    Removes an offer inserted with the given key.
+    */
+    fn remove(&mut self, key: Option<String>, offer: usize) {
+        match key {
+            Some(key) => {
+                let key = key.to_lowercase();
+                let emptied = match self.entries.get_mut(&key) {
+                    Some(entries) => entries.remove(&offer) && entries.is_empty(),
+                    None => false,
+                };
+                if emptied {
+                    self.entries.remove(&key);
+                }
+            }
+            None => { self.unindexed.remove(&offer); }
+        }
+    }
+
+    /**
+    Returns the offers stating one of the keys, plus all unindexed offers.
+    */
+    fn select(&self, keys: &Vec<String>) -> Vec<usize> {
+        let mut offers: Vec<usize> = self.unindexed.iter().cloned().collect();
+        for key in keys {
+            if let Some(entries) = self.entries.get(&key.to_lowercase()) {
+                offers.extend(entries.iter().cloned());
+            }
+        }
+        offers.sort();
+        offers.dedup();
+        offers
+    }
+}
+
+/**
+Returns the values an offered StringElement may have to satisfy the requested one,
+or None if the request cannot be used to narrow down the offers.
+*/
+fn string_keys(element: &StringElement) -> Option<Vec<String>> {
+    match *element.get_compare_operator() {
+        StringOperator::eq | StringOperator::lowerCaseEq => element.get_value().as_ref().map(|value| vec![value.clone()]),
+        StringOperator::oneOf => {
+            let mut keys = Vec::new();
+            for alternative in element.get_values() {
+                match *alternative.get_value() {
+                    Some(ref value) => keys.push(value.clone()),
+                    None => return None,
+                }
+            }
+            Some(keys)
+        }
+        _ => None,
+    }
+}
+
+/**
+The values an offer is kept under in the indexes of an `OfferIndex`,
+None for an attribute the offer does not state.
+*/
+struct IndexKeys {
+    cpu_amount: Option<f64>,
+    cpu_frequency: Option<f64>,
+    cpu_manufacturer: Option<String>,
+    memory_size: Option<f64>,
+    system_type: Option<String>,
+    disk_size: Vec<Option<f64>>,
+    disk_type: Vec<Option<String>>,
+}
+
+impl IndexKeys {
+    fn of(offer: &Offer) -> IndexKeys {
+        let cpu = offer.get_cpu().as_ref();
+        let mut keys = IndexKeys {
+            cpu_amount: cpu.and_then(|cpu| cpu.get_amount().as_ref()).and_then(|amount| amount.get_absolute_value()),
+            cpu_frequency: cpu.and_then(|cpu| cpu.get_frequency().as_ref()).and_then(|frequency| frequency.get_absolute_value()),
+            cpu_manufacturer: cpu.and_then(|cpu| cpu.get_manufacturer().as_ref()).and_then(|manufacturer| manufacturer.get_value().clone()),
+            memory_size: offer.get_memory().as_ref().and_then(|memory| memory.get_size().as_ref()).and_then(|size| size.get_absolute_value()),
+            system_type: offer.get_operating_system().as_ref().and_then(|os| os.get_system_type().as_ref()).and_then(|system_type| system_type.get_value().clone()),
+            disk_size: vec![None],
+            disk_type: vec![None],
+        };
+
+        // An offer can serve a requested disk with any of its disks. If one of
+        // them does not state an attribute, that disk fits every request for it.
+        if let Some(ref disks) = *offer.get_disk() {
+            let sizes: Vec<Option<f64>> = disks.iter()
+                .map(|disk| disk.get_size().as_ref().and_then(|size| size.get_absolute_value())).collect();
+            if !sizes.iter().any(|size| size.is_none()) {
+                keys.disk_size = sizes;
+            }
+            let types: Vec<Option<String>> = disks.iter()
+                .map(|disk| disk.get_disk_type().as_ref().and_then(|disk_type| disk_type.get_value().as_ref())
+                    .map(|disk_type| format!("{:?}", disk_type))).collect();
+            if !types.iter().any(|disk_type| disk_type.is_none()) {
+                keys.disk_type = types;
+            }
+        }
+        keys
+    }
+}
+
+/**
+An offer store answering which offers are sufficient for a request.
+Numeric attributes (CPU amount and frequency, memory size, disk size) are kept
+in sorted indexes, the manufacturer, operating system type and disk type in
+hash indexes. A request narrows down the offers with every indexed attribute
+it states; only the remaining candidates are compared with `comparing`, so the
+result equals comparing the request with every offer.
+The offers are kept in slots numbered in the order they were inserted, so that
+adding, replacing and removing an offer only updates the index entries of that offer.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct OfferIndex {
+    offers: BTreeMap<usize, Offer>,
+    next_slot: usize,
+    cpu_amount: NumericIndex,
+    cpu_frequency: NumericIndex,
+    memory_size: NumericIndex,
+    disk_size: NumericIndex,
+    cpu_manufacturer: HashIndex,
+    system_type: HashIndex,
+    disk_type: HashIndex,
+}
+
+impl OfferIndex {
+    pub fn new() -> OfferIndex { OfferIndex::default() }
+
+    pub fn from_offers(offers: &Offers) -> OfferIndex {
+        let mut index = OfferIndex::new();
+        for offer in offers.get_offers() {
+            index.insert(offer.clone());
+        }
+        index
+    }
+
+    /**
+    Returns the offers in the order they were inserted.
+    */
+    pub fn get_offers(&self) -> Vec<&Offer> { self.offers.values().collect() }
+
+    pub fn len(&self) -> usize { self.offers.len() }
+
+    pub fn is_empty(&self) -> bool { self.offers.is_empty() }
+
+    /**
+    Adds an offer to the store and all indexes in O(log n).
+    */
+    pub fn insert(&mut self, offer: Offer) {
+        let slot = self.next_slot;
+        self.next_slot += 1;
+        self.add_keys(&offer, slot);
+        self.offers.insert(slot, offer);
+    }
+
+    fn add_keys(&mut self, offer: &Offer, slot: usize) {
+        let keys = IndexKeys::of(offer);
+        self.cpu_amount.insert(keys.cpu_amount, slot);
+        self.cpu_frequency.insert(keys.cpu_frequency, slot);
+        self.cpu_manufacturer.insert(keys.cpu_manufacturer, slot);
+        self.memory_size.insert(keys.memory_size, slot);
+        self.system_type.insert(keys.system_type, slot);
+        for size in keys.disk_size {
+            self.disk_size.insert(size, slot);
+        }
+        for disk_type in keys.disk_type {
+            self.disk_type.insert(disk_type, slot);
+        }
+    }
+
+    fn remove_keys(&mut self, offer: &Offer, slot: usize) {
+        let keys = IndexKeys::of(offer);
+        self.cpu_amount.remove(keys.cpu_amount, slot);
+        self.cpu_frequency.remove(keys.cpu_frequency, slot);
+        self.cpu_manufacturer.remove(keys.cpu_manufacturer, slot);
+        self.memory_size.remove(keys.memory_size, slot);
+        self.system_type.remove(keys.system_type, slot);
+        for size in keys.disk_size {
+            self.disk_size.remove(size, slot);
+        }
+        for disk_type in keys.disk_type {
+            self.disk_type.remove(disk_type, slot);
+        }
+    }
+
+    /**
+    Returns the slots of all offers which satisfy every indexed attribute of the request, in ascending order.
+    */
+    fn candidate_slots(&self, request: &Offer) -> Vec<usize> {
+        let mut selections: Vec<Vec<usize>> = Vec::new();
+
+        if let Some(ref cpu) = *request.get_cpu() {
+            if let Some(interval) = cpu.get_amount().as_ref().and_then(Interval::of_int) {
+                selections.push(self.cpu_amount.select(&interval));
+            }
+            if let Some(interval) = cpu.get_frequency().as_ref().and_then(Interval::of_float) {
+                selections.push(self.cpu_frequency.select(&interval));
+            }
+            if let Some(keys) = cpu.get_manufacturer().as_ref().and_then(string_keys) {
+                selections.push(self.cpu_manufacturer.select(&keys));
+            }
+        }
+
+        if let Some(ref memory) = *request.get_memory() {
+            if let Some(interval) = memory.get_size().as_ref().and_then(Interval::of_float) {
+                selections.push(self.memory_size.select(&interval));
+            }
+        }
+
+        if let Some(ref operating_system) = *request.get_operating_system() {
+            if let Some(keys) = operating_system.get_system_type().as_ref().and_then(string_keys) {
+                selections.push(self.system_type.select(&keys));
+            }
+        }
+
+        if let Some(ref disks) = *request.get_disk() {
+            for disk in disks {
+                if let Some(interval) = disk.get_size().as_ref().and_then(Interval::of_int) {
+                    selections.push(self.disk_size.select(&interval));
+                }
+                if let Some(disk_type) = disk.get_disk_type().as_ref().and_then(|disk_type| disk_type.get_value().as_ref()) {
+                    selections.push(self.disk_type.select(&vec![format!("{:?}", disk_type)]));
+                }
+            }
+        }
+
+        selections.sort_by_key(|selection| selection.len());
+        let mut selections = selections.into_iter();
+        match selections.next() {
+            Some(mut candidates) => {
+                for selection in selections {
+                    candidates.retain(|offer| selection.binary_search(offer).is_ok());
+                }
+                candidates
+            }
+            None => self.offers.keys().cloned().collect(),
+        }
+    }
+
+    /**
+    Returns all offers which satisfy every indexed attribute of the request.
+    This is a superset of the sufficient offers, in the order they were inserted.
+    */
+    pub fn candidates(&self, request: &Offer) -> Vec<&Offer> {
+        self.candidate_slots(request).into_iter().map(|slot| &self.offers[&slot]).collect()
+    }
+
+    /**
+    Returns all offers which are sufficient for the request.
+    `Parameters`
+    * request:&Offer: Offer used as request
+    `Return`
+     Vec<&Offer>:
+        The offers for which `comparing` reports a match, in the order they were inserted.
+    */
+    pub fn sufficient(&self, request: &Offer) -> Vec<&Offer> {
+        let mut sufficient = Vec::new();
+        for offer in self.candidates(request) {
+            if comparing(request, offer).is_match() {
+                sufficient.push(offer);
+            }
+        }
+        sufficient
+    }
+
+    /**
+    Returns all offers which are sufficient for the request and not stale
+    at the given time in seconds since the Unix epoch.
+    */
+    pub fn sufficient_at(&self, request: &Offer, now: u64) -> Vec<&Offer> {
+        let mut sufficient = Vec::new();
+        for offer in self.candidates(request) {
+            if !offer.is_expired(now) && comparing(request, offer).is_match() {
+                sufficient.push(offer);
+            }
+        }
+        sufficient
+    }
+
+    /**
+    Drops all offers which are stale at the given time and their index entries.
+    `Return`
+      The dropped offers
+    */
+    pub fn remove_expired(&mut self, now: u64) -> Vec<Offer> {
+        let expired: Vec<usize> = self.offers.iter()
+            .filter(|&(_, offer)| offer.is_expired(now))
+            .map(|(slot, _)| *slot)
+            .collect();
+        let mut removed = Vec::new();
+        for slot in expired {
+            if let Some(offer) = self.offers.remove(&slot) {
+                self.remove_keys(&offer, slot);
+                removed.push(offer);
+            }
+        }
+        removed
+    }
+
+    /**
+    Renews the offer at the given time and replaces the earlier copy of it, see `Offers::refresh`.
+    The offer keeps the place of the earlier copy.
+    `Return`
+      True if an earlier copy of the offer was replaced
+    */
+    pub fn refresh(&mut self, mut offer: Offer, now: u64) -> bool {
+        offer.renew(now);
+        let earlier = self.offers.iter().find(|&(_, existing)| existing.same_offer(&offer)).map(|(slot, _)| *slot);
+        match earlier.and_then(|slot| self.offers.remove(&slot).map(|existing| (slot, existing))) {
+            Some((slot, existing)) => {
+                self.remove_keys(&existing, slot);
+                self.add_keys(&offer, slot);
+                self.offers.insert(slot, offer);
+                true
+            }
+            None => {
+                self.insert(offer);
+                false
+            }
+        }
+    }
+}
+
+impl std::iter::FromIterator<Offer> for OfferIndex {
+    fn from_iter<I: IntoIterator<Item = Offer>>(offers: I) -> OfferIndex {
+        let mut index = OfferIndex::new();
+        for offer in offers {
+            index.insert(offer);
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use std::time::Instant;
+
+    /**
+    Generates offers with pseudo random attributes, some of which leave out
+    sections or attributes so that the unindexed offers are covered as well.
+    */
+    fn generate_offers(count: usize) -> Vec<Offer> {
+        let manufacturers = ["AMD", "Intel", "ARM"];
+        let systems = ["Linux", "Windows", "FreeBSD"];
+        let disk_types = ["SSD", "HDD"];
+        let mut seed: u64 = 42;
+        let mut next = move |modulus: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % modulus
+        };
+
+        (0..count).map(|i| {
+            let cpu = if next(10) == 0 { String::new() } else {
+                format!(r#", "cpu": {{"manufacturer": {{"value": "{}"}}, "amount": {{"value": {}}},
+                           "frequency": {{"value": {}, "magnitude": {{"magnitude": "giga"}}}}, "instruction_set": []}}"#,
+                        manufacturers[next(3) as usize], 1 + next(32), 1.0 + next(30) as f64 / 10.0)
+            };
+            let memory = format!(r#", "memory": {{"size": {{"value": {}, "magnitude": {{"magnitude": "giga"}}}}}}"#, 1 << next(8));
+            let operating_system = format!(r#", "operating_system": {{"system_type": {{"value": "{}"}}}}"#, systems[next(3) as usize]);
+            let disks: Vec<String> = (0..next(3)).map(|_| {
+                let size = if next(20) == 0 { String::new() } else {
+                    format!(r#", "size": {{"value": {}, "magnitude": {{"magnitude": "giga"}}}}"#, 64 << next(6))
+                };
+                format!(r#"{{"diskType": {{"value": "{}"}}{}}}"#, disk_types[next(2) as usize], size)
+            }).collect();
+            serde_json::from_str(&format!(r#"{{
+                "host": {{"value": "host {}"}},
+                "ontology_version": {{"value": 1.1}},
+                "repository_version": {{"value": 2.1}},
+                "timeout": {{"value": 1000}}{}{}{}, "disk": [{}]
+            }}"#, i, cpu, memory, operating_system, disks.join(", "))).unwrap()
+        }).collect()
+    }
+
+    fn requests() -> Vec<Offer> {
+        let sections = vec![
+            r#""cpu": {"manufacturer": {"value": "AMD"}, "amount": {"value": 8, "compareOperator": "leq"}, "instruction_set": []}"#,
+            r#""cpu": {"manufacturer": {"value": "intel", "compareOperator": "lowerCaseEq"}, "frequency": {"value": 2500, "magnitude": {"magnitude": "mega"}, "compareOperator": "le"}, "instruction_set": []}"#,
+            r#""cpu": {"manufacturer": {"compareOperator": "oneOf", "values": [{"value": "AMD"}, {"value": "ARM"}]}, "amount": {"compareOperator": "range", "range": {"lower": {"value": 4}, "upper": {"value": 16, "inclusive": false}}}, "instruction_set": []}"#,
+            r#""memory": {"size": {"value": 16, "magnitude": {"magnitude": "giga"}, "compareOperator": "eq"}}"#,
+            r#""memory": {"size": {"value": 32, "magnitude": {"magnitude": "giga"}, "compareOperator": "geq"}}, "operating_system": {"system_type": {"value": "Linux"}}"#,
+            r#""operating_system": {"system_type": {"value": "Win", "compareOperator": "prefix"}}, "cpu": {"amount": {"value": 16, "compareOperator": "ge"}, "instruction_set": []}"#,
+            r#""disk": [{"diskType": {"value": "SSD"}, "size": {"value": 512, "magnitude": {"magnitude": "giga"}, "compareOperator": "leq"}}]"#,
+            r#""disk": [{"size": {"value": 1, "magnitude": {"magnitude": "tera"}, "compareOperator": "leq"}}, {"diskType": {"value": "HDD"}}]"#,
+        ];
+        sections.iter().map(|section| serde_json::from_str(&format!(r#"{{
+            "host": {{"value": "host ", "compareOperator": "prefix"}},
+            "ontology_version": {{"value": 1.1}},
+            "repository_version": {{"value": 2.1}},
+            "timeout": {{"value": 1000}}, {}
+        }}"#, section)).unwrap()).collect()
+    }
+
+    fn scan<'a>(offers: &'a Vec<Offer>, request: &Offer) -> Vec<&'a Offer> {
+        offers.iter().filter(|offer| comparing(request, offer).is_match()).collect()
+    }
+
+    #[test]
+    fn index_finds_the_same_offers_as_comparing() {
+        let offers = generate_offers(2000);
+        let index: OfferIndex = offers.iter().cloned().collect();
+        for request in requests() {
+            let expected = scan(&offers, &request);
+            assert!(!expected.is_empty());
+            assert_eq!(index.sufficient(&request), expected);
+            assert!(index.candidates(&request).len() < offers.len());
+        }
+    }
+
+    #[test]
+    fn stale_offers_are_dropped_and_refreshed() {
+        let offers: Vec<Offer> = generate_offers(100).into_iter().enumerate()
+            .map(|(i, offer)| offer.with_created(i as u64)).collect();
+        let mut index: OfferIndex = offers.iter().cloned().collect();
+        let request = &requests()[6];
+        let current = index.sufficient_at(request, 1050).len();
+        assert!(current < index.sufficient(request).len());
+
+        let expired = index.remove_expired(1050);
+        assert_eq!(expired.len(), 51);
+        assert_eq!(index.sufficient(request).len(), current);
+
+        assert!(index.refresh(offers[99].clone(), 2000));
+        assert_eq!(index.len(), 49);
+        assert_eq!(index.get_offers()[48].get_created(), &Some(2000));
+
+        let mut changed = offers[98].clone();
+        changed.set_memory(None);
+        assert!(!index.refresh(changed, 2000));
+        let remaining: Vec<Offer> = index.get_offers().into_iter().cloned().collect();
+        for request in requests() {
+            assert_eq!(index.sufficient(&request), scan(&remaining, &request));
+        }
+    }
+
+    /**
+    Compares the index with a naive scan over all offers, run with
+    `cargo test --release -- --ignored --nocapture`.
+    */
+    #[test]
+    #[ignore]
+    fn benchmark_index_against_scan() {
+        let offers = generate_offers(50000);
+        let requests = requests();
+
+        let start = Instant::now();
+        let index: OfferIndex = offers.iter().cloned().collect();
+        println!("building the index: {:?}", start.elapsed());
+
+        let start = Instant::now();
+        let scanned: usize = requests.iter().map(|request| scan(&offers, request).len()).sum();
+        let scan_time = start.elapsed();
+
+        let start = Instant::now();
+        let indexed: usize = requests.iter().map(|request| index.sufficient(request).len()).sum();
+        let index_time = start.elapsed();
+
+        assert_eq!(scanned, indexed);
+        println!("{} requests over {} offers: scan {:?}, index {:?}", requests.len(), offers.len(), scan_time, index_time);
+    }
+}
@@ -0,0 +1,37 @@
+//! Ontology to describe, compress and compare the resource offers
+//! and requests exchanged in a SwarmCloud.
+
+// The element and operator names are part of the serialized format.
+#![allow(non_snake_case, non_camel_case_types)]
+
+#[macro_use]
+extern crate serde;
+extern crate serde_json;
+extern crate rmp_serde as rmps;
+extern crate regex;
+pub extern crate ed25519_dalek;
+extern crate sha2;
+
+#[macro_use]
+extern crate serde_derive;
+
+pub mod error;
+pub mod basicElements;
+pub mod ontology;
+pub mod repository;
+pub mod report;
+pub mod ranking;
+pub mod index;
+pub mod reservation;
+pub mod placement;
+pub mod signature;
+pub mod canonical;
+pub mod gossip;
+pub mod protocol;
+pub mod patch;
+mod frame;
+mod optional;
+
+pub use error::OntologyError;
+pub use ontology::{Offers, Offer, comparing};
+pub use repository::Repository;
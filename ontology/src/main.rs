@@ -1,57 +1,438 @@
 extern crate serde;
 extern crate serde_json;
 extern crate rmp_serde as rmps;
+extern crate swarmcloud_ontology;
 
-use serde::{Deserialize, Serialize};
-use rmps::{Deserializer, Serializer};
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::process;
+use std::thread;
 
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 
-use std::fs::File;
-use std::io::Read;
-use std::cmp::Ordering;
-use std::str::FromStr;
+use swarmcloud_ontology::error::OntologyError;
+use swarmcloud_ontology::ontology::{Offers, Offer, comparing, unix_time};
+use swarmcloud_ontology::repository::Repository;
+use swarmcloud_ontology::ranking::{ranking, RankingWeights};
+use swarmcloud_ontology::signature::{verify, HostKeys};
+use swarmcloud_ontology::protocol::{Header, MatchServer, MatchClient};
+use swarmcloud_ontology::patch::{RepositoryPatch, merge};
 
+const USAGE: &'static str = "Usage:
+  swarmcloud_ontology validate OFFERS [--repository REPOSITORY]
+  swarmcloud_ontology compress OFFERS REPOSITORY OUTPUT [--format json|msgpack]
+  swarmcloud_ontology decompress OFFERS REPOSITORY OUTPUT [--format json|msgpack]
+  swarmcloud_ontology match REQUEST OFFERS [--repository REPOSITORY] [--weights WEIGHTS] [--keys KEYS]
+  swarmcloud_ontology convert INPUT OUTPUT
+  swarmcloud_ontology diff REPOSITORY NEW_REPOSITORY OUTPUT [--format json|msgpack]
+  swarmcloud_ontology patch REPOSITORY PATCH OUTPUT [--format json|msgpack]
+  swarmcloud_ontology merge REPOSITORY OTHER_REPOSITORY OUTPUT [--format json|msgpack]
+  swarmcloud_ontology serve ADDRESS OFFERS REPOSITORY
+  swarmcloud_ontology submit ADDRESS REQUEST REPOSITORY [--accept OFFER_ID]
 
-use serde_json::{Value, Error};
+Offer lists, requests and repositories may be given as JSON or MsgPack,
+the format is detected from the content of the file. Written files use the
+format of the input unless --format is given; convert writes the other format.
+Given --keys, a JSON file of host keys, match rejects offers which are not
+signed by their host.
+serve answers the requests submitted to ADDRESS with the matching offers until
+it is stopped; submit prints the candidates for the request and withdraws it,
+unless one of them is accepted with --accept.";
 
-#[macro_use]
-extern crate serde_derive;
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Json,
+    MsgPack,
+}
 
-mod basicElements;
-mod ontology;
-use ontology::{Offers,Offer,comparing};
+impl Format {
+    fn parse(name: &str) -> Option<Format> {
+        match name {
+            "json" => Some(Format::Json),
+            "msgpack" => Some(Format::MsgPack),
+            _ => None,
+        }
+    }
 
-mod repository;
-use repository::Repository;
+    fn other(&self) -> Format {
+        match *self {
+            Format::Json => Format::MsgPack,
+            Format::MsgPack => Format::Json,
+        }
+    }
+}
 
-fn main() {
-    println!("Initializing local offer list and repository");
+/**
+Reads a JSON or MsgPack document. A JSON document starts with `{` or `[`,
+whereas the offers, requests and repositories are encoded as MsgPack arrays.
+*/
+fn load<T: DeserializeOwned>(path: &str) -> Result<(T, Format), OntologyError> {
+    let mut data = Vec::new();
+    File::open(path)?.read_to_end(&mut data)?;
+    let first = data.iter().cloned().find(|byte| !(*byte as char).is_whitespace());
+    match first {
+        Some(b'{') | Some(b'[') => Ok((serde_json::from_slice(&data)?, Format::Json)),
+        _ => Ok((rmps::decode::from_slice(&data)?, Format::MsgPack)),
+    }
+}
+
+fn save<T: Serialize>(path: &str, value: &T, format: Format) -> Result<(), OntologyError> {
+    let mut file = File::create(path)?;
+    match format {
+        Format::Json => serde_json::to_writer_pretty(file, value)?,
+        Format::MsgPack => file.write_all(&rmps::encode::to_vec(value)?)?,
+    }
+    Ok(())
+}
+
+/**
+Command line arguments split into positional arguments and `--name value` options.
+*/
+struct Arguments {
+    positional: Vec<String>,
+    options: Vec<(String, String)>,
+}
+
+impl Arguments {
+    fn parse(args: Vec<String>) -> Result<Arguments, String> {
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg.starts_with("--") {
+                match args.next() {
+                    Some(value) => options.push((arg[2..].to_string(), value)),
+                    None => return Err(format!("Missing value for {}", arg)),
+                }
+            } else {
+                positional.push(arg);
+            }
+        }
+        Ok(Arguments { positional: positional, options: options })
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.iter().find(|&&(ref option, _)| option == name).map(|&(_, ref value)| value.as_str())
+    }
+
+    /**
+    Returns the positional arguments if there are exactly `count` of them
+    and every option is one of the allowed ones.
+    */
+    fn expect(&self, count: usize, allowed: &[&str]) -> Result<Vec<&str>, String> {
+        if let Some(&(ref option, _)) = self.options.iter().find(|&&(ref option, _)| !allowed.contains(&option.as_str())) {
+            return Err(format!("Unknown option --{}", option));
+        }
+        if self.positional.len() != count {
+            return Err(format!("Expected {} arguments, got {}", count, self.positional.len()));
+        }
+        Ok(self.positional.iter().map(|arg| arg.as_str()).collect())
+    }
+
+    fn format(&self) -> Result<Option<Format>, String> {
+        match self.option("format") {
+            Some(name) => match Format::parse(name) {
+                Some(format) => Ok(Some(format)),
+                None => Err(format!("Unknown format {}", name)),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/**
+Loads the repository given with `--repository`, if any.
+*/
+fn load_repository(args: &Arguments) -> Result<Option<Repository>, String> {
+    match args.option("repository") {
+        Some(path) => match load::<Repository>(path) {
+            Ok((repo, _)) => Ok(Some(repo)),
+            Err(e) => Err(format!("Could not load the repository {}: {}", path, e)),
+        },
+        None => Ok(None),
+    }
+}
+
+fn describe(position: usize, offer: &Offer) -> String {
+    format!("offer {} ({})", position, offer.get_host())
+}
+
+fn describe_host(offer: &Offer) -> String {
+    format!("offer of {}", offer.get_host())
+}
+
+/**
+Checks that the offer list can be parsed and, given a repository,
+that the repository is consistent and every ID used by the offers exists in it.
+Stale offers are flagged.
+*/
+fn validate(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(1, &["repository"])?;
+    let offers: Offers = match load(paths[0]) {
+        Ok((offers, _)) => offers,
+        Err(e) => {
+            println!("{}: {}", paths[0], e);
+            return Ok(false);
+        }
+    };
+    let repo = load_repository(args)?;
+    let now = unix_time();
+
+    let mut valid = true;
+    if let Some(ref repo) = repo {
+        for issue in repo.validate() {
+            println!("repository: {}", issue);
+            valid = false;
+        }
+    }
+    for (position, offer) in offers.get_offers().iter().enumerate() {
+        let result = match repo {
+            Some(ref repo) => offer.clone().decompress(repo),
+            None => Ok(()),
+        };
+        match result {
+            Ok(_) if offer.is_expired(now) => println!("{}: ok, but expired", describe(position, offer)),
+            Ok(_) => println!("{}: ok", describe(position, offer)),
+            Err(e) => {
+                println!("{}: {}", describe(position, offer), e);
+                valid = false;
+            }
+        }
+    }
+    Ok(valid)
+}
 
-    let mut offers:Offers = Offers::from_json_file("offerlist.json");
-    let repo:Repository = Repository::from_json_file("repository.json");
+fn compress(args: &Arguments, decompress: bool) -> Result<bool, String> {
+    let paths = args.expect(3, &["format"])?;
+    let (mut offers, format): (Offers, Format) = load(paths[0])
+        .map_err(|e| format!("Could not load the offers {}: {}", paths[0], e))?;
+    let (repo, _): (Repository, Format) = load(paths[1])
+        .map_err(|e| format!("Could not load the repository {}: {}", paths[1], e))?;
 
-    Offers::to_msgpack_file("offerlist_msgpack.json", &offers);
-    Repository::to_msgpack_file("repository_msgpack.json", &repo);
+    let result = if decompress { offers.decompress(&repo) } else { offers.compress(&repo) };
+    result.map_err(|e| e.to_string())?;
+    save(paths[2], &offers, args.format()?.unwrap_or(format))
+        .map_err(|e| format!("Could not write {}: {}", paths[2], e))?;
+    Ok(true)
+}
 
+/**
+Prints the sufficient offers ranked by their fitness score,
+followed by the reasons why the other offers do not match.
+Stale offers are left out, as are offers without a valid signature if host keys are given.
+*/
+fn match_offers(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(2, &["repository", "weights", "keys"])?;
+    let (mut request, _): (Offer, Format) = load(paths[0])
+        .map_err(|e| format!("Could not load the request {}: {}", paths[0], e))?;
+    let (mut offers, _): (Offers, Format) = load(paths[1])
+        .map_err(|e| format!("Could not load the offers {}: {}", paths[1], e))?;
+    if let Some(path) = args.option("keys") {
+        let keys = HostKeys::from_json_file(path)
+            .map_err(|e| format!("Could not load the host keys {}: {}", path, e))?;
+        offers.get_offers_mut().retain(|offer| match verify(offer, &keys) {
+            Ok(()) => true,
+            Err(e) => {
+                println!("{} is rejected: {}", describe_host(offer), e);
+                false
+            }
+        });
+    }
+    if let Some(repo) = load_repository(args)? {
+        request.decompress(&repo).map_err(|e| e.to_string())?;
+        offers.decompress(&repo).map_err(|e| e.to_string())?;
+    }
+    request.normalize();
+    offers.normalize();
+    let weights: RankingWeights = match args.option("weights") {
+        Some(path) => load::<RankingWeights>(path)
+            .map_err(|e| format!("Could not load the weights {}: {}", path, e))?.0,
+        None => RankingWeights::default(),
+    };
+
+    for expired in offers.remove_expired(unix_time()) {
+        println!("{} expired and is ignored", describe_host(&expired));
+    }
 
-    println!("Offers: {:?}", offers);
+    let ranked = ranking(&request, &offers, &weights);
 
-    println!("Compare two elements of the offer list with each other to test the compare function:");
-    {
-        let a:&Offer = offers.get_offers().get(0).unwrap();
-        let b:&Offer = offers.get_offers().get(1).unwrap();
+    println!("{} of {} offers are sufficient:", ranked.len(), offers.get_offers().len());
+    for (rank, ranked_offer) in ranked.iter().enumerate() {
+        let offer = ranked_offer.get_offer();
+        println!("{}. {} score {:.3}", rank + 1, describe(ranked_offer.get_position(), offer), ranked_offer.get_score());
+    }
 
-        match comparing(a,b) {
-            Ok(_) => println!("If 'a' would be a request, 'b' would be a sufficient offer."),
-            Err(e) => println!("There was an error: {:?}", e),
+    for (position, offer) in offers.get_offers().iter().enumerate() {
+        let report = comparing(&request, offer);
+        if !report.is_match() {
+            println!("{} does not match:", describe(position, offer));
+            for failure in report.get_failures() {
+                println!("- {}", failure);
+            }
         }
+    }
+    Ok(!ranked.is_empty())
+}
+
+/**
+Converts an offer list or a repository between JSON and MsgPack.
+*/
+fn convert(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(2, &[])?;
+    let written = match load::<Offers>(paths[0]) {
+        Ok((offers, format)) => save(paths[1], &offers, format.other()),
+        Err(_) => match load::<Repository>(paths[0]) {
+            Ok((repo, format)) => save(paths[1], &repo, format.other()),
+            Err(e) => return Err(format!("{} is neither an offer list nor a repository: {}", paths[0], e)),
+        },
+    };
+    written.map_err(|e| format!("Could not write {}: {}", paths[1], e))?;
+    Ok(true)
+}
 
+fn load_repository_file(path: &str) -> Result<(Repository, Format), String> {
+    load(path).map_err(|e| format!("Could not load the repository {}: {}", path, e))
+}
+
+/**
+Writes the patch from the first repository to the second one.
+*/
+fn diff(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(3, &["format"])?;
+    let (old, format) = load_repository_file(paths[0])?;
+    let (new, _) = load_repository_file(paths[1])?;
+    let patch = RepositoryPatch::between(&old, &new);
+    save(paths[2], &patch, args.format()?.unwrap_or(format))
+        .map_err(|e| format!("Could not write {}: {}", paths[2], e))?;
+    Ok(true)
+}
+
+/**
+Applies a patch to a repository and writes the patched repository.
+*/
+fn patch(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(3, &["format"])?;
+    let (mut repo, format) = load_repository_file(paths[0])?;
+    let (patch, _): (RepositoryPatch, Format) = load(paths[1])
+        .map_err(|e| format!("Could not load the patch {}: {}", paths[1], e))?;
+    patch.apply(&mut repo).map_err(|e| e.to_string())?;
+    save(paths[2], &repo, args.format()?.unwrap_or(format))
+        .map_err(|e| format!("Could not write {}: {}", paths[2], e))?;
+    Ok(true)
+}
+
+/**
+Merges two independently extended repositories, printing the ID collisions if they conflict.
+*/
+fn merge_repositories(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(3, &["format"])?;
+    let (repo, format) = load_repository_file(paths[0])?;
+    let (other, _) = load_repository_file(paths[1])?;
+    match merge(&repo, &other) {
+        Ok(merged) => {
+            save(paths[2], &merged, args.format()?.unwrap_or(format))
+                .map_err(|e| format!("Could not write {}: {}", paths[2], e))?;
+            Ok(true)
+        }
+        Err(e) => {
+            println!("{}", e);
+            Ok(false)
+        }
     }
-    offers.compress(&repo);
-    println!("Compressed offers: {:?}", offers);
+}
+
+/**
+Answers the requests of tenants with the offers, using the versions of the first offer.
+*/
+fn serve(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(3, &[])?;
+    let (mut offers, _): (Offers, Format) = load(paths[1])
+        .map_err(|e| format!("Could not load the offers {}: {}", paths[1], e))?;
+    let (repo, _): (Repository, Format) = load(paths[2])
+        .map_err(|e| format!("Could not load the repository {}: {}", paths[2], e))?;
+    offers.decompress(&repo).map_err(|e| e.to_string())?;
+    offers.normalize();
+    let header = match offers.get_offers().first().and_then(Header::of) {
+        Some(header) => header,
+        None => return Err(format!("The first offer of {} has no ontology and repository version", paths[1])),
+    };
 
+    let server = MatchServer::bind(paths[0], header, offers, repo).map_err(|e| e.to_string())?;
+    println!("listening on {}", server.get_address());
+    loop {
+        thread::park();
+    }
+}
 
-    offers.decompress(&repo);
-    println!("Decompressed offers!!: {:?}", offers);
+/**
+Submits a request to a node and prints the candidates it sends.
+*/
+fn submit(args: &Arguments) -> Result<bool, String> {
+    let paths = args.expect(3, &["accept"])?;
+    let address = paths[0].parse().map_err(|_| format!("Invalid address {}", paths[0]))?;
+    let (mut request, _): (Offer, Format) = load(paths[1])
+        .map_err(|e| format!("Could not load the request {}: {}", paths[1], e))?;
+    let (repo, _): (Repository, Format) = load(paths[2])
+        .map_err(|e| format!("Could not load the repository {}: {}", paths[2], e))?;
+    request.decompress(&repo).map_err(|e| e.to_string())?;
+    request.normalize();
+    let header = match Header::of(&request) {
+        Some(header) => header,
+        None => return Err(format!("The request {} has no ontology and repository version", paths[1])),
+    };
 
+    let mut client = MatchClient::connect(&address, header, repo).map_err(|e| e.to_string())?;
+    let candidates = match client.submit(0, &request) {
+        Ok(candidates) => candidates,
+        Err(e) => {
+            println!("{}", e);
+            return Ok(false);
+        }
+    };
+    println!("{} candidates:", candidates.len());
+    for candidate in &candidates {
+        println!("- {} {}", candidate.get_offer_id(), describe_host(candidate.get_offer()));
+    }
+    match args.option("accept") {
+        Some(offer_id) => {
+            client.accept(0, offer_id).map_err(|e| e.to_string())?;
+            println!("accepted {}", offer_id);
+        }
+        None => client.withdraw(0).map_err(|e| e.to_string())?,
+    }
+    Ok(true)
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() {
+        println!("{}", USAGE);
+        process::exit(2);
+    }
+    let command = args.remove(0);
+
+    let result = Arguments::parse(args).and_then(|args| match command.as_str() {
+        "validate" => validate(&args),
+        "compress" => compress(&args, false),
+        "decompress" => compress(&args, true),
+        "match" => match_offers(&args),
+        "convert" => convert(&args),
+        "diff" => diff(&args),
+        "patch" => patch(&args),
+        "merge" => merge_repositories(&args),
+        "serve" => serve(&args),
+        "submit" => submit(&args),
+        _ => Err(format!("Unknown command {}", command)),
+    });
+
+    match result {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    }
 }
@@ -15,50 +15,64 @@ use std::fs::File;
 use std::io::Read;
 use std::cmp::Ordering;
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std;
 
 
-use basicElements::{BasicElement,IntElement,FloatElement,StringElement,MagnitudeElement};
-use basicElements::{InstructionSet, DiskType,MemoryGeneration};
-use serde_json::{Value, Error};
+use basicElements::{BasicElement,IntElement,FloatElement,StringElement,MagnitudeElement,VersionElement,Quantity};
+use basicElements::{InstructionSet, DiskType, MemoryGeneration, GenerationElement};
+use serde_json::Value;
 
-use repository::Repository;
+use repository::{Repository, compress_element, decompress_element, compress_magnitudes, decompress_magnitudes};
+use error::OntologyError;
+use optional;
+use canonical::offer_id;
+use report::MatchReport;
 
-
-enum ontology_errors {
-    compare_error(String)
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Application {
     name: Option<StringElement>,
-    version: Option<StringElement>,
+    version: Option<VersionElement>,
 }
 
 impl Application {
-    fn compare(&self, app: &Application) -> bool {
+    pub fn new() -> Application { Application::default() }
+
+    pub fn get_name(&self) -> &Option<StringElement> { &self.name }
+    pub fn get_name_mut(&mut self) -> &mut Option<StringElement> { &mut self.name }
+    pub fn set_name(&mut self, name: Option<StringElement>) { self.name = name; }
+    pub fn with_name(mut self, name: StringElement) -> Application { self.name = Some(name); self }
+
+    pub fn get_version(&self) -> &Option<VersionElement> { &self.version }
+    pub fn get_version_mut(&mut self) -> &mut Option<VersionElement> { &mut self.version }
+    pub fn set_version(&mut self, version: Option<VersionElement>) { self.version = version; }
+    pub fn with_version(mut self, version: VersionElement) -> Application { self.version = Some(version); self }
+
+    fn compare(&self, app: &Application, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
 
         if let Some(ref name_a) = self.name {
             if let Some(ref name_b) = app.name {
-                result &= name_a.compare(name_b);
+                result &= report.record(format!("{}.name", path), name_a, name_b,
+                                        name_a.get_compare_operator(), name_a.compare(name_b));
             }
         }
 
         if let Some(ref version_a) = self.version {
             if let Some(ref version_b) = app.version {
-                result &= version_a.compare(version_b);
+                result &= report.record(format!("{}.version", path), version_a, version_b,
+                                        version_a.get_compare_operator(), version_a.compare(version_b));
             }
         }
 
         result
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {}
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {}
+    fn compress_magnitude_elements(&mut self, _mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> { Ok(()) }
+    fn decompress_magnitude_elements(&mut self, _mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> { Ok(()) }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct CPU {
     manufacturer: Option<StringElement>,
     amount: Option<IntElement>,
@@ -69,268 +83,230 @@ pub struct CPU {
 
 
 impl CPU {
-    fn compress(&mut self, repo: &Repository) {
-        let frequencies = repo.get_cpu().get_frequency();
-        for cpu_frequency in frequencies {
-            match self.frequency {
-                Some(ref mut frequency) => {
-                    if frequency.get_value() == cpu_frequency.get_value() {
-                        frequency.set_id(cpu_frequency.get_id().unwrap().clone());
-                        frequency.set_value(None);
-                    }
-                }
-                None => ()
-            }
+    pub fn new() -> CPU { CPU::default() }
+
+    pub fn get_manufacturer(&self) -> &Option<StringElement> { &self.manufacturer }
+    pub fn get_manufacturer_mut(&mut self) -> &mut Option<StringElement> { &mut self.manufacturer }
+    pub fn set_manufacturer(&mut self, manufacturer: Option<StringElement>) { self.manufacturer = manufacturer; }
+    pub fn with_manufacturer(mut self, manufacturer: StringElement) -> CPU { self.manufacturer = Some(manufacturer); self }
+
+    pub fn get_amount(&self) -> &Option<IntElement> { &self.amount }
+    pub fn get_amount_mut(&mut self) -> &mut Option<IntElement> { &mut self.amount }
+    pub fn set_amount(&mut self, amount: Option<IntElement>) { self.amount = amount; }
+    pub fn with_amount(mut self, amount: IntElement) -> CPU { self.amount = Some(amount); self }
+
+    pub fn get_frequency(&self) -> &Option<FloatElement> { &self.frequency }
+    pub fn get_frequency_mut(&mut self) -> &mut Option<FloatElement> { &mut self.frequency }
+    pub fn set_frequency(&mut self, frequency: Option<FloatElement>) { self.frequency = frequency; }
+    pub fn with_frequency(mut self, frequency: FloatElement) -> CPU { self.frequency = Some(frequency); self }
+
+    pub fn get_instruction_set(&self) -> &Vec<BasicElement<InstructionSet>> { &self.instruction_set }
+    pub fn get_instruction_set_mut(&mut self) -> &mut Vec<BasicElement<InstructionSet>> { &mut self.instruction_set }
+    pub fn set_instruction_set(&mut self, instruction_set: Vec<BasicElement<InstructionSet>>) { self.instruction_set = instruction_set; }
+    pub fn with_instruction_set(mut self, instruction_set: Vec<BasicElement<InstructionSet>>) -> CPU { self.instruction_set = instruction_set; self }
+
+    fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.frequency {
+            Some(ref mut frequency) => compress_element(frequency, repo.get_cpu().get_frequency(), "cpu.frequency")?,
+            None => ()
         }
 
-        let manufacturers = repo.get_cpu().get_manufacturer();
-        for cpu_manufacturer in manufacturers {
-            match self.manufacturer {
-                Some(ref mut manufacturer) => {
-                    if manufacturer.get_value() == cpu_manufacturer.get_value() {
-                        manufacturer.set_id(cpu_manufacturer.get_id().unwrap().clone());
-                        manufacturer.set_value(None);
-                    }
-                }
-                None => ()
-            }
+        match self.manufacturer {
+            Some(ref mut manufacturer) => compress_element(manufacturer, repo.get_cpu().get_manufacturer(), "cpu.manufacturer")?,
+            None => ()
         }
 
-
-        let instruction_set_extensions = repo.get_cpu().get_instruction_set();
-        for cpu_instruction_sets in instruction_set_extensions {
-            for mut instruction_set_element in self.instruction_set.iter_mut() {
-                if instruction_set_element.get_value() == cpu_instruction_sets.get_value() {
-                    instruction_set_element.set_id(cpu_instruction_sets.get_id().unwrap().clone());
-                    instruction_set_element.set_value(None);
-                }
-            }
+        for instruction_set_element in self.instruction_set.iter_mut() {
+            compress_element(instruction_set_element, repo.get_cpu().get_instruction_set(), "cpu.instruction_set")?;
         }
+        Ok(())
     }
 
-    fn decompress(&mut self, repo: &Repository) {
-        let frequencies = repo.get_cpu().get_frequency();
-        for cpu_frequency in frequencies {
-            match self.frequency {
-                Some(ref mut frequency) => {
-                    if frequency.get_id() == cpu_frequency.get_id() {
-                        frequency.set_value(cpu_frequency.get_value().clone());
-                    }
-                }
-                None => ()
-            }
+    fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.frequency {
+            Some(ref mut frequency) => decompress_element(frequency, repo.get_cpu().get_frequency(), "cpu.frequency")?,
+            None => ()
         }
 
-        let manufacturers = repo.get_cpu().get_manufacturer();
-        for cpu_manufacturer in manufacturers {
-            match self.manufacturer {
-                Some(ref mut manufacturer) => {
-                    if manufacturer.get_id() == cpu_manufacturer.get_id() {
-                        manufacturer.set_value(cpu_manufacturer.get_value().clone());
-                    }
-                }
-                None => ()
-            }
+        match self.manufacturer {
+            Some(ref mut manufacturer) => decompress_element(manufacturer, repo.get_cpu().get_manufacturer(), "cpu.manufacturer")?,
+            None => ()
         }
 
+        for instruction_set_element in self.instruction_set.iter_mut() {
+            decompress_element(instruction_set_element, repo.get_cpu().get_instruction_set(), "cpu.instruction_set")?;
+        }
+        Ok(())
+    }
 
-        let instruction_set_extensions = repo.get_cpu().get_instruction_set();
-        for cpu_instruction_sets in instruction_set_extensions {
-            for mut instruction_set_element in self.instruction_set.iter_mut() {
-                if instruction_set_element.get_id() == cpu_instruction_sets.get_id() {
-                    instruction_set_element.set_value(cpu_instruction_sets.get_value().clone());
-                }
-            }
+    /**
+    Returns every quantity of the CPU, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut amount) = self.amount {
+            quantities.push(amount);
+        }
+        if let Some(ref mut frequency) = self.frequency {
+            quantities.push(frequency);
         }
+        quantities
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.amount {
-                Some(ref mut amount) => {
-                    if amount.get_magnitude().clone().get_magnitude() == mg.get_magnitude() {
-                        amount.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        amount.get_magnitude_mut().set_magnitude(None);
-                    }
-                }
-                None => ()
-            }
-            match self.frequency {
-                Some(ref mut frequency) => {
-                    if frequency.get_magnitude().clone().get_magnitude() == mg.get_magnitude() {
-                        frequency.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        frequency.get_magnitude_mut().set_magnitude(None);
-                    }
-                }
-                None => ()
-            }
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.amount {
+            Some(ref mut amount) => compress_magnitudes(amount.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.frequency {
+            Some(ref mut frequency) => compress_magnitudes(frequency.get_magnitudes_mut(), mag)?,
+            None => ()
         }
+        Ok(())
     }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.amount {
-                Some(ref mut amount) => {
-                    if amount.get_magnitude().clone().get_id() == mg.get_id() {
-                        amount.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        amount.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                    }
-                }
-                None => ()
-            }
-            match self.frequency {
-                Some(ref mut frequency) => {
-                    if frequency.get_magnitude().clone().get_id() == mg.get_id() {
-                        frequency.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                    }
-                }
-                None => ()
-            }
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.amount {
+            Some(ref mut amount) => decompress_magnitudes(amount.get_magnitudes_mut(), mag)?,
+            None => ()
         }
+        match self.frequency {
+            Some(ref mut frequency) => decompress_magnitudes(frequency.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        Ok(())
     }
 
-    fn compare(&self, b: &CPU) -> bool {
+    fn compare(&self, b: &CPU, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
         if let Some(ref manufacturer_a) = self.manufacturer {
             if let Some(ref manufacturer_b) = b.manufacturer {
-                result &= manufacturer_a.compare(manufacturer_b);
+                result &= report.record(format!("{}.manufacturer", path), manufacturer_a, manufacturer_b,
+                                        manufacturer_a.get_compare_operator(), manufacturer_a.compare(manufacturer_b));
             }
         }
         if let Some(ref amount_a) = self.amount {
             if let Some(ref amount_b) = b.amount {
-                result &= amount_a.compare(&amount_b);
+                result &= report.record_with_fitness(format!("{}.amount", path), amount_a, amount_b,
+                                        amount_a.get_compare_operator(), amount_a.compare(&amount_b),
+                                        amount_a.fitness(&amount_b));
             }
         }
         if let Some(ref frequency_a) = self.frequency {
             if let Some(ref frequency_b) = b.frequency {
-                result &= frequency_a.compare(frequency_b);
+                result &= report.record_with_fitness(format!("{}.frequency", path), frequency_a, frequency_b,
+                                        frequency_a.get_compare_operator(), frequency_a.compare(frequency_b),
+                                        frequency_a.fitness(frequency_b));
             }
         }
-        let mut subset: bool = true;
-        for set_a in &self.instruction_set {
+
+        let offered: Vec<String> = b.instruction_set.iter().map(|set_b| set_b.to_string()).collect();
+        let offered = format!("[{}]", offered.join(", "));
+        for (i, set_a) in self.instruction_set.iter().enumerate() {
             let mut element_of: bool = false;
             for set_b in &b.instruction_set {
-                if set_a.get_value() == set_b.get_value() {
-                    element_of = true;
-                }
+                element_of |= match (set_a.get_value(), set_b.get_value()) {
+                    (&Some(ref requested), &Some(ref supported)) => supported.implies_set(requested),
+                    (requested, supported) => requested == supported,
+                };
             }
-            subset &= element_of;
+            result &= report.record(format!("{}.instruction_set[{}]", path, i), set_a, &offered, &"implied by", element_of);
         }
-        result &= subset;
 
         result
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Memory {
     size: Option<FloatElement>,
-    generation: Option<BasicElement<MemoryGeneration>>,
+    generation: Option<GenerationElement>,
 }
 
 
 
 impl Memory {
-    fn compress(&mut self, repo: &Repository) {
-        let magnitude = repo.get_magnitude();
-        self.compress_magnitude_elements(magnitude);
+    pub fn new() -> Memory { Memory::default() }
 
+    pub fn get_size(&self) -> &Option<FloatElement> { &self.size }
+    pub fn get_size_mut(&mut self) -> &mut Option<FloatElement> { &mut self.size }
+    pub fn set_size(&mut self, size: Option<FloatElement>) { self.size = size; }
+    pub fn with_size(mut self, size: FloatElement) -> Memory { self.size = Some(size); self }
 
-        let memory_sizes = repo.get_memory().get_size();
+    pub fn get_generation(&self) -> &Option<GenerationElement> { &self.generation }
+    pub fn get_generation_mut(&mut self) -> &mut Option<GenerationElement> { &mut self.generation }
+    pub fn set_generation(&mut self, generation: Option<GenerationElement>) { self.generation = generation; }
+    pub fn with_generation(mut self, generation: GenerationElement) -> Memory { self.generation = Some(generation); self }
+
+    fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
         match self.size {
-            Some(ref mut size) => {
-                for memory in memory_sizes {
-                    if size.get_value() == memory.get_value() {
-                        size.set_id(memory.get_id().unwrap().clone());
-                        size.set_value(None);
-                    }
-                }
-            }
+            Some(ref mut size) => compress_element(size, repo.get_memory().get_size(), "memory.size")?,
             None => ()
         }
 
-        let memory_generation = repo.get_memory().get_generation();
         match self.generation {
-            Some(ref mut generation) => {
-                for gen in memory_generation {
-                    if generation.get_value() == gen.get_value() {
-                        generation.set_id(gen.get_id().unwrap().clone());
-                        generation.set_value(None);
-                    }
-                }
-            }
-            None => {}
+            Some(ref mut generation) => compress_element(generation, repo.get_memory().get_generation(), "memory.generation")?,
+            None => ()
         }
-    }
-
 
-    fn decompress(&mut self, repo: &Repository) {
-        let magnitude = repo.get_magnitude();
-        self.decompress_magnitude_elements(magnitude);
+        Ok(())
+    }
 
 
-        let memory_sizes = repo.get_memory().get_size();
+    fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
         match self.size {
-            Some(ref mut size) => {
-                for memory in memory_sizes {
-                    if size.get_id() == memory.get_id() {
-                        size.set_value(memory.get_value().clone());
-                    }
-                }
-            }
+            Some(ref mut size) => decompress_element(size, repo.get_memory().get_size(), "memory.size")?,
             None => ()
         }
 
-        let memory_generation = repo.get_memory().get_generation();
         match self.generation {
-            Some(ref mut generation) => {
-                for gen in memory_generation {
-                    if generation.get_id() == gen.get_id() {
-                        generation.set_value(gen.get_value().clone());
-                    }
-                }
-            }
-            None => {}
+            Some(ref mut generation) => decompress_element(generation, repo.get_memory().get_generation(), "memory.generation")?,
+            None => ()
+        }
+
+        Ok(())
+    }
+
+    /**
+    Returns every quantity of the memory, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut size) = self.size {
+            quantities.push(size);
         }
+        quantities
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
         match self.size {
-            Some(ref mut size) => {
-                for mg in mag {
-                    if size.get_magnitude().clone().get_magnitude() == mg.get_magnitude() {
-                        size.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        size.get_magnitude_mut().set_magnitude(None);
-                    }
-                }
-            }
+            Some(ref mut size) => compress_magnitudes(size.get_magnitudes_mut(), mag)?,
             None => ()
         }
+        Ok(())
     }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
         match self.size {
-            Some(ref mut size) => {
-                for mg in mag {
-                    if size.get_magnitude().clone().get_id() == mg.get_id() {
-                        size.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                    }
-                }
-            }
+            Some(ref mut size) => decompress_magnitudes(size.get_magnitudes_mut(), mag)?,
             None => ()
         }
+        Ok(())
     }
 
-    fn compare(&self, b: &Memory) -> bool {
+    fn compare(&self, b: &Memory, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
         if let Some(ref size_a) = self.size {
             if let Some(ref size_b) = b.size {
-                result &= size_a.compare(&size_b);
+                result &= report.record_with_fitness(format!("{}.size", path), size_a, size_b,
+                                        size_a.get_compare_operator(), size_a.compare(&size_b),
+                                        size_a.fitness(&size_b));
             }
         }
         if let Some(ref generation_a) = self.generation {
             if let Some(ref generation_b) = b.generation {
-                result &= generation_a.get_value() <= generation_b.get_value();
+                result &= report.record(format!("{}.generation", path), generation_a, generation_b,
+                                        generation_a.get_compare_operator(), generation_a.compare(generation_b));
             }
         }
 
@@ -346,75 +322,114 @@ impl std::fmt::Display for MemoryGeneration {
             MemoryGeneration::DDR2RAM => "DDR2-RAM",
             MemoryGeneration::DDR3RAM => "DDR3-RAM",
             MemoryGeneration::DDR4RAM => "DDR4-RAM",
+            MemoryGeneration::DDR5RAM => "DDR5-RAM",
+            MemoryGeneration::LPDDR4RAM => "LPDDR4-RAM",
+            MemoryGeneration::LPDDR5RAM => "LPDDR5-RAM",
+            MemoryGeneration::HBM => "HBM",
+            MemoryGeneration::HBM2 => "HBM2",
+            MemoryGeneration::HBM3 => "HBM3",
         };
         write!(f, "{}", generation)
     }
 }
 
 impl std::str::FromStr for MemoryGeneration {
-    type Err = ();
+    type Err = OntologyError;
 
-    fn from_str(s: &str) -> Result<MemoryGeneration, ()> {
+    fn from_str(s: &str) -> Result<MemoryGeneration, OntologyError> {
         match s {
             "DDRRAM" => Ok(MemoryGeneration::DDRRAM),
             "DDR2RAM" => Ok(MemoryGeneration::DDR2RAM),
             "DDR3RAM" => Ok(MemoryGeneration::DDR3RAM),
             "DDR4RAM" => Ok(MemoryGeneration::DDR4RAM),
-            _ => {
-                println!("Error in String to MemoryGeneration parsing");
-                Ok(MemoryGeneration::DDRRAM)
-            }
+            "DDR5RAM" => Ok(MemoryGeneration::DDR5RAM),
+            "LPDDR4RAM" => Ok(MemoryGeneration::LPDDR4RAM),
+            "LPDDR5RAM" => Ok(MemoryGeneration::LPDDR5RAM),
+            "HBM" => Ok(MemoryGeneration::HBM),
+            "HBM2" => Ok(MemoryGeneration::HBM2),
+            "HBM3" => Ok(MemoryGeneration::HBM3),
+            _ => Err(OntologyError::UnknownEnumValue(format!("{} is not a valid MemoryGeneration", s)))
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct OperatingSystem {
     system_type: Option<StringElement>,
     repository: Option<StringElement>,
-    version: Option<StringElement>,
+    version: Option<VersionElement>,
     custom_template: Option<bool>,
 }
 
 
 impl OperatingSystem {
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {}
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {}
+    pub fn new() -> OperatingSystem { OperatingSystem::default() }
+
+    pub fn get_system_type(&self) -> &Option<StringElement> { &self.system_type }
+    pub fn get_system_type_mut(&mut self) -> &mut Option<StringElement> { &mut self.system_type }
+    pub fn set_system_type(&mut self, system_type: Option<StringElement>) { self.system_type = system_type; }
+    pub fn with_system_type(mut self, system_type: StringElement) -> OperatingSystem { self.system_type = Some(system_type); self }
+
+    pub fn get_repository(&self) -> &Option<StringElement> { &self.repository }
+    pub fn get_repository_mut(&mut self) -> &mut Option<StringElement> { &mut self.repository }
+    pub fn set_repository(&mut self, repository: Option<StringElement>) { self.repository = repository; }
+    pub fn with_repository(mut self, repository: StringElement) -> OperatingSystem { self.repository = Some(repository); self }
+
+    pub fn get_version(&self) -> &Option<VersionElement> { &self.version }
+    pub fn get_version_mut(&mut self) -> &mut Option<VersionElement> { &mut self.version }
+    pub fn set_version(&mut self, version: Option<VersionElement>) { self.version = version; }
+    pub fn with_version(mut self, version: VersionElement) -> OperatingSystem { self.version = Some(version); self }
+
+    pub fn get_custom_template(&self) -> &Option<bool> { &self.custom_template }
+    pub fn get_custom_template_mut(&mut self) -> &mut Option<bool> { &mut self.custom_template }
+    pub fn set_custom_template(&mut self, custom_template: Option<bool>) { self.custom_template = custom_template; }
+    pub fn with_custom_template(mut self, custom_template: bool) -> OperatingSystem { self.custom_template = Some(custom_template); self }
+
+    fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.system_type {
+            Some(ref mut system_type) => compress_element(system_type, repo.get_operating_system().get_system_type(), "operating_system.system_type")?,
+            None => ()
+        }
+        Ok(())
+    }
+
+    fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.system_type {
+            Some(ref mut system_type) => decompress_element(system_type, repo.get_operating_system().get_system_type(), "operating_system.system_type")?,
+            None => ()
+        }
+        Ok(())
+    }
 
-    fn compare(&self, b: &OperatingSystem) -> bool {
+    fn compress_magnitude_elements(&mut self, _mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> { Ok(()) }
+    fn decompress_magnitude_elements(&mut self, _mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> { Ok(()) }
+
+    fn compare(&self, b: &OperatingSystem, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
         if let Some(ref system_type_a) = self.system_type {
             if let Some(ref system_type_b) = b.system_type {
-                result &= system_type_a.compare(system_type_b);
-                if !result {
-                    println!("- System Type is not the same");
-                }
+                result &= report.record(format!("{}.system_type", path), system_type_a, system_type_b,
+                                        system_type_a.get_compare_operator(), system_type_a.compare(system_type_b));
             }
         }
         if let Some(ref repository_a) = self.repository {
             if let Some(ref repository_b) = b.repository {
-                result &= repository_a.compare(repository_b);
-                if !result {
-                    println!("- Repository is not the same");
-                }
+                result &= report.record(format!("{}.repository", path), repository_a, repository_b,
+                                        repository_a.get_compare_operator(), repository_a.compare(repository_b));
             }
         }
 
         if let Some(ref version_a) = self.version {
             if let Some(ref version_b) = b.version {
-                result &= version_a.compare(version_b);
-                if !result {
-                    println!("- Version is not the same");
-                }
+                result &= report.record(format!("{}.version", path), version_a, version_b,
+                                        version_a.get_compare_operator(), version_a.compare(version_b));
             }
         }
 
         if let Some(ref custom_template_a) = self.custom_template {
             if let Some(ref custom_template_b) = b.custom_template {
-                result &= custom_template_a == custom_template_b;
-                if !result {
-                    println!("- Custom Template is not the same");
-                }
+                result &= report.record(format!("{}.custom_template", path), custom_template_a, custom_template_b,
+                                        &"eq", custom_template_a == custom_template_b);
             }
         }
 
@@ -424,7 +439,7 @@ impl OperatingSystem {
 
 
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Disk {
     diskType: Option<BasicElement<DiskType>>,
     size: Option<IntElement>,
@@ -432,284 +447,344 @@ pub struct Disk {
 }
 
 impl Disk {
-    fn compare(&self, dsk: &Disk) -> bool {
+    pub fn new() -> Disk { Disk::default() }
+
+    pub fn get_disk_type(&self) -> &Option<BasicElement<DiskType>> { &self.diskType }
+    pub fn get_disk_type_mut(&mut self) -> &mut Option<BasicElement<DiskType>> { &mut self.diskType }
+    pub fn set_disk_type(&mut self, disk_type: Option<BasicElement<DiskType>>) { self.diskType = disk_type; }
+    pub fn with_disk_type(mut self, disk_type: BasicElement<DiskType>) -> Disk { self.diskType = Some(disk_type); self }
+
+    pub fn get_size(&self) -> &Option<IntElement> { &self.size }
+    pub fn get_size_mut(&mut self) -> &mut Option<IntElement> { &mut self.size }
+    pub fn set_size(&mut self, size: Option<IntElement>) { self.size = size; }
+    pub fn with_size(mut self, size: IntElement) -> Disk { self.size = Some(size); self }
+
+    pub fn get_performance(&self) -> &Option<DiskPerformance> { &self.performance }
+    pub fn get_performance_mut(&mut self) -> &mut Option<DiskPerformance> { &mut self.performance }
+    pub fn set_performance(&mut self, performance: Option<DiskPerformance>) { self.performance = performance; }
+    pub fn with_performance(mut self, performance: DiskPerformance) -> Disk { self.performance = Some(performance); self }
+
+    fn compare(&self, dsk: &Disk, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
 
         if let Some(ref size_a) = self.size {
             if let Some(ref size_b) = dsk.size {
-                result &= size_a.compare(size_b);
+                result &= report.record_with_fitness(format!("{}.size", path), size_a, size_b,
+                                        size_a.get_compare_operator(), size_a.compare(size_b),
+                                        size_a.fitness(size_b));
             }
         }
 
         if let Some(ref diskType_a) = self.diskType {
             if let Some(ref diskType_b) = dsk.diskType {
-                result &= diskType_a.compare(diskType_b);
+                result &= report.record(format!("{}.diskType", path), diskType_a, diskType_b,
+                                        &"eq", diskType_a.compare(diskType_b));
             }
         }
 
-        if let Some(ref performance_a) = self.size {
-            if let Some(ref performance_b) = dsk.size {
-                result &= performance_a.compare(performance_b);
+        if let Some(ref performance_a) = self.performance {
+            if let Some(ref performance_b) = dsk.performance {
+                result &= performance_a.compare(performance_b, &format!("{}.performance", path), report);
             }
         }
         result
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.size {
-                Some(ref mut size) => {
-                    if size.get_magnitude().clone().get_magnitude() == mg.get_magnitude() {
-                        size.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        size.get_magnitude_mut().set_magnitude(None);
-                    }
-                }
-                None => ()
-            }
-            match self.performance {
-                Some(ref mut performance) => {
-                    performance.compress_magnitude_elements(&mag);
-                }
-                None => ()
-            }
+    /**
+    Returns every quantity of the disk, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut size) = self.size {
+            quantities.push(size);
+        }
+        if let Some(ref mut performance) = self.performance {
+            quantities.extend(performance.quantities_mut());
         }
+        quantities
     }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.size {
-                Some(ref mut size) => {
-                    if size.get_magnitude().clone().get_id() == mg.get_id() {
-                        size.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                    }
-                }
-                None => ()
-            }
-            match self.performance {
-                Some(ref mut performance) => {
-                    performance.decompress_magnitude_elements(&mag);
-                }
-                None => ()
-            }
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.size {
+            Some(ref mut size) => compress_magnitudes(size.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.performance {
+            Some(ref mut performance) => performance.compress_magnitude_elements(mag)?,
+            None => ()
         }
+        Ok(())
     }
 
-    fn compress(&mut self, repo: &Repository) {
-        let disk_types = repo.get_disk().get_disk_type();
-        for disk_type in disk_types {
-            match self.diskType {
-                Some(ref mut diskType) => {
-                    if diskType.get_value() == disk_type.get_value() {
-                        diskType.set_id(disk_type.get_id().unwrap().clone());
-                        diskType.set_value(None);
-                    }
-                }
-                None => ()
-            }
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.size {
+            Some(ref mut size) => decompress_magnitudes(size.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.performance {
+            Some(ref mut performance) => performance.decompress_magnitude_elements(mag)?,
+            None => ()
         }
+        Ok(())
     }
 
-    fn decompress(&mut self, repo: &Repository) {
-        let disk_types = repo.get_disk().get_disk_type();
-        for disk_type in disk_types {
-            match self.diskType {
-                Some(ref mut diskType) => {
-                    if diskType.get_id() == disk_type.get_id() {
-                        diskType.set_value(disk_type.get_value().clone());
-                    }
-                }
-                None => ()
-            }
+    fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.diskType {
+            Some(ref mut diskType) => compress_element(diskType, repo.get_disk().get_disk_type(), "disk.disk_type")?,
+            None => ()
+        }
+        Ok(())
+    }
+
+    fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.diskType {
+            Some(ref mut diskType) => decompress_element(diskType, repo.get_disk().get_disk_type(), "disk.disk_type")?,
+            None => ()
         }
+        Ok(())
     }
 }
 
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct DiskPerformance {
     read_performance: Option<IntElement>,
     write_performance: Option<IntElement>,
 }
 
 impl DiskPerformance {
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.read_performance {
-                Some(ref mut read_performance) => {
-                    if read_performance.get_magnitude().get_magnitude() == mg.get_magnitude() {
-                        read_performance.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        read_performance.get_magnitude_mut().set_magnitude(None);
-                    }
-                }
-                None => ()
+    pub fn new() -> DiskPerformance { DiskPerformance::default() }
+
+    pub fn get_read_performance(&self) -> &Option<IntElement> { &self.read_performance }
+    pub fn get_read_performance_mut(&mut self) -> &mut Option<IntElement> { &mut self.read_performance }
+    pub fn set_read_performance(&mut self, read_performance: Option<IntElement>) { self.read_performance = read_performance; }
+    pub fn with_read_performance(mut self, read_performance: IntElement) -> DiskPerformance { self.read_performance = Some(read_performance); self }
+
+    pub fn get_write_performance(&self) -> &Option<IntElement> { &self.write_performance }
+    pub fn get_write_performance_mut(&mut self) -> &mut Option<IntElement> { &mut self.write_performance }
+    pub fn set_write_performance(&mut self, write_performance: Option<IntElement>) { self.write_performance = write_performance; }
+    pub fn with_write_performance(mut self, write_performance: IntElement) -> DiskPerformance { self.write_performance = Some(write_performance); self }
+
+    fn compare(&self, performance: &DiskPerformance, path: &str, report: &mut MatchReport) -> bool {
+        let mut result: bool = true;
+
+        if let Some(ref read_performance_a) = self.read_performance {
+            if let Some(ref read_performance_b) = performance.read_performance {
+                result &= report.record_with_fitness(format!("{}.read_performance", path), read_performance_a, read_performance_b,
+                                        read_performance_a.get_compare_operator(), read_performance_a.compare(read_performance_b),
+                                        read_performance_a.fitness(read_performance_b));
             }
-            match self.write_performance {
-                Some(ref mut write_performance) => {
-                    if write_performance.get_magnitude().get_magnitude() == mg.get_magnitude() {
-                        write_performance.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        write_performance.get_magnitude_mut().set_magnitude(None);
-                    }
-                }
-                None => ()
+        }
+
+        if let Some(ref write_performance_a) = self.write_performance {
+            if let Some(ref write_performance_b) = performance.write_performance {
+                result &= report.record_with_fitness(format!("{}.write_performance", path), write_performance_a, write_performance_b,
+                                        write_performance_a.get_compare_operator(), write_performance_a.compare(write_performance_b),
+                                        write_performance_a.fitness(write_performance_b));
             }
         }
+
+        result
     }
 
+    /**
+    Returns every quantity of the disk performance, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut read_performance) = self.read_performance {
+            quantities.push(read_performance);
+        }
+        if let Some(ref mut write_performance) = self.write_performance {
+            quantities.push(write_performance);
+        }
+        quantities
+    }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.read_performance {
-                Some(ref mut read_performance) => {
-                    if read_performance.get_magnitude().get_id() == mg.get_id() {
-                        read_performance.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                    }
-                }
-                None => ()
-            }
-            match self.write_performance {
-                Some(ref mut write_performance) => {
-                    if write_performance.get_magnitude().get_id() == mg.get_id() {
-                        write_performance.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                    }
-                }
-                None => ()
-            }
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.read_performance {
+            Some(ref mut read_performance) => compress_magnitudes(read_performance.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.write_performance {
+            Some(ref mut write_performance) => compress_magnitudes(write_performance.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        Ok(())
+    }
+
+
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.read_performance {
+            Some(ref mut read_performance) => decompress_magnitudes(read_performance.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.write_performance {
+            Some(ref mut write_performance) => decompress_magnitudes(write_performance.get_magnitudes_mut(), mag)?,
+            None => ()
         }
+        Ok(())
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct NetworkInterface {
     performance: Option<NetworkPerformance>,
     quota: Option<Quota>,
 }
 
 impl NetworkInterface {
-    fn compare(&self, networkInterface: &NetworkInterface) -> bool {
+    pub fn new() -> NetworkInterface { NetworkInterface::default() }
+
+    pub fn get_performance(&self) -> &Option<NetworkPerformance> { &self.performance }
+    pub fn get_performance_mut(&mut self) -> &mut Option<NetworkPerformance> { &mut self.performance }
+    pub fn set_performance(&mut self, performance: Option<NetworkPerformance>) { self.performance = performance; }
+    pub fn with_performance(mut self, performance: NetworkPerformance) -> NetworkInterface { self.performance = Some(performance); self }
+
+    pub fn get_quota(&self) -> &Option<Quota> { &self.quota }
+    pub fn get_quota_mut(&mut self) -> &mut Option<Quota> { &mut self.quota }
+    pub fn set_quota(&mut self, quota: Option<Quota>) { self.quota = quota; }
+    pub fn with_quota(mut self, quota: Quota) -> NetworkInterface { self.quota = Some(quota); self }
+
+    fn compare(&self, networkInterface: &NetworkInterface, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
 
         if let Some(ref performance_a) = self.performance {
             if let Some(ref performance_b) = networkInterface.performance {
-                result &= performance_a.compare(performance_b);
+                result &= performance_a.compare(performance_b, &format!("{}.performance", path), report);
             }
         }
 
         if let Some(ref quota_a) = self.quota {
             if let Some(ref quota_b) = networkInterface.quota {
-                result &= quota_a.compare(quota_b);
+                result &= quota_a.compare(quota_b, &format!("{}.quota", path), report);
             }
         }
 
         result
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
+    /**
+    Returns every quantity of the network interface, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut performance) = self.performance {
+            quantities.extend(performance.quantities_mut());
+        }
+        if let Some(ref mut quota) = self.quota {
+            quantities.extend(quota.quantities_mut());
+        }
+        quantities
+    }
+
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
         match self.performance {
-            Some(ref mut performance) => {
-                performance.compress_magnitude_elements(&mag);
-            }
+            Some(ref mut performance) => performance.compress_magnitude_elements(mag)?,
             None => ()
         }
         match self.quota {
-            Some(ref mut quota) => {
-                quota.compress_magnitude_elements(&mag);
-            }
+            Some(ref mut quota) => quota.compress_magnitude_elements(mag)?,
             None => ()
         }
+        Ok(())
     }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
         match self.performance {
-            Some(ref mut performance) => {
-                performance.decompress_magnitude_elements(&mag);
-            }
+            Some(ref mut performance) => performance.decompress_magnitude_elements(mag)?,
             None => ()
         }
         match self.quota {
-            Some(ref mut quota) => {
-                quota.decompress_magnitude_elements(&mag);
-            }
+            Some(ref mut quota) => quota.decompress_magnitude_elements(mag)?,
             None => ()
         }
+        Ok(())
     }
 }
 
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct NetworkPerformance {
     download_speed: Option<IntElement>,
     upload_speed: Option<IntElement>,
 }
 
 impl NetworkPerformance {
-    fn compare(&self, networkPerformance: &NetworkPerformance) -> bool {
+    pub fn new() -> NetworkPerformance { NetworkPerformance::default() }
+
+    pub fn get_download_speed(&self) -> &Option<IntElement> { &self.download_speed }
+    pub fn get_download_speed_mut(&mut self) -> &mut Option<IntElement> { &mut self.download_speed }
+    pub fn set_download_speed(&mut self, download_speed: Option<IntElement>) { self.download_speed = download_speed; }
+    pub fn with_download_speed(mut self, download_speed: IntElement) -> NetworkPerformance { self.download_speed = Some(download_speed); self }
+
+    pub fn get_upload_speed(&self) -> &Option<IntElement> { &self.upload_speed }
+    pub fn get_upload_speed_mut(&mut self) -> &mut Option<IntElement> { &mut self.upload_speed }
+    pub fn set_upload_speed(&mut self, upload_speed: Option<IntElement>) { self.upload_speed = upload_speed; }
+    pub fn with_upload_speed(mut self, upload_speed: IntElement) -> NetworkPerformance { self.upload_speed = Some(upload_speed); self }
+
+    fn compare(&self, networkPerformance: &NetworkPerformance, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
 
         if let Some(ref download_speed_a) = self.download_speed {
             if let Some(ref download_speed_b) = networkPerformance.download_speed {
-                result &= download_speed_a.compare(download_speed_b);
+                result &= report.record_with_fitness(format!("{}.download_speed", path), download_speed_a, download_speed_b,
+                                        download_speed_a.get_compare_operator(), download_speed_a.compare(download_speed_b),
+                                        download_speed_a.fitness(download_speed_b));
             }
         }
 
         if let Some(ref upload_speed_a) = self.upload_speed {
             if let Some(ref upload_speed_b) = networkPerformance.upload_speed {
-                result &= upload_speed_a.compare(upload_speed_b);
+                result &= report.record_with_fitness(format!("{}.upload_speed", path), upload_speed_a, upload_speed_b,
+                                        upload_speed_a.get_compare_operator(), upload_speed_a.compare(upload_speed_b),
+                                        upload_speed_a.fitness(upload_speed_b));
             }
         }
 
         result
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        {
-            for mg in mag {
-                match self.download_speed {
-                    Some(ref mut download_speed) => {
-                        if download_speed.get_magnitude().get_magnitude() == mg.get_magnitude() {
-                            download_speed.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                            download_speed.get_magnitude_mut().set_magnitude(None);
-                        }
-                    }
-                    None => ()
-                }
-                match self.upload_speed {
-                    Some(ref mut upload_speed) => {
-                        if upload_speed.get_magnitude().get_magnitude() == mg.get_magnitude() {
-                            upload_speed.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                            upload_speed.get_magnitude_mut().set_magnitude(None);
-                        }
-                    }
-                    None => ()
-                }
-            }
+    /**
+    Returns every quantity of the network performance, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut download_speed) = self.download_speed {
+            quantities.push(download_speed);
+        }
+        if let Some(ref mut upload_speed) = self.upload_speed {
+            quantities.push(upload_speed);
         }
+        quantities
     }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        {
-            for mg in mag {
-                match self.download_speed {
-                    Some(ref mut download_speed) => {
-                        if download_speed.get_magnitude().get_id() == mg.get_id() {
-                            download_speed.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                        }
-                    }
-                    None => ()
-                }
-                match self.upload_speed {
-                    Some(ref mut upload_speed) => {
-                        if upload_speed.get_magnitude().get_id() == mg.get_id() {
-                            upload_speed.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                        }
-                    }
-                    None => ()
-                }
-            }
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.download_speed {
+            Some(ref mut download_speed) => compress_magnitudes(download_speed.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.upload_speed {
+            Some(ref mut upload_speed) => compress_magnitudes(upload_speed.get_magnitudes_mut(), mag)?,
+            None => ()
         }
+        Ok(())
+    }
+
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.download_speed {
+            Some(ref mut download_speed) => decompress_magnitudes(download_speed.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.upload_speed {
+            Some(ref mut upload_speed) => decompress_magnitudes(upload_speed.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        Ok(())
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Quota {
     size: Option<IntElement>,
     reset_interval: Option<IntElement>,
@@ -717,136 +792,149 @@ pub struct Quota {
 
 
 impl Quota {
-    fn compare(&self, quota: &Quota) -> bool {
+    pub fn new() -> Quota { Quota::default() }
+
+    pub fn get_size(&self) -> &Option<IntElement> { &self.size }
+    pub fn get_size_mut(&mut self) -> &mut Option<IntElement> { &mut self.size }
+    pub fn set_size(&mut self, size: Option<IntElement>) { self.size = size; }
+    pub fn with_size(mut self, size: IntElement) -> Quota { self.size = Some(size); self }
+
+    pub fn get_reset_interval(&self) -> &Option<IntElement> { &self.reset_interval }
+    pub fn get_reset_interval_mut(&mut self) -> &mut Option<IntElement> { &mut self.reset_interval }
+    pub fn set_reset_interval(&mut self, reset_interval: Option<IntElement>) { self.reset_interval = reset_interval; }
+    pub fn with_reset_interval(mut self, reset_interval: IntElement) -> Quota { self.reset_interval = Some(reset_interval); self }
+
+    fn compare(&self, quota: &Quota, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
 
         if let Some(ref size_a) = self.size {
             if let Some(ref size_b) = quota.size {
-                result &= size_a.compare(size_b);
+                result &= report.record_with_fitness(format!("{}.size", path), size_a, size_b,
+                                        size_a.get_compare_operator(), size_a.compare(size_b),
+                                        size_a.fitness(size_b));
             }
         }
 
         if let Some(ref reset_interval_a) = self.reset_interval {
             if let Some(ref reset_interval_b) = quota.reset_interval {
-                result &= reset_interval_a.compare(reset_interval_b);
+                result &= report.record_with_fitness(format!("{}.reset_interval", path), reset_interval_a, reset_interval_b,
+                                        reset_interval_a.get_compare_operator(), reset_interval_a.compare(reset_interval_b),
+                                        reset_interval_a.fitness(reset_interval_b));
             }
         }
 
         result
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        {
-            for mg in mag {
-                match self.size {
-                    Some(ref mut size) => {
-                        if size.get_magnitude().get_magnitude() == mg.get_magnitude() {
-                            size.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                            size.get_magnitude_mut().set_magnitude(None);
-                        }
-                    }
-                    None => ()
-                }
-                match self.reset_interval {
-                    Some(ref mut reset_interval) => {
-                        if reset_interval.get_magnitude().get_magnitude() == mg.get_magnitude() {
-                            reset_interval.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                            reset_interval.get_magnitude_mut().set_magnitude(None);
-                        }
-                    }
-                    None => ()
-                }
-            }
+    /**
+    Returns every quantity of the quota, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut size) = self.size {
+            quantities.push(size);
         }
+        if let Some(ref mut reset_interval) = self.reset_interval {
+            quantities.push(reset_interval);
+        }
+        quantities
     }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        {
-            for mg in mag {
-                match self.size {
-                    Some(ref mut size) => {
-                        if size.get_magnitude().get_id() == mg.get_id() {
-                            size.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                        }
-                    }
-                    None => ()
-                }
-                match self.reset_interval {
-                    Some(ref mut reset_interval) => {
-                        if reset_interval.get_magnitude().get_id() == mg.get_id() {
-                            reset_interval.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                        }
-                    }
-                    None => ()
-                }
-            }
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.size {
+            Some(ref mut size) => compress_magnitudes(size.get_magnitudes_mut(), mag)?,
+            None => ()
         }
+        match self.reset_interval {
+            Some(ref mut reset_interval) => compress_magnitudes(reset_interval.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        Ok(())
+    }
+
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.size {
+            Some(ref mut size) => decompress_magnitudes(size.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        match self.reset_interval {
+            Some(ref mut reset_interval) => decompress_magnitudes(reset_interval.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        Ok(())
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Virtualization {
     technology: Option<StringElement>,
-    version: Option<IntElement>,
+    version: Option<VersionElement>,
     virtualization_type: Option<StringElement>,
 }
 
 impl Virtualization {
-    fn compare(&self, virt: &Virtualization) -> bool {
+    pub fn new() -> Virtualization { Virtualization::default() }
+
+    pub fn get_technology(&self) -> &Option<StringElement> { &self.technology }
+    pub fn get_technology_mut(&mut self) -> &mut Option<StringElement> { &mut self.technology }
+    pub fn set_technology(&mut self, technology: Option<StringElement>) { self.technology = technology; }
+    pub fn with_technology(mut self, technology: StringElement) -> Virtualization { self.technology = Some(technology); self }
+
+    pub fn get_version(&self) -> &Option<VersionElement> { &self.version }
+    pub fn get_version_mut(&mut self) -> &mut Option<VersionElement> { &mut self.version }
+    pub fn set_version(&mut self, version: Option<VersionElement>) { self.version = version; }
+    pub fn with_version(mut self, version: VersionElement) -> Virtualization { self.version = Some(version); self }
+
+    pub fn get_virtualization_type(&self) -> &Option<StringElement> { &self.virtualization_type }
+    pub fn get_virtualization_type_mut(&mut self) -> &mut Option<StringElement> { &mut self.virtualization_type }
+    pub fn set_virtualization_type(&mut self, virtualization_type: Option<StringElement>) { self.virtualization_type = virtualization_type; }
+    pub fn with_virtualization_type(mut self, virtualization_type: StringElement) -> Virtualization { self.virtualization_type = Some(virtualization_type); self }
+
+    fn compare(&self, virt: &Virtualization, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
 
         if let Some(ref technology_a) = self.technology {
             if let Some(ref technology_b) = virt.technology {
-                result &= technology_a.compare(technology_b);
+                result &= report.record(format!("{}.technology", path), technology_a, technology_b,
+                                        technology_a.get_compare_operator(), technology_a.compare(technology_b));
             }
         }
 
         if let Some(ref version_a) = self.version {
             if let Some(ref version_b) = virt.version {
-                result &= version_a.compare(version_b);
+                result &= report.record(format!("{}.version", path), version_a, version_b,
+                                        version_a.get_compare_operator(), version_a.compare(version_b));
             }
         }
 
         if let Some(ref virtualization_type_a) = self.virtualization_type {
             if let Some(ref virtualization_type_b) = virt.virtualization_type {
-                result &= virtualization_type_a.compare(virtualization_type_b);
+                result &= report.record(format!("{}.virtualization_type", path), virtualization_type_a, virtualization_type_b,
+                                        virtualization_type_a.get_compare_operator(), virtualization_type_a.compare(virtualization_type_b));
             }
         }
         result
     }
     
     
-    fn compress(&mut self, repo: &Repository) {
-        let virtualization_types = repo.get_virtualization().get_virtualization_type();
-        for virtualization_type in virtualization_types {
-            match self.virtualization_type {
-                Some(ref mut virtualizationType) => {
-                    if virtualizationType.get_value() == virtualization_type.get_value() {
-                        virtualizationType.set_id(virtualization_type.get_id().unwrap().clone());
-                        virtualizationType.set_value(None);
-                    }
-                }
-                None => ()
-            }
+    fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.virtualization_type {
+            Some(ref mut virtualization_type) => compress_element(virtualization_type, repo.get_virtualization().get_virtualization_type(), "virtualization.virtualization_type")?,
+            None => ()
         }
+        Ok(())
     }
 
-    fn decompress(&mut self, repo: &Repository) {
-        let virtualization_types = repo.get_virtualization().get_virtualization_type();
-        for virtualization_type in virtualization_types {
-            match self.virtualization_type {
-                Some(ref mut virtualizationType) => {
-                    if virtualizationType.get_id() == virtualization_type.get_id() {
-                        virtualizationType.set_value(virtualization_type.get_value().clone());
-                    }
-                }
-                None => ()
-            }
+    fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match self.virtualization_type {
+            Some(ref mut virtualization_type) => decompress_element(virtualization_type, repo.get_virtualization().get_virtualization_type(), "virtualization.virtualization_type")?,
+            None => ()
         }
+        Ok(())
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Payment {
     price: Option<IntElement>,
     currency: Option<StringElement>,
@@ -854,18 +942,33 @@ pub struct Payment {
 
 
 impl Payment {
-    fn compare(&self, payment: &Payment) -> bool {
+    pub fn new() -> Payment { Payment::default() }
+
+    pub fn get_price(&self) -> &Option<IntElement> { &self.price }
+    pub fn get_price_mut(&mut self) -> &mut Option<IntElement> { &mut self.price }
+    pub fn set_price(&mut self, price: Option<IntElement>) { self.price = price; }
+    pub fn with_price(mut self, price: IntElement) -> Payment { self.price = Some(price); self }
+
+    pub fn get_currency(&self) -> &Option<StringElement> { &self.currency }
+    pub fn get_currency_mut(&mut self) -> &mut Option<StringElement> { &mut self.currency }
+    pub fn set_currency(&mut self, currency: Option<StringElement>) { self.currency = currency; }
+    pub fn with_currency(mut self, currency: StringElement) -> Payment { self.currency = Some(currency); self }
+
+    fn compare(&self, payment: &Payment, path: &str, report: &mut MatchReport) -> bool {
         let mut result: bool = true;
 
         if let Some(ref price_a) = self.price {
             if let Some(ref price_b) = payment.price {
-                result &= price_a.compare(price_b);
+                result &= report.record_with_fitness(format!("{}.price", path), price_a, price_b,
+                                        price_a.get_compare_operator(), price_a.compare(price_b),
+                                        price_a.fitness(price_b));
             }
         }
 
         if let Some(ref currency_a) = self.currency {
             if let Some(ref currency_b) = payment.currency {
-                result &= currency_a.compare(currency_b);
+                result &= report.record(format!("{}.currency", path), currency_a, currency_b,
+                                        currency_a.get_compare_operator(), currency_a.compare(currency_b));
             }
         }
 
@@ -873,41 +976,70 @@ impl Payment {
     }
 
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.price {
-                Some(ref mut price) => {
-                    if price.get_magnitude().get_magnitude() == mg.get_magnitude() {
-                        price.get_magnitude_mut().set_id(mg.get_id().unwrap().clone());
-                        price.get_magnitude_mut().set_magnitude(None);
-                    }
-                }
-                None => ()
-            }
+    /**
+    Returns every quantity of the payment, so that all of them can be normalized or humanized.
+    */
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+        if let Some(ref mut price) = self.price {
+            quantities.push(price);
         }
+        quantities
     }
 
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        for mg in mag {
-            match self.price {
-                Some(ref mut price) => {
-                    if price.get_magnitude().get_id() == mg.get_id() {
-                        price.get_magnitude_mut().set_magnitude(mg.get_magnitude().clone());
-                    }
-                }
-                None => ()
-            }
+    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.price {
+            Some(ref mut price) => compress_magnitudes(price.get_magnitudes_mut(), mag)?,
+            None => ()
+        }
+        Ok(())
+    }
+
+    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+        match self.price {
+            Some(ref mut price) => decompress_magnitudes(price.get_magnitudes_mut(), mag)?,
+            None => ()
         }
+        Ok(())
     }
 }
 
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Offer {
-    host: StringElement,
-    ontology_version: FloatElement,
-    repository_version: FloatElement,
-    timeout: IntElement,
+/**
+Returns every quantity of the sections shared by offers and requirements.
+*/
+fn section_quantities_mut<'a>(network_interface: &'a mut Option<Vec<NetworkInterface>>, cpu: &'a mut Option<CPU>, memory: &'a mut Option<Memory>,
+                              disk: &'a mut Option<Vec<Disk>>, payment: &'a mut Option<Payment>) -> Vec<&'a mut dyn Quantity> {
+    let mut quantities: Vec<&mut dyn Quantity> = Vec::new();
+    if let Some(ref mut network_interfaces) = *network_interface {
+        for interface in network_interfaces.iter_mut() {
+            quantities.extend(interface.quantities_mut());
+        }
+    }
+    if let Some(ref mut cpu) = *cpu {
+        quantities.extend(cpu.quantities_mut());
+    }
+    if let Some(ref mut memory) = *memory {
+        quantities.extend(memory.quantities_mut());
+    }
+    if let Some(ref mut disks) = *disk {
+        for dsk in disks.iter_mut() {
+            quantities.extend(dsk.quantities_mut());
+        }
+    }
+    if let Some(ref mut payment) = *payment {
+        quantities.extend(payment.quantities_mut());
+    }
+    quantities
+}
+
+/**
+A sub-request inside a requirement expression. It holds the same sections
+as an offer and, like a plain request, is satisfied if every present
+section is satisfied.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Requirement {
     operating_system: Option<OperatingSystem>,
     network_interface: Option<Vec<NetworkInterface>>,
     virtualization: Option<Virtualization>,
@@ -918,164 +1050,654 @@ pub struct Offer {
     payment: Option<Payment>,
 }
 
-impl Offer {
+impl Requirement {
+    pub fn new() -> Requirement { Requirement::default() }
+
+    pub fn get_operating_system(&self) -> &Option<OperatingSystem> { &self.operating_system }
+    pub fn get_operating_system_mut(&mut self) -> &mut Option<OperatingSystem> { &mut self.operating_system }
+    pub fn set_operating_system(&mut self, operating_system: Option<OperatingSystem>) { self.operating_system = operating_system; }
+    pub fn with_operating_system(mut self, operating_system: OperatingSystem) -> Requirement { self.operating_system = Some(operating_system); self }
+
+    pub fn get_network_interface(&self) -> &Option<Vec<NetworkInterface>> { &self.network_interface }
+    pub fn get_network_interface_mut(&mut self) -> &mut Option<Vec<NetworkInterface>> { &mut self.network_interface }
+    pub fn set_network_interface(&mut self, network_interface: Option<Vec<NetworkInterface>>) { self.network_interface = network_interface; }
+    pub fn with_network_interface(mut self, network_interface: Vec<NetworkInterface>) -> Requirement { self.network_interface = Some(network_interface); self }
+
+    pub fn get_virtualization(&self) -> &Option<Virtualization> { &self.virtualization }
+    pub fn get_virtualization_mut(&mut self) -> &mut Option<Virtualization> { &mut self.virtualization }
+    pub fn set_virtualization(&mut self, virtualization: Option<Virtualization>) { self.virtualization = virtualization; }
+    pub fn with_virtualization(mut self, virtualization: Virtualization) -> Requirement { self.virtualization = Some(virtualization); self }
+
+    pub fn get_cpu(&self) -> &Option<CPU> { &self.cpu }
+    pub fn get_cpu_mut(&mut self) -> &mut Option<CPU> { &mut self.cpu }
+    pub fn set_cpu(&mut self, cpu: Option<CPU>) { self.cpu = cpu; }
+    pub fn with_cpu(mut self, cpu: CPU) -> Requirement { self.cpu = Some(cpu); self }
+
+    pub fn get_memory(&self) -> &Option<Memory> { &self.memory }
+    pub fn get_memory_mut(&mut self) -> &mut Option<Memory> { &mut self.memory }
+    pub fn set_memory(&mut self, memory: Option<Memory>) { self.memory = memory; }
+    pub fn with_memory(mut self, memory: Memory) -> Requirement { self.memory = Some(memory); self }
+
+    pub fn get_disk(&self) -> &Option<Vec<Disk>> { &self.disk }
+    pub fn get_disk_mut(&mut self) -> &mut Option<Vec<Disk>> { &mut self.disk }
+    pub fn set_disk(&mut self, disk: Option<Vec<Disk>>) { self.disk = disk; }
+    pub fn with_disk(mut self, disk: Vec<Disk>) -> Requirement { self.disk = Some(disk); self }
+
+    pub fn get_application(&self) -> &Option<Vec<Application>> { &self.application }
+    pub fn get_application_mut(&mut self) -> &mut Option<Vec<Application>> { &mut self.application }
+    pub fn set_application(&mut self, application: Option<Vec<Application>>) { self.application = application; }
+    pub fn with_application(mut self, application: Vec<Application>) -> Requirement { self.application = Some(application); self }
+
+    pub fn get_payment(&self) -> &Option<Payment> { &self.payment }
+    pub fn get_payment_mut(&mut self) -> &mut Option<Payment> { &mut self.payment }
+    pub fn set_payment(&mut self, payment: Option<Payment>) { self.payment = payment; }
+    pub fn with_payment(mut self, payment: Payment) -> Requirement { self.payment = Some(payment); self }
+
+    fn sections(&self) -> Sections {
+        Sections {
+            operating_system: &self.operating_system,
+            network_interface: &self.network_interface,
+            virtualization: &self.virtualization,
+            cpu: &self.cpu,
+            memory: &self.memory,
+            disk: &self.disk,
+            application: &self.application,
+            payment: &self.payment,
+        }
+    }
 
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        section_quantities_mut(&mut self.network_interface, &mut self.cpu, &mut self.memory, &mut self.disk, &mut self.payment)
+    }
 
-    pub fn decompress(&mut self, repo: &Repository) {
+    fn sections_mut(&mut self) -> SectionsMut {
+        SectionsMut {
+            operating_system: &mut self.operating_system,
+            network_interface: &mut self.network_interface,
+            virtualization: &mut self.virtualization,
+            cpu: &mut self.cpu,
+            memory: &mut self.memory,
+            disk: &mut self.disk,
+            application: &mut self.application,
+            payment: &mut self.payment,
+        }
+    }
 
-        let magnitude = repo.get_magnitude();
-        self.decompress_magnitude_elements(magnitude);
-        match self.cpu {
-            Some(ref mut cpu) => {
-                cpu.decompress(repo);
+    fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        self.sections_mut().compress(repo)
+    }
+
+    fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        self.sections_mut().decompress(repo)
+    }
+}
+
+/**
+A boolean expression over sub-requests, e.g.
+`{"any": [{"requirement": {"cpu": ...}}, {"not": {"requirement": {"operating_system": ...}}}]}`.
+The expression of a request is evaluated in addition to the sections of the request.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// Satisfied if every sub-expression is satisfied, or if there is none.
+    all(Vec<Expression>),
+    /// Satisfied if at least one sub-expression is satisfied.
+    any(Vec<Expression>),
+    /// Satisfied if the sub-expression is not satisfied.
+    not(Box<Expression>),
+    requirement(Requirement),
+}
+
+impl Expression {
+    /**
+    Evaluates the expression against an offer.
+    `Parameters`
+    * b:&Offer: Offer which is compared to the expression
+    * path:&str: Path of the expression inside the request, e.g. `requirements.any[1]`
+    * report:&mut MatchReport: Report receiving the entries
+    `Return`
+      True if the offer satisfies the expression. For `any` the report receives the
+      entries of the first satisfied sub-expression, or of the closest one if none is
+      satisfied. For `not` it receives a single entry.
+    */
+    pub fn evaluate(&self, b: &Offer, path: &str, report: &mut MatchReport) -> bool {
+        match *self {
+            Expression::all(ref expressions) => {
+                let mut result = true;
+                for (i, expression) in expressions.iter().enumerate() {
+                    result &= expression.evaluate(b, &format!("{}.all[{}]", path, i), report);
+                }
+                result
+            }
+            Expression::any(ref expressions) => {
+                let mut closest: Option<MatchReport> = None;
+                for (i, expression) in expressions.iter().enumerate() {
+                    let mut candidate = MatchReport::new();
+                    if expression.evaluate(b, &format!("{}.any[{}]", path, i), &mut candidate) {
+                        report.append(candidate);
+                        return true;
+                    }
+                    let is_closer = match closest {
+                        Some(ref closest) => candidate.failure_count() < closest.failure_count(),
+                        None => true,
+                    };
+                    if is_closer {
+                        closest = Some(candidate);
+                    }
+                }
+                match closest {
+                    Some(closest) => report.append(closest),
+                    None => { report.record(format!("{}.any", path), &"requested", &"-", &"any", false); }
+                }
+                false
+            }
+            Expression::not(ref expression) => {
+                let mut inner = MatchReport::new();
+                let satisfied = expression.evaluate(b, &format!("{}.not", path), &mut inner);
+                report.record(format!("{}.not", path), &"requirement",
+                              &if satisfied { "satisfied" } else { "not satisfied" }, &"not", !satisfied)
+            }
+            Expression::requirement(ref requirement) => {
+                compare_sections(&requirement.sections(), &b.sections(), &format!("{}.", path), report)
             }
-            None => ()
         }
-        match self.memory {
-            Some(ref mut memory) => {
-                memory.decompress(repo);
+    }
+
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        match *self {
+            Expression::all(ref mut expressions) | Expression::any(ref mut expressions) =>
+                expressions.iter_mut().flat_map(|expression| expression.quantities_mut()).collect(),
+            Expression::not(ref mut expression) => expression.quantities_mut(),
+            Expression::requirement(ref mut requirement) => requirement.quantities_mut(),
+        }
+    }
+
+    fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match *self {
+            Expression::all(ref mut expressions) | Expression::any(ref mut expressions) => {
+                for expression in expressions.iter_mut() {
+                    expression.compress(repo)?;
+                }
+                Ok(())
             }
-            None => ()
+            Expression::not(ref mut expression) => expression.compress(repo),
+            Expression::requirement(ref mut requirement) => requirement.compress(repo),
         }
+    }
 
-        match self.disk {
-            Some(ref mut disks) => {
-                for mut disk in disks.iter_mut() {
-                    disk.decompress(repo);
+    fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        match *self {
+            Expression::all(ref mut expressions) | Expression::any(ref mut expressions) => {
+                for expression in expressions.iter_mut() {
+                    expression.decompress(repo)?;
                 }
+                Ok(())
             }
-            None => ()
+            Expression::not(ref mut expression) => expression.decompress(repo),
+            Expression::requirement(ref mut requirement) => requirement.decompress(repo),
         }
     }
+}
 
+/**
+References to the sections shared by offers and requirements.
+*/
+struct Sections<'a> {
+    operating_system: &'a Option<OperatingSystem>,
+    network_interface: &'a Option<Vec<NetworkInterface>>,
+    virtualization: &'a Option<Virtualization>,
+    cpu: &'a Option<CPU>,
+    memory: &'a Option<Memory>,
+    disk: &'a Option<Vec<Disk>>,
+    application: &'a Option<Vec<Application>>,
+    payment: &'a Option<Payment>,
+}
+
+/**
+Mutable references to the sections shared by offers and requirements,
+which compress and decompress them.
+*/
+struct SectionsMut<'a> {
+    operating_system: &'a mut Option<OperatingSystem>,
+    network_interface: &'a mut Option<Vec<NetworkInterface>>,
+    virtualization: &'a mut Option<Virtualization>,
+    cpu: &'a mut Option<CPU>,
+    memory: &'a mut Option<Memory>,
+    disk: &'a mut Option<Vec<Disk>>,
+    application: &'a mut Option<Vec<Application>>,
+    payment: &'a mut Option<Payment>,
+}
 
-    pub fn compress(&mut self, repo: &Repository) {
-        let magnitude = repo.get_magnitude();
-        self.compress_magnitude_elements(magnitude);
-        match self.cpu {
+impl<'a> SectionsMut<'a> {
+    /**
+    Replaces the values of the sections with the IDs of the equal repository entries.
+    The value of an element is looked up in its magnitude, so the magnitude is compressed afterwards.
+    */
+    fn compress(self, repo: &Repository) -> Result<(), OntologyError> {
+        let mag = repo.get_magnitude();
+        match *self.operating_system {
+            Some(ref mut operating_system) => {
+                operating_system.compress(repo)?;
+                operating_system.compress_magnitude_elements(mag)?;
+            }
+            None => ()
+        }
+        match *self.network_interface {
+            Some(ref mut network_interfaces) => {
+                for interface in network_interfaces.iter_mut() {
+                    interface.compress_magnitude_elements(mag)?;
+                }
+            }
+            None => ()
+        }
+        match *self.virtualization {
+            Some(ref mut virtualization) => virtualization.compress(repo)?,
+            None => ()
+        }
+        match *self.cpu {
             Some(ref mut cpu) => {
-                cpu.compress(repo);
+                cpu.compress(repo)?;
+                cpu.compress_magnitude_elements(mag)?;
             }
             None => ()
         }
-        match self.memory {
+        match *self.memory {
             Some(ref mut memory) => {
-                memory.compress(repo);
+                memory.compress(repo)?;
+                memory.compress_magnitude_elements(mag)?;
             }
             None => ()
         }
-
-        match self.disk {
+        match *self.disk {
             Some(ref mut disks) => {
-                for mut disk in disks.iter_mut() {
-                    disk.compress(repo);
+                for disk in disks.iter_mut() {
+                    disk.compress(repo)?;
+                    disk.compress_magnitude_elements(mag)?;
+                }
+            }
+            None => ()
+        }
+        match *self.application {
+            Some(ref mut applications) => {
+                for application in applications.iter_mut() {
+                    application.compress_magnitude_elements(mag)?;
                 }
             }
             None => ()
         }
+        match *self.payment {
+            Some(ref mut payment) => payment.compress_magnitude_elements(mag)?,
+            None => ()
+        }
+        Ok(())
     }
 
-    fn compress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        match self.operating_system {
-            Some(ref mut operating_system) => { operating_system.compress_magnitude_elements(&mag); }
+    /**
+    Replaces the IDs of the sections with the values of the repository entries,
+    the magnitudes first, see `compress`.
+    */
+    fn decompress(self, repo: &Repository) -> Result<(), OntologyError> {
+        let mag = repo.get_magnitude();
+        match *self.operating_system {
+            Some(ref mut operating_system) => {
+                operating_system.decompress_magnitude_elements(mag)?;
+                operating_system.decompress(repo)?;
+            }
             None => ()
         }
-        match self.network_interface {
+        match *self.network_interface {
             Some(ref mut network_interfaces) => {
-                for ref mut interface in network_interfaces {
-                    interface.compress_magnitude_elements(&mag);
+                for interface in network_interfaces.iter_mut() {
+                    interface.decompress_magnitude_elements(mag)?;
                 }
             }
             None => ()
         }
-        match self.cpu {
-            Some(ref mut cpu) => { cpu.compress_magnitude_elements(&mag); }
+        match *self.virtualization {
+            Some(ref mut virtualization) => virtualization.decompress(repo)?,
             None => ()
         }
-
-        match self.memory {
-            Some(ref mut memory) => { memory.compress_magnitude_elements(&mag); }
+        match *self.cpu {
+            Some(ref mut cpu) => {
+                cpu.decompress_magnitude_elements(mag)?;
+                cpu.decompress(repo)?;
+            }
             None => ()
         }
-
-        match self.disk {
+        match *self.memory {
+            Some(ref mut memory) => {
+                memory.decompress_magnitude_elements(mag)?;
+                memory.decompress(repo)?;
+            }
+            None => ()
+        }
+        match *self.disk {
             Some(ref mut disks) => {
-                for ref mut dsk in disks {
-                    dsk.compress_magnitude_elements(&mag);
+                for disk in disks.iter_mut() {
+                    disk.decompress_magnitude_elements(mag)?;
+                    disk.decompress(repo)?;
                 }
             }
             None => ()
         }
-
-        match self.application {
-            Some(ref mut application) => {
-                for ref mut app in application {
-                    app.compress_magnitude_elements(&mag);
+        match *self.application {
+            Some(ref mut applications) => {
+                for application in applications.iter_mut() {
+                    application.decompress_magnitude_elements(mag)?;
                 }
             }
             None => ()
         }
-
-        match self.payment {
-            Some(ref mut payment) => { payment.compress_magnitude_elements(&mag); }
+        match *self.payment {
+            Some(ref mut payment) => payment.decompress_magnitude_elements(mag)?,
             None => ()
         }
+        Ok(())
     }
-    
-    fn decompress_magnitude_elements(&mut self, mag: &Vec<MagnitudeElement>) {
-        match self.operating_system {
-            Some(ref mut operating_system) => { operating_system.decompress_magnitude_elements(&mag); }
-            None => ()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Offer {
+    host: StringElement,
+    ontology_version: FloatElement,
+    repository_version: VersionElement,
+    timeout: IntElement,
+    operating_system: Option<OperatingSystem>,
+    network_interface: Option<Vec<NetworkInterface>>,
+    virtualization: Option<Virtualization>,
+    cpu: Option<CPU>,
+    memory: Option<Memory>,
+    disk: Option<Vec<Disk>>,
+    application: Option<Vec<Application>>,
+    payment: Option<Payment>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    requirements: Option<Expression>,
+    /// Creation time of the offer in seconds since the Unix epoch.
+    #[serde(default)]
+    created: Option<u64>,
+    /// Ed25519 signature of the host over the canonical encoding of the offer, see `signature`.
+    #[serde(default)]
+    signature: Option<Vec<u8>>,
+}
+
+impl Offer {
+    /**
+    Creates an offer without any sections.
+    `Parameters`
+    * host:StringElement: Host providing the offer
+    * ontology_version:FloatElement: Version of the ontology the offer uses
+    * repository_version:VersionElement: Version of the repository the offer is compressed with
+    * timeout:IntElement: Time the offer is valid
+    */
+    pub fn new(host: StringElement, ontology_version: FloatElement, repository_version: VersionElement, timeout: IntElement) -> Offer {
+        Offer {
+            host: host,
+            ontology_version: ontology_version,
+            repository_version: repository_version,
+            timeout: timeout,
+            operating_system: None,
+            network_interface: None,
+            virtualization: None,
+            cpu: None,
+            memory: None,
+            disk: None,
+            application: None,
+            payment: None,
+            requirements: None,
+            created: None,
+            signature: None,
         }
-        match self.network_interface {
-            Some(ref mut network_interfaces) => {
-                for ref mut interface in network_interfaces {
-                    interface.decompress_magnitude_elements(&mag);
-                }
-            }
-            None => ()
+    }
+
+    pub fn get_host(&self) -> &StringElement { &self.host }
+    pub fn get_host_mut(&mut self) -> &mut StringElement { &mut self.host }
+    pub fn set_host(&mut self, host: StringElement) { self.host = host; }
+    pub fn with_host(mut self, host: StringElement) -> Offer { self.host = host; self }
+
+    pub fn get_ontology_version(&self) -> &FloatElement { &self.ontology_version }
+    pub fn get_ontology_version_mut(&mut self) -> &mut FloatElement { &mut self.ontology_version }
+    pub fn set_ontology_version(&mut self, ontology_version: FloatElement) { self.ontology_version = ontology_version; }
+    pub fn with_ontology_version(mut self, ontology_version: FloatElement) -> Offer { self.ontology_version = ontology_version; self }
+
+    pub fn get_repository_version(&self) -> &VersionElement { &self.repository_version }
+    pub fn get_repository_version_mut(&mut self) -> &mut VersionElement { &mut self.repository_version }
+    pub fn set_repository_version(&mut self, repository_version: VersionElement) { self.repository_version = repository_version; }
+    pub fn with_repository_version(mut self, repository_version: VersionElement) -> Offer { self.repository_version = repository_version; self }
+
+    pub fn get_timeout(&self) -> &IntElement { &self.timeout }
+    pub fn get_timeout_mut(&mut self) -> &mut IntElement { &mut self.timeout }
+    pub fn set_timeout(&mut self, timeout: IntElement) { self.timeout = timeout; }
+    pub fn with_timeout(mut self, timeout: IntElement) -> Offer { self.timeout = timeout; self }
+
+    pub fn get_operating_system(&self) -> &Option<OperatingSystem> { &self.operating_system }
+    pub fn get_operating_system_mut(&mut self) -> &mut Option<OperatingSystem> { &mut self.operating_system }
+    pub fn set_operating_system(&mut self, operating_system: Option<OperatingSystem>) { self.operating_system = operating_system; }
+    pub fn with_operating_system(mut self, operating_system: OperatingSystem) -> Offer { self.operating_system = Some(operating_system); self }
+
+    pub fn get_network_interface(&self) -> &Option<Vec<NetworkInterface>> { &self.network_interface }
+    pub fn get_network_interface_mut(&mut self) -> &mut Option<Vec<NetworkInterface>> { &mut self.network_interface }
+    pub fn set_network_interface(&mut self, network_interface: Option<Vec<NetworkInterface>>) { self.network_interface = network_interface; }
+    pub fn with_network_interface(mut self, network_interface: Vec<NetworkInterface>) -> Offer { self.network_interface = Some(network_interface); self }
+
+    pub fn get_virtualization(&self) -> &Option<Virtualization> { &self.virtualization }
+    pub fn get_virtualization_mut(&mut self) -> &mut Option<Virtualization> { &mut self.virtualization }
+    pub fn set_virtualization(&mut self, virtualization: Option<Virtualization>) { self.virtualization = virtualization; }
+    pub fn with_virtualization(mut self, virtualization: Virtualization) -> Offer { self.virtualization = Some(virtualization); self }
+
+    pub fn get_cpu(&self) -> &Option<CPU> { &self.cpu }
+    pub fn get_cpu_mut(&mut self) -> &mut Option<CPU> { &mut self.cpu }
+    pub fn set_cpu(&mut self, cpu: Option<CPU>) { self.cpu = cpu; }
+    pub fn with_cpu(mut self, cpu: CPU) -> Offer { self.cpu = Some(cpu); self }
+
+    pub fn get_memory(&self) -> &Option<Memory> { &self.memory }
+    pub fn get_memory_mut(&mut self) -> &mut Option<Memory> { &mut self.memory }
+    pub fn set_memory(&mut self, memory: Option<Memory>) { self.memory = memory; }
+    pub fn with_memory(mut self, memory: Memory) -> Offer { self.memory = Some(memory); self }
+
+    pub fn get_disk(&self) -> &Option<Vec<Disk>> { &self.disk }
+    pub fn get_disk_mut(&mut self) -> &mut Option<Vec<Disk>> { &mut self.disk }
+    pub fn set_disk(&mut self, disk: Option<Vec<Disk>>) { self.disk = disk; }
+    pub fn with_disk(mut self, disk: Vec<Disk>) -> Offer { self.disk = Some(disk); self }
+
+    pub fn get_application(&self) -> &Option<Vec<Application>> { &self.application }
+    pub fn get_application_mut(&mut self) -> &mut Option<Vec<Application>> { &mut self.application }
+    pub fn set_application(&mut self, application: Option<Vec<Application>>) { self.application = application; }
+    pub fn with_application(mut self, application: Vec<Application>) -> Offer { self.application = Some(application); self }
+
+    pub fn get_payment(&self) -> &Option<Payment> { &self.payment }
+    pub fn get_payment_mut(&mut self) -> &mut Option<Payment> { &mut self.payment }
+    pub fn set_payment(&mut self, payment: Option<Payment>) { self.payment = payment; }
+    pub fn with_payment(mut self, payment: Payment) -> Offer { self.payment = Some(payment); self }
+
+    pub fn get_requirements(&self) -> &Option<Expression> { &self.requirements }
+    pub fn get_requirements_mut(&mut self) -> &mut Option<Expression> { &mut self.requirements }
+    pub fn set_requirements(&mut self, requirements: Option<Expression>) { self.requirements = requirements; }
+    pub fn with_requirements(mut self, requirements: Expression) -> Offer { self.requirements = Some(requirements); self }
+
+    pub fn get_created(&self) -> &Option<u64> { &self.created }
+    pub fn set_created(&mut self, created: Option<u64>) { self.created = created; }
+    pub fn with_created(mut self, created: u64) -> Offer { self.created = Some(created); self }
+
+    pub fn get_signature(&self) -> &Option<Vec<u8>> { &self.signature }
+    pub fn set_signature(&mut self, signature: Option<Vec<u8>>) { self.signature = signature; }
+
+    /**
+    Returns the time in seconds since the Unix epoch at which the offer expires,
+    i.e. its creation time plus its timeout in seconds. Offers without
+    creation time or timeout value never expire.
+    */
+    pub fn expires_at(&self) -> Option<u64> {
+        match (self.created, self.timeout.get_absolute_value()) {
+            (Some(created), Some(timeout)) => Some(created.saturating_add(timeout as u64)),
+            (_, _) => None,
         }
-        match self.cpu {
-            Some(ref mut cpu) => { cpu.decompress_magnitude_elements(&mag); }
-            None => ()
+    }
+
+    /**
+    Checks if the offer is stale at the given time in seconds since the Unix epoch.
+    */
+    pub fn is_expired(&self, now: u64) -> bool {
+        match self.expires_at() {
+            Some(expires_at) => now >= expires_at,
+            None => false,
         }
+    }
 
-        match self.memory {
-            Some(ref mut memory) => { memory.decompress_magnitude_elements(&mag); }
-            None => ()
+    /**
+    Renews the offer, so that its timeout starts again at the given time.
+    A signed offer has to be signed again afterwards.
+    */
+    pub fn renew(&mut self, now: u64) {
+        self.created = Some(now);
+    }
+
+    /**
+    Checks if both offers were published by the same host.
+    */
+    pub fn same_host(&self, other: &Offer) -> bool {
+        self.host.get_value().is_some() && self.host.get_value() == other.host.get_value()
+    }
+
+    /**
+    Checks if both offers are copies of the same offer, i.e. have the same `canonical::offer_id`.
+    They may differ in their creation time, signature and the representation of their quantities.
+    */
+    pub fn same_offer(&self, other: &Offer) -> bool {
+        match (offer_id(self), offer_id(other)) {
+            (Ok(id), Ok(other_id)) => id == other_id,
+            (_, _) => false,
         }
+    }
 
-        match self.disk {
-            Some(ref mut disks) => {
-                for ref mut dsk in disks {
-                    dsk.decompress_magnitude_elements(&mag);
-                }
-            }
-            None => ()
+    fn quantities_mut(&mut self) -> Vec<&mut dyn Quantity> {
+        let mut quantities = section_quantities_mut(&mut self.network_interface, &mut self.cpu, &mut self.memory,
+                                                    &mut self.disk, &mut self.payment);
+        quantities.push(&mut self.timeout);
+        if let Some(ref mut requirements) = self.requirements {
+            quantities.extend(requirements.quantities_mut());
         }
+        quantities
+    }
 
-        match self.application {
-            Some(ref mut application) => {
-                for ref mut app in application {
-                    app.decompress_magnitude_elements(&mag);
-                }
-            }
+    /**
+    Converts every quantity of the offer, including those of its requirements,
+    to base units without magnitude, e.g. 4 giga becomes 4000000000. Quantities
+    with a compressed magnitude are kept, so offers are normalized after decompressing.
+    */
+    pub fn normalize(&mut self) {
+        for quantity in self.quantities_mut() {
+            quantity.normalize();
+        }
+    }
+
+    /**
+    Expresses every quantity given in base units in the largest fitting magnitude,
+    e.g. 4096000000 becomes 4096 mega, to display a normalized offer.
+    */
+    pub fn humanize(&mut self) {
+        for quantity in self.quantities_mut() {
+            quantity.humanize();
+        }
+    }
+
+    fn sections(&self) -> Sections {
+        Sections {
+            operating_system: &self.operating_system,
+            network_interface: &self.network_interface,
+            virtualization: &self.virtualization,
+            cpu: &self.cpu,
+            memory: &self.memory,
+            disk: &self.disk,
+            application: &self.application,
+            payment: &self.payment,
+        }
+    }
+
+    fn sections_mut(&mut self) -> SectionsMut {
+        SectionsMut {
+            operating_system: &mut self.operating_system,
+            network_interface: &mut self.network_interface,
+            virtualization: &mut self.virtualization,
+            cpu: &mut self.cpu,
+            memory: &mut self.memory,
+            disk: &mut self.disk,
+            application: &mut self.application,
+            payment: &mut self.payment,
+        }
+    }
+
+    /**
+    Checks that the offer uses the version of the repository, unless one of them has no version.
+    */
+    fn check_repository_version(&self, repo: &Repository) -> Result<(), OntologyError> {
+        match (self.repository_version.get_version(), repo.get_version()) {
+            (Some(Ok(used)), &Some(ref version)) if used != *version => Err(OntologyError::RepositoryMismatch(format!(
+                "offer of {} uses repository version {}, not {}", self.host, used, version))),
+            (Some(Err(e)), &Some(_)) => Err(e),
+            (_, _) => Ok(()),
+        }
+    }
+
+    /**
+    Replaces the IDs of the offer with the values of the repository entries.
+    `Return`
+      Err(RepositoryMismatch) if the offer uses another repository version,
+      Err(MissingRepositoryId) if the repository has no entry with an ID of the offer
+    */
+    pub fn decompress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        self.check_repository_version(repo)?;
+        decompress_magnitudes(self.timeout.get_magnitudes_mut(), repo.get_magnitude())?;
+        self.sections_mut().decompress(repo)?;
+        match self.requirements {
+            Some(ref mut requirements) => requirements.decompress(repo)?,
             None => ()
         }
+        Ok(())
+    }
 
-        match self.payment {
-            Some(ref mut payment) => { payment.decompress_magnitude_elements(&mag); }
+
+    /**
+    Replaces the values of the offer with the IDs of the equal repository entries.
+    `Return`
+      Err(RepositoryMismatch) if the offer uses another repository version,
+      Err(MissingRepositoryId) if an equal repository entry has no ID
+    */
+    pub fn compress(&mut self, repo: &Repository) -> Result<(), OntologyError> {
+        self.check_repository_version(repo)?;
+        compress_magnitudes(self.timeout.get_magnitudes_mut(), repo.get_magnitude())?;
+        self.sections_mut().compress(repo)?;
+        match self.requirements {
+            Some(ref mut requirements) => requirements.compress(repo)?,
             None => ()
         }
+        Ok(())
+    }
+
+    /**
+    Translates an offer compressed with one repository version into another one by
+    decompressing it with `from` and compressing it with `to`, and sets its
+    repository version to the one of `to`. Values without an entry in `to` are kept.
+    A signed offer has to be signed again afterwards.
+    */
+    pub fn translate(&mut self, from: &Repository, to: &Repository) -> Result<(), OntologyError> {
+        self.decompress(from)?;
+        if let Some(ref version) = *to.get_version() {
+            self.repository_version.set_value(Some(version.to_string()));
+        }
+        self.compress(to)
     }
+
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Offers {
     offers: Vec<Offer>
 }
 
 impl Offers {
+    pub fn new(offers: Vec<Offer>) -> Offers {
+        Offers { offers: offers }
+    }
 
     pub fn get_offers(&self) -> &Vec<Offer> {
         &self.offers
@@ -1091,24 +1713,51 @@ impl Offers {
     `Parameters`
       path:&str => Path to a file containing the offerlist
     `Return`
-      An instance of the Offers struct
+      An instance of the Offers struct with normalized quantities, or the
+      I/O or JSON error which occurred while reading the file
     **/
-    pub fn from_json_file(path:&str) -> Offers {
-        let mut file = File::open(path).unwrap();
+    pub fn from_json_file(path:&str) -> Result<Offers, OntologyError> {
+        let mut file = File::open(path)?;
         let mut data = String::new();
-        file.read_to_string(&mut data).unwrap();
+        file.read_to_string(&mut data)?;
 
-        let mut off: Offers = serde_json::from_str(&data).unwrap();
+        let mut off: Offers = serde_json::from_str(&data)?;
+        off.normalize();
 
-        off
+        Ok(off)
     }
 
-    pub fn to_msgpack_file(path:&str, mut off:&Offers) {
-        let mut file_new = File::create(path).unwrap();
-        let mut new_data = rmps::encode::to_vec(&mut off).unwrap();
+    /**
+    This function writes an instance of the Offers struct
+    to a file, encoded as JSON dictionary.
+    `Parameters`
+      path:&str => Path of the file to create
+      off:&Offers => The offers to write
+    `Return`
+      The I/O or JSON error which occurred while writing the file
+    **/
+    pub fn to_json_file(path:&str, off:&Offers) -> Result<(), OntologyError> {
+        let file_new = File::create(path)?;
+        serde_json::to_writer_pretty(file_new, off)?;
+        Ok(())
+    }
+
+    /**
+    This function writes an instance of the Offers struct
+    to a file, encoded as MsgPack dictionary.
+    `Parameters`
+      path:&str => Path of the file to create
+      off:&Offers => The offers to write
+    `Return`
+      The I/O or MsgPack error which occurred while writing the file
+    **/
+    pub fn to_msgpack_file(path:&str, off:&Offers) -> Result<(), OntologyError> {
+        let mut file_new = File::create(path)?;
+        let new_data = rmps::encode::to_vec(off)?;
 
         use std::io::Write;
-        file_new.write_all(&new_data);
+        file_new.write_all(&new_data)?;
+        Ok(())
     }
 
     /**
@@ -1117,18 +1766,78 @@ impl Offers {
     `Parameters`
       path:&str => Path to a file containing an offerlist
     `Return`
-      An instance of the Offers struct
+      An instance of the Offers struct with normalized quantities, or the
+      I/O or MsgPack error which occurred while reading the file
     **/
-    pub fn from_msgpack_file(path:&str) -> Offers {
+    pub fn from_msgpack_file(path:&str) -> Result<Offers, OntologyError> {
 
-        let mut msgpack_file: File = File::open(path).unwrap();
+        let mut msgpack_file: File = File::open(path)?;
         let mut msgpack_data = Vec::new();
 
-        msgpack_file.read_to_end(&mut msgpack_data).unwrap();
-        let o: Offers = rmps::decode::from_slice(&msgpack_data).unwrap();
-        o
+        msgpack_file.read_to_end(&mut msgpack_data)?;
+        let mut o: Offers = rmps::decode::from_slice(&msgpack_data)?;
+        o.normalize();
+        Ok(o)
     }
 
+    /**
+    Converts every quantity of the offers to base units, see `Offer::normalize`.
+    */
+    pub fn normalize(&mut self) {
+        for offer in self.offers.iter_mut() {
+            offer.normalize();
+        }
+    }
+
+    /**
+    Expresses every quantity of the offers in a readable magnitude, see `Offer::humanize`.
+    */
+    pub fn humanize(&mut self) {
+        for offer in self.offers.iter_mut() {
+            offer.humanize();
+        }
+    }
+
+
+    /**
+    Returns the offers which are stale at the given time in seconds since the Unix epoch.
+    */
+    pub fn get_expired(&self, now: u64) -> Vec<&Offer> {
+        self.offers.iter().filter(|offer| offer.is_expired(now)).collect()
+    }
+
+    /**
+    Drops all offers which are stale at the given time.
+    `Return`
+      The dropped offers
+    */
+    pub fn remove_expired(&mut self, now: u64) -> Vec<Offer> {
+        let (expired, current) = self.offers.drain(..).partition(|offer| offer.is_expired(now));
+        self.offers = current;
+        expired
+    }
+
+    /**
+    Renews the offer at the given time and replaces the earlier copy of it, i.e. the
+    offer with the same `canonical::offer_id`, which leaves out the creation time and
+    the signature. Other offers of the same host are kept. The offer is added if it
+    is not known yet.
+    `Return`
+      True if an earlier copy of the offer was replaced
+    */
+    pub fn refresh(&mut self, mut offer: Offer, now: u64) -> bool {
+        offer.renew(now);
+        match self.offers.iter().position(|existing| existing.same_offer(&offer)) {
+            Some(position) => {
+                self.offers[position] = offer;
+                true
+            }
+            None => {
+                self.offers.push(offer);
+                false
+            }
+        }
+    }
 
     /**
     Compresses all containing offers by using the repository and setting the Id fields.
@@ -1136,14 +1845,14 @@ impl Offers {
     `Parameters`
     * repo:&Repository: The repository which should be used to compress.
     `Return`
-      None
+      Err(RepositoryMismatch) if an offer uses another repository version,
+      Err(MissingRepositoryId) if a repository entry used for compression has no ID
     */
-    pub fn compress(&mut self, repo:&Repository) {
+    pub fn compress(&mut self, repo:&Repository) -> Result<(), OntologyError> {
         for of in &mut self.offers {
-            {
-                of.compress(repo);
-            }
+            of.compress(repo)?;
         }
+        Ok(())
     }
 
     /**
@@ -1152,134 +1861,605 @@ impl Offers {
     `Parameters`
     * repo:&Repository: The repository which should be used to decompress.
     `Return`
-      None
+      Err(RepositoryMismatch) if an offer uses another repository version,
+      Err(MissingRepositoryId) if an offer refers to an ID the repository does not contain
+    */
+    pub fn decompress(&mut self, repo:&Repository) -> Result<(), OntologyError> {
+        for of in &mut self.offers {
+            of.decompress(repo)?;
+        }
+        Ok(())
+    }
+
+    /**
+    Translates all containing offers from one repository version into another, see `Offer::translate`.
     */
-    pub fn decompress(&mut self, repo:&Repository) {
+    pub fn translate(&mut self, from: &Repository, to: &Repository) -> Result<(), OntologyError> {
         for of in &mut self.offers {
-            {
-                of.decompress(repo);
+            of.translate(from, to)?;
+        }
+        Ok(())
+    }
+}
+
+/**
+Compares every element requested in `a` with the elements offered in `b`.
+Each requested element needs at least one sufficient element in `b`.
+The report receives the entries of the first sufficient element, or of the
+closest candidate if no element of `b` is sufficient.
+`Parameters`
+* a:&Vec<T>: Elements of the request
+* b:&Vec<T>: Elements of the offer
+* path:&str: Path of the list inside the offer, e.g. `disk`
+* report:&mut MatchReport: Report receiving the entries
+* compare:F: Comparison function of the element type
+`Return`
+  True if every element of a is satisfied by an element of b
+*/
+fn compare_any<T, F>(a: &Vec<T>, b: &Vec<T>, path: &str, report: &mut MatchReport, compare: F) -> bool
+    where F: Fn(&T, &T, &str, &mut MatchReport) -> bool {
+    let mut result: bool = true;
+    for (i, element_a) in a.iter().enumerate() {
+        let element_path = format!("{}[{}]", path, i);
+        let mut closest: Option<MatchReport> = None;
+        for element_b in b {
+            let mut candidate = MatchReport::new();
+            compare(element_a, element_b, &element_path, &mut candidate);
+            let is_closer = match closest {
+                Some(ref closest) => candidate.failure_count() < closest.failure_count(),
+                None => true,
+            };
+            if is_closer {
+                closest = Some(candidate);
+            }
+        }
+        match closest {
+            Some(closest) => {
+                result &= closest.is_match();
+                report.append(closest);
+            }
+            None => {
+                result &= report.record(element_path, &"requested", &"-", &"any", false);
             }
         }
     }
+    result
 }
 
 /**
-Compares two offers with each other. It uses the individual
-comparison functions defined in each element to check if
-offer b is sufficient for an request defined in offer a.
+Compares the sections of a request or requirement with the sections of an offer.
+Sections missing in either of them are not compared.
 `Parameters`
-* a:&Offer: Offer used as request
-* b:&Offer: Offer which is compared to the request
+* prefix:&str: Prefix of the section paths, empty for the top level of a request
 `Return`
- Ok(bool):
-    True if b is sufficient for the request a,
-    False if b is not sufficient.
+  True if every compared section is sufficient
 */
-pub fn comparing(a: &Offer, b: &Offer) -> Result<bool, Error> {
+fn compare_sections(a: &Sections, b: &Sections, prefix: &str, report: &mut MatchReport) -> bool {
     let mut result: bool = true;
 
-    result &= a.host.compare(&b.host);
-    if !result {
-        println!("Hosts are not equal");
+    if let Some(ref cpu_a) = *a.cpu {
+        if let Some(ref cpu_b) = *b.cpu {
+            result &= cpu_a.compare(cpu_b, &format!("{}cpu", prefix), report);
+        }
     }
-    result &= a.ontology_version.compare(&b.ontology_version);
-    if !result {
-        println!("Versions are not equal");
+
+    if let Some(ref memory_a) = *a.memory {
+        if let Some(ref memory_b) = *b.memory {
+            result &= memory_a.compare(memory_b, &format!("{}memory", prefix), report);
+        }
     }
 
-    result &= a.repository_version.compare(&b.repository_version);
-    if !result {
-        println!("Versions are not equal");
+    if let Some(ref operating_system_a) = *a.operating_system {
+        if let Some(ref operating_system_b) = *b.operating_system {
+            result &= operating_system_a.compare(operating_system_b, &format!("{}operating_system", prefix), report);
+        }
     }
 
-    if let Some(ref cpu_a) = a.cpu {
-        if let Some(ref cpu_b) = b.cpu {
-            result &= cpu_a.compare(cpu_b);
-            if !result {
-                println!("cpu are not equal");
-            }
+    if let Some(ref disks_a) = *a.disk {
+        if let Some(ref disks_b) = *b.disk {
+            result &= compare_any(disks_a, disks_b, &format!("{}disk", prefix), report, Disk::compare);
         }
     }
 
-    if let Some(ref memory_a) = a.memory {
-        if let Some(ref memory_b) = b.memory {
-            result &= memory_a.compare(memory_b);
-            if !result {
-                println!("memory are not equal");
-            }
+    if let Some(ref network_interfaces_a) = *a.network_interface {
+        if let Some(ref network_interfaces_b) = *b.network_interface {
+            result &= compare_any(network_interfaces_a, network_interfaces_b, &format!("{}network_interface", prefix), report, NetworkInterface::compare);
         }
     }
 
-    if let Some(ref operating_system_a) = a.operating_system {
-        if let Some(ref operating_system_b) = b.operating_system {
-            result &= operating_system_a.compare(operating_system_b);
-            if !result {
-                println!("operating_system are not equal");
-            }
+    if let Some(ref virtualization_a) = *a.virtualization {
+        if let Some(ref virtualization_b) = *b.virtualization {
+            result &= virtualization_a.compare(virtualization_b, &format!("{}virtualization", prefix), report);
         }
     }
-    if let Some(ref disks_a) = a.disk {
-        if let Some(ref disks_b) = b.disk {
-            for ref disk_a in disks_a {
-                let mut one_compatible: bool = false;
-                for ref disk_b in disks_b {
-                    one_compatible |= disk_a.compare(disk_b);
-                }
-                result &= one_compatible;
-                if !result {
-                    println!("operating_system are not equal");
-                }
-            }
+
+    if let Some(ref applications_a) = *a.application {
+        if let Some(ref applications_b) = *b.application {
+            result &= compare_any(applications_a, applications_b, &format!("{}application", prefix), report, Application::compare);
         }
     }
 
-    if let Some(ref network_interfaces_a) = a.network_interface {
-        if let Some(ref network_interfaces_b) = b.network_interface {
-            for ref network_interface_a in network_interfaces_a {
-                let mut one_compatible: bool = false;
-                for ref network_interface_b in network_interfaces_b {
-                    one_compatible |= network_interface_a.compare(network_interface_b);
-                }
-                result &= one_compatible;
-                if !result {
-                    println!("network interfaces are not equal");
-                }
-            }
+    if let Some(ref payment_a) = *a.payment {
+        if let Some(ref payment_b) = *b.payment {
+            result &= payment_a.compare(payment_b, &format!("{}payment", prefix), report);
         }
     }
 
-    if let Some(ref virtualization_a) = a.virtualization {
-        if let Some(ref virtualization_b) = b.virtualization {
-            result &= virtualization_a.compare(virtualization_b);
-            if !result {
-                println!("virtualization are not equal");
-            }
+    result
+}
+
+/**
+Compares two offers with each other. It uses the individual
+comparison functions defined in each element to check if
+offer b is sufficient for an request defined in offer a.
+`Parameters`
+* a:&Offer: Offer used as request
+* b:&Offer: Offer which is compared to the request
+`Return`
+ MatchReport:
+    A report listing every compared attribute, its request and offer
+    value, the operator used and whether it passed.
+    `MatchReport::is_match` is true if b is sufficient for the request a.
+*/
+pub fn comparing(a: &Offer, b: &Offer) -> MatchReport {
+    let mut report = MatchReport::new();
+
+    report.record("host".to_string(), &a.host, &b.host,
+                  a.host.get_compare_operator(), a.host.compare(&b.host));
+    report.record("ontology_version".to_string(), &a.ontology_version, &b.ontology_version,
+                  a.ontology_version.get_compare_operator(), a.ontology_version.compare(&b.ontology_version));
+    report.record("repository_version".to_string(), &a.repository_version, &b.repository_version,
+                  a.repository_version.get_compare_operator(), a.repository_version.compare(&b.repository_version));
+
+    compare_sections(&a.sections(), &b.sections(), "", &mut report);
+
+    if let Some(ref requirements) = a.requirements {
+        requirements.evaluate(b, "requirements", &mut report);
+    }
+
+    report
+}
+
+/**
+Returns the current time in seconds since the Unix epoch,
+as used for the creation time of offers.
+*/
+pub fn unix_time() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs(),
+        Err(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use basicElements::VersionOperator;
+
+    fn load() -> (Offers, Repository) {
+        (Offers::from_json_file("offerlist.json").unwrap(), Repository::from_json_file("repository.json").unwrap())
+    }
+
+    #[test]
+    fn decompress_restores_compressed_offers() {
+        let (offers, repo) = load();
+
+        let mut round_trip = offers.clone();
+        round_trip.compress(&repo).unwrap();
+        assert!(round_trip != offers);
+        round_trip.decompress(&repo).unwrap();
+
+        assert_eq!(round_trip, offers);
+    }
+
+    #[test]
+    fn normalized_offers_compress_every_quantity() {
+        let (mut offers, repo) = load();
+        let loaded = offers.clone();
+
+        offers.compress(&repo).unwrap();
+        for offer in offers.get_offers() {
+            let cpu = offer.get_cpu().as_ref().unwrap();
+            assert!(cpu.get_frequency().as_ref().unwrap().get_id().is_some());
+            assert!(offer.get_memory().as_ref().unwrap().get_size().as_ref().unwrap().get_id().is_some());
         }
+
+        offers.decompress(&repo).unwrap();
+        assert_eq!(offers, loaded);
     }
 
-   if let Some(ref applications_a) = a.application {
-        if let Some(ref applications_b) = b.application {
-            for ref application_a in applications_a {
-                let mut one_compatible: bool = false;
-                for ref application_b in applications_b {
-                    one_compatible |= application_a.compare(application_b);
-                }
-                result &= one_compatible;
-                if !result {
-                    println!("applications are not equal");
-                }
-            }
+    #[test]
+    fn uncompressed_offers_survive_msgpack() {
+        let (offers, _) = load();
+        let encoded = rmps::encode::to_vec(&offers).unwrap();
+        let decoded: Offers = rmps::decode::from_slice(&encoded).unwrap();
+        assert_eq!(decoded, offers);
+    }
+
+    #[test]
+    fn compress_covers_every_repository_table() {
+        let (_, repo) = load();
+        let mut offer: Offer = serde_json::from_str(r#"{
+            "host": {"value": "TU KL"},
+            "ontology_version": {"value": 1.1},
+            "repository_version": {"value": 2.1},
+            "timeout": {"value": 10, "magnitude": {"magnitude": "kilo"}},
+            "operating_system": {"system_type": {"value": "Linux"}},
+            "cpu": {
+                "manufacturer": {"value": "AMD"},
+                "frequency": {"value": 3.0, "magnitude": {"magnitude": "giga"}},
+                "instruction_set": [{"value": "AVX2"}]
+            },
+            "memory": {"size": {"value": 4, "magnitude": {"magnitude": "giga"}}, "generation": {"value": "DDR4RAM"}},
+            "disk": [{"diskType": {"value": "SSD"}, "size": {"value": 512, "magnitude": {"magnitude": "giga"}}}],
+            "virtualization": {"virtualization_type": {"value": "Hardware virtualization"}}
+        }"#).unwrap();
+        let original = offer.clone();
+
+        offer.compress(&repo).unwrap();
+
+        assert_eq!(offer.timeout.get_magnitude().get_id(), &Some(4));
+        assert_eq!(offer.operating_system.as_ref().unwrap().system_type.as_ref().unwrap().get_id(), &Some(3));
+        let cpu = offer.cpu.as_ref().unwrap();
+        assert_eq!(cpu.manufacturer.as_ref().unwrap().get_id(), &Some(1));
+        assert_eq!(cpu.frequency.as_ref().unwrap().get_id(), &Some(20));
+        assert_eq!(cpu.instruction_set[0].get_id(), &Some(9));
+        let memory = offer.memory.as_ref().unwrap();
+        assert_eq!(memory.size.as_ref().unwrap().get_id(), &Some(14));
+        assert_eq!(memory.generation.as_ref().unwrap().get_id(), &Some(3));
+        let disk = &offer.disk.as_ref().unwrap()[0];
+        assert_eq!(disk.diskType.as_ref().unwrap().get_id(), &Some(1));
+        assert_eq!(disk.size.as_ref().unwrap().get_magnitude().get_id(), &Some(6));
+        assert_eq!(offer.virtualization.as_ref().unwrap().virtualization_type.as_ref().unwrap().get_id(), &Some(0));
+
+        offer.decompress(&repo).unwrap();
+        assert_eq!(offer, original);
+    }
+
+    fn offer_with_cpu(cpu: &str) -> Offer {
+        serde_json::from_str(&format!(r#"{{
+            "host": {{"value": "TU KL"}},
+            "ontology_version": {{"value": 1.1}},
+            "repository_version": {{"value": 2.1}},
+            "timeout": {{"value": 1000}},
+            "cpu": {}
+        }}"#, cpu)).unwrap()
+    }
+
+    #[test]
+    fn range_requests_match_values_within_bounds() {
+        let (_, repo) = load();
+        let mut request = offer_with_cpu(r#"{
+            "amount": {"compareOperator": "range", "range": {"lower": {"value": 2}, "upper": {"value": 8, "inclusive": false}}},
+            "frequency": {"compareOperator": "range", "range": {
+                "lower": {"value": 2400, "magnitude": {"magnitude": "mega"}},
+                "upper": {"value": 3.6, "magnitude": {"magnitude": "giga"}}}},
+            "instruction_set": []
+        }"#);
+        let fitting = offer_with_cpu(r#"{"amount": {"value": 4}, "frequency": {"value": 3.0, "magnitude": {"magnitude": "giga"}}, "instruction_set": []}"#);
+        let too_many = offer_with_cpu(r#"{"amount": {"value": 8}, "frequency": {"value": 3.0, "magnitude": {"magnitude": "giga"}}, "instruction_set": []}"#);
+        let too_fast = offer_with_cpu(r#"{"amount": {"value": 4}, "frequency": {"value": 3.7, "magnitude": {"magnitude": "giga"}}, "instruction_set": []}"#);
+
+        assert!(comparing(&request, &fitting).is_match());
+        assert!(!comparing(&request, &too_many).is_match());
+        assert!(!comparing(&request, &too_fast).is_match());
+
+        let encoded = rmps::encode::to_vec(&request).unwrap();
+        let decoded: Offer = rmps::decode::from_slice(&encoded).unwrap();
+        assert_eq!(decoded, request);
+
+        let original = request.clone();
+        request.compress(&repo).unwrap();
+        request.decompress(&repo).unwrap();
+        assert_eq!(request, original);
+    }
+
+    #[test]
+    fn range_bounds_are_compared_exactly() {
+        use basicElements::{Magnitude, IntOperator};
+
+        // 2^53 + 1 has no f64 representation and would equal the bound as floating point number
+        let above: IntElement = serde_json::from_str(r#"{"compareOperator": "range", "range": {
+            "lower": {"value": 9007199254740992, "inclusive": false}}}"#).unwrap();
+        assert!(above.compare(&IntElement::new(9007199254740993, None, IntOperator::eq)));
+        assert!(!above.compare(&IntElement::new(9007199254740992, None, IntOperator::eq)));
+
+        let size: IntElement = serde_json::from_str(r#"{"compareOperator": "range", "range": {
+            "lower": {"value": 4096, "magnitude": {"magnitude": "mega"}},
+            "upper": {"value": 4096001, "magnitude": {"magnitude": "kilo"}, "inclusive": false}}}"#).unwrap();
+        assert!(size.compare(&IntElement::new(4_096_000, Some(Magnitude::kilo), IntOperator::eq)));
+        assert!(size.compare(&IntElement::new(4_096_000_999, None, IntOperator::eq)));
+        assert!(!size.compare(&IntElement::new(4_096_001_000, None, IntOperator::eq)));
+        assert!(!size.compare(&IntElement::new(4_095_999_999, None, IntOperator::eq)));
+    }
+
+    #[test]
+    fn string_operators_match_sets_and_patterns() {
+        let (_, repo) = load();
+        let mut request: Offer = serde_json::from_str(r#"{
+            "host": {"value": "TU", "compareOperator": "prefix"},
+            "ontology_version": {"value": 1.1},
+            "repository_version": {"value": 2.1},
+            "timeout": {"value": 1000},
+            "operating_system": {
+                "system_type": {"value": "Microsoft Windows", "compareOperator": "notEq"},
+                "repository": {"value": "Ubuntu 1[68]\\.04", "compareOperator": "regex"},
+                "version": {"value": "16.04", "compareOperator": "leq"}
+            },
+            "cpu": {"manufacturer": {"compareOperator": "oneOf", "values": [{"value": "AMD"}, {"value": "Intel"}]}, "instruction_set": []}
+        }"#).unwrap();
+        let offers = Offers::from_json_file("offerlist.json").unwrap();
+        for offer in offers.get_offers() {
+            assert!(comparing(&request, offer).is_match());
+        }
+
+        let mut arm = offers.get_offers()[0].clone();
+        arm.cpu.as_mut().unwrap().manufacturer.as_mut().unwrap().set_value(Some("ARM".to_string()));
+        assert!(!comparing(&request, &arm).is_match());
+
+        let original = request.clone();
+        request.compress(&repo).unwrap();
+        let manufacturer = request.cpu.as_ref().unwrap().manufacturer.as_ref().unwrap();
+        assert_eq!(manufacturer.get_values()[0].get_id(), &Some(1));
+        assert_eq!(manufacturer.get_values()[1].get_id(), &Some(0));
+        request.decompress(&repo).unwrap();
+        assert_eq!(request, original);
+    }
+
+    #[test]
+    fn decompress_rejects_unknown_ids() {
+        let (_, repo) = load();
+        let mut offer: Offer = serde_json::from_str(r#"{
+            "host": {"value": "TU KL"},
+            "ontology_version": {"value": 1.1},
+            "repository_version": {"value": 2.1},
+            "timeout": {"value": 1000},
+            "cpu": {"manufacturer": {"id": 42}, "instruction_set": []}
+        }"#).unwrap();
+
+        match offer.decompress(&repo) {
+            Err(OntologyError::MissingRepositoryId(_)) => (),
+            other => panic!("expected a missing repository ID, got {:?}", other),
         }
     }
 
-    if let Some(ref payment_a) = a.payment {
-        if let Some(ref payment_b) = b.payment {
-            result &= payment_a.compare(payment_b);
-            if !result {
-                println!("payment are not equal");
+    #[test]
+    fn version_operators_follow_semantic_versioning() {
+        let version = |value: &str, op: VersionOperator| VersionElement::new(value, op);
+        assert!(version("16.4", VersionOperator::eq).compare(&version("16.04.0", VersionOperator::eq)));
+        assert!(version("1.2.3-rc1", VersionOperator::le).compare(&version("1.2.3", VersionOperator::eq)));
+        assert!(version("1.2.3-rc.2", VersionOperator::le).compare(&version("1.2.3-rc.10", VersionOperator::eq)));
+        assert!(version("16.04", VersionOperator::leq).compare(&version("18.04", VersionOperator::eq)));
+        assert!(!version("16.04", VersionOperator::geq).compare(&version("18.04", VersionOperator::eq)));
+        assert!(version("2.1", VersionOperator::caret).compare(&version("2.9.4", VersionOperator::eq)));
+        assert!(!version("2.1", VersionOperator::caret).compare(&version("3.0", VersionOperator::eq)));
+        assert!(!version("0.2.1", VersionOperator::caret).compare(&version("0.3", VersionOperator::eq)));
+        assert!(version("2.1.3", VersionOperator::tilde).compare(&version("2.1.9", VersionOperator::eq)));
+        assert!(!version("2.1.3", VersionOperator::tilde).compare(&version("2.2.0", VersionOperator::eq)));
+        assert!(!version("2.x", VersionOperator::eq).compare(&version("2.0", VersionOperator::eq)));
+
+        let virtualization: Virtualization = serde_json::from_str(
+            r#"{"version": {"value": 2, "compareOperator": "leq"}}"#).unwrap();
+        assert_eq!(virtualization.version, Some(version("2", VersionOperator::leq)));
+    }
+
+    #[test]
+    fn requirement_expressions_combine_sub_requests() {
+        let (_, repo) = load();
+        let mut request: Offer = serde_json::from_str(r#"{
+            "host": {"value": "TU KL"},
+            "ontology_version": {"value": 1.1},
+            "repository_version": {"value": 2.1},
+            "timeout": {"value": 1000},
+            "requirements": {"all": [
+                {"any": [
+                    {"requirement": {"cpu": {"manufacturer": {"value": "AMD"}, "amount": {"value": 4, "compareOperator": "leq"},
+                                             "instruction_set": [{"value": "AES"}]}}},
+                    {"requirement": {"cpu": {"manufacturer": {"value": "Intel"}, "amount": {"value": 4, "compareOperator": "leq"},
+                                             "instruction_set": []}}}
+                ]},
+                {"not": {"requirement": {"operating_system": {"system_type": {"value": "Windows"}}}}}
+            ]}
+        }"#).unwrap();
+        let offers = Offers::from_json_file("offerlist.json").unwrap();
+        let matches: Vec<bool> = offers.get_offers().iter()
+            .map(|offer| comparing(&request, offer).is_match()).collect();
+        assert_eq!(matches, vec![false, true, true]);
+
+        let report = comparing(&request, &offers.get_offers()[0]);
+        assert!(report.get_failures().iter()
+            .any(|failure| failure.get_path() == "requirements.all[0].any[0].cpu.instruction_set[0]"));
+
+        let mut linux = request.clone();
+        if let Some(Expression::all(ref mut expressions)) = linux.requirements {
+            expressions[1] = serde_json::from_str(
+                r#"{"not": {"requirement": {"operating_system": {"system_type": {"value": "Linux"}}}}}"#).unwrap();
+        }
+        assert!(offers.get_offers().iter().all(|offer| !comparing(&linux, offer).is_match()));
+
+        let original = request.clone();
+        let mut buf = Vec::new();
+        request.serialize(&mut Serializer::new(&mut buf)).unwrap();
+        let decoded: Offer = Deserialize::deserialize(&mut Deserializer::new(&buf[..])).unwrap();
+        assert_eq!(decoded, original);
+
+        request.compress(&repo).unwrap();
+        assert!(request != original);
+        request.decompress(&repo).unwrap();
+        assert_eq!(request, original);
+    }
+
+    #[test]
+    fn builders_create_the_same_offer_as_deserialization() {
+        use basicElements::{Magnitude, StringOperator, IntOperator, FloatOperator, VersionOperator};
+
+        let built = Offer::new(StringElement::new("TU KL", StringOperator::eq),
+                               FloatElement::new(1.1, None, FloatOperator::eq),
+                               VersionElement::new("2.1", VersionOperator::eq),
+                               IntElement::new(1000, None, IntOperator::eq))
+            .with_cpu(CPU::new()
+                .with_manufacturer(StringElement::any_of(&["AMD", "Intel"]))
+                .with_amount(IntElement::new(4, None, IntOperator::leq))
+                .with_instruction_set(vec![BasicElement::new(InstructionSet::AVX2)]))
+            .with_memory(Memory::new().with_size(FloatElement::new(8.0, Some(Magnitude::giga), FloatOperator::leq)));
+
+        let parsed: Offer = serde_json::from_str(r#"{
+            "host": {"value": "TU KL"},
+            "ontology_version": {"value": 1.1},
+            "repository_version": {"value": 2.1},
+            "timeout": {"value": 1000},
+            "cpu": {"manufacturer": {"compareOperator": "oneOf", "values": [{"value": "AMD"}, {"value": "Intel"}]},
+                    "amount": {"value": 4, "compareOperator": "leq"},
+                    "instruction_set": [{"value": "AVX2"}]},
+            "memory": {"size": {"value": 8.0, "magnitude": {"magnitude": "giga"}, "compareOperator": "leq"}}
+        }"#).unwrap();
+        assert_eq!(built, parsed);
+    }
+
+    #[test]
+    fn offers_expire_after_their_timeout_unless_renewed() {
+        use basicElements::{IntOperator, StringOperator};
+
+        let offers = Offers::from_json_file("offerlist.json").unwrap();
+        let mut offer = offers.get_offers()[0].clone();
+        assert_eq!(offer.expires_at(), None);
+        assert!(!offer.is_expired(u64::max_value()));
+
+        offer.set_timeout(IntElement::new(60, None, IntOperator::eq));
+        offer.renew(1000);
+        assert_eq!(offer.expires_at(), Some(1060));
+        assert!(!offer.is_expired(1059));
+        assert!(offer.is_expired(1060));
+
+        let mut other = offer.clone();
+        other.set_host(StringElement::new("other host", StringOperator::eq));
+        let mut store = Offers::new(vec![offer.clone(), other]);
+        assert!(store.refresh(offer.clone(), 1050));
+        assert_eq!(store.get_offers().len(), 2);
+
+        let mut same_host = offers.clone();
+        assert!(same_host.refresh(offers.get_offers()[1].clone(), 1050));
+        assert_eq!(same_host.get_offers()[0], offers.get_offers()[0]);
+        assert_eq!(same_host.get_offers()[1].get_created(), &Some(1050));
+        assert_eq!(same_host.get_offers().len(), 3);
+        assert_eq!(store.get_expired(1100).len(), 1);
+
+        let dropped = store.remove_expired(1100);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].get_host().get_value(), &Some("other host".to_string()));
+        assert_eq!(store.get_offers()[0].get_created(), &Some(1050));
+    }
+
+    #[test]
+    fn quantities_are_normalized_to_base_units_and_compared_exactly() {
+        use basicElements::{Magnitude, StringOperator, IntOperator, FloatOperator, VersionOperator};
+
+        let size = IntElement::new(4096, Some(Magnitude::mega), IntOperator::eq);
+        assert!(size.compare(&IntElement::new(4_096_000, Some(Magnitude::kilo), IntOperator::eq)));
+        assert!(!size.compare(&IntElement::new(4, Some(Magnitude::giga), IntOperator::eq)));
+        let frequency = FloatElement::new(3.0, Some(Magnitude::giga), FloatOperator::eq);
+        assert!(frequency.compare(&FloatElement::new(3000.0, Some(Magnitude::mega), FloatOperator::eq)));
+
+        let mut offer = Offer::new(StringElement::new("TU KL", StringOperator::eq),
+                                   FloatElement::new(1.1, None, FloatOperator::leq),
+                                   VersionElement::new("2.1", VersionOperator::leq),
+                                   IntElement::new(1000, None, IntOperator::leq))
+            .with_memory(Memory::new().with_size(FloatElement::new(4.0, Some(Magnitude::giga), FloatOperator::leq)))
+            .with_disk(vec![Disk::new().with_size(IntElement::new(500, Some(Magnitude::giga), IntOperator::leq))]);
+        let original = offer.clone();
+
+        offer.normalize();
+        assert_eq!(offer.get_memory().as_ref().unwrap().get_size().as_ref().unwrap().get_value(), &Some(4e9));
+        let disk_size = offer.get_disk().as_ref().unwrap()[0].get_size().clone().unwrap();
+        assert_eq!(disk_size.get_value(), &Some(500_000_000_000));
+        assert_eq!(disk_size.get_magnitude().get_magnitude(), &None);
+        assert!(comparing(&original, &offer).is_match());
+
+        offer.humanize();
+        offer.set_timeout(IntElement::new(1000, None, IntOperator::leq));
+        assert_eq!(offer, original);
+    }
+
+    #[test]
+    fn units_are_converted_within_their_dimension() {
+        use basicElements::{Magnitude, IntOperator, Unit, Quantity};
+
+        let size = |value: u64, magnitude: Magnitude, unit: Option<Unit>| {
+            let element = IntElement::new(value, Some(magnitude), IntOperator::leq);
+            match unit {
+                Some(unit) => element.with_unit(unit),
+                None => element,
             }
-        }
+        };
+        let request = size(16, Magnitude::gibi, Some(Unit::bytes));
+        assert!(request.compare(&size(16, Magnitude::gibi, Some(Unit::bytes))));
+        assert!(!request.compare(&size(16, Magnitude::giga, Some(Unit::bytes))));
+        assert!(request.compare(&size(128, Magnitude::gibi, Some(Unit::bits))));
+        assert!(!request.compare(&size(64, Magnitude::gibi, Some(Unit::bits))));
+        assert!(request.compare(&size(16, Magnitude::gibi, None)));
+        assert!(!request.compare(&size(1, Magnitude::peta, Some(Unit::Hz))));
+        assert_eq!(request.fitness(&size(1, Magnitude::peta, Some(Unit::Hz))), None);
+
+        let speed: IntElement = serde_json::from_str(r#"{"value": 100, "magnitude": {"magnitude": "mega"}, "unit": "bit/s"}"#).unwrap();
+        assert_eq!(speed.get_unit(), &Some(Unit::bits_per_second));
+        assert_eq!(speed.to_string(), "100 mega bit/s");
+
+        let mut normalized = request.clone();
+        normalized.normalize();
+        assert_eq!(normalized.get_value(), &Some(17_179_869_184));
+        normalized.humanize();
+        assert_eq!(normalized, request);
     }
 
-    Ok(result)
+    #[test]
+    fn requested_instruction_sets_are_satisfied_by_implying_sets() {
+        let cpu = |sets: Vec<InstructionSet>| CPU::new()
+            .with_instruction_set(sets.into_iter().map(BasicElement::new).collect());
+        let matches = |request: &CPU, offer: &CPU| request.compare(offer, "cpu", &mut MatchReport::new());
+
+        let avx2 = cpu(vec![InstructionSet::AVX2]);
+        assert!(matches(&cpu(vec![InstructionSet::SSE4_1]), &avx2));
+        assert!(matches(&cpu(vec![InstructionSet::MMX, InstructionSet::AVX]), &avx2));
+        assert!(!matches(&cpu(vec![InstructionSet::AVX512F]), &avx2));
+        assert!(!matches(&cpu(vec![InstructionSet::NEON]), &avx2));
+        assert!(!matches(&cpu(vec![InstructionSet::SSE4a]), &avx2));
+
+        let mut report = MatchReport::new();
+        let arm = cpu(vec![InstructionSet::SVE2, InstructionSet::SHA]);
+        assert!(!cpu(vec![InstructionSet::NEON, InstructionSet::AES]).compare(&arm, "cpu", &mut report));
+        assert_eq!(report.get_failures().len(), 1);
+        assert_eq!(report.get_failures()[0].get_path(), "cpu.instruction_set[1]");
+        assert!(InstructionSet::AVX512VL.implies_set(&InstructionSet::SSE));
+    }
+
+    #[test]
+    fn memory_generations_are_ordered_within_their_family() {
+        use basicElements::{GenerationOperator, MEMORY_GENERATIONS};
+
+        let memory = |generation: MemoryGeneration, op: GenerationOperator| Memory::new()
+            .with_generation(GenerationElement::new(generation, op));
+        let matches = |request: &Memory, offer: &Memory| request.compare(offer, "memory", &mut MatchReport::new());
+
+        assert!(MemoryGeneration::DDR4RAM > MemoryGeneration::DDR3RAM);
+        assert!(MemoryGeneration::HBM3 > MemoryGeneration::HBM);
+        assert!(MemoryGeneration::LPDDR4RAM > MemoryGeneration::DDR5RAM);
+        assert!(MemoryGeneration::HBM < MemoryGeneration::HBM3);
+        let mut generations = MEMORY_GENERATIONS.to_vec();
+        generations.reverse();
+        generations.sort();
+        assert_eq!(generations, MEMORY_GENERATIONS.to_vec());
+
+        let ddr4 = memory(MemoryGeneration::DDR4RAM, GenerationOperator::eq);
+        assert!(matches(&memory(MemoryGeneration::DDR3RAM, GenerationOperator::leq), &ddr4));
+        assert!(matches(&memory(MemoryGeneration::DDR4RAM, GenerationOperator::leq), &ddr4));
+        assert!(!matches(&memory(MemoryGeneration::DDR5RAM, GenerationOperator::leq), &ddr4));
+        assert!(!matches(&memory(MemoryGeneration::DDR3RAM, GenerationOperator::eq), &ddr4));
+        assert!(matches(&memory(MemoryGeneration::DDR4RAM, GenerationOperator::eq), &ddr4));
+        for generation in vec![MemoryGeneration::LPDDR4RAM, MemoryGeneration::LPDDR5RAM, MemoryGeneration::HBM2] {
+            assert!(!matches(&memory(MemoryGeneration::DDR4RAM, GenerationOperator::leq), &memory(generation, GenerationOperator::eq)));
+        }
+
+        let parsed: Memory = serde_json::from_str(r#"{"generation": {"value": "DDR3RAM"}}"#).unwrap();
+        assert_eq!(parsed.get_generation().as_ref().unwrap().get_compare_operator(), &GenerationOperator::leq);
+    }
 }
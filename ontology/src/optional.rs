@@ -0,0 +1,199 @@
+/**
+Deserializes optional values such that enums survive the MsgPack encoding.
+
+rmp-serde 0.13 encodes an enum as `[variant index, [arguments]]`. Decoding an
+`Option` it reads the first marker of the value to tell `None` from `Some`, but
+its `deserialize_enum` ignores that marker again, so `Some` of an enum, e.g. the
+magnitude of an uncompressed offer, fails to decode with a type mismatch.
+`deserialize` instead reads the value with `deserialize_any` and feeds it to the
+`Deserialize` implementation of the value, which accepts enums as MsgPack array
+as well as JSON string or map. Use it on optional fields which may hold an enum:
+
+`#[serde(default, deserialize_with = "optional::deserialize")]`
+*/
+
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, IntoDeserializer, Visitor};
+use serde::de::{SeqAccess, MapAccess, EnumAccess, VariantAccess, IgnoredAny};
+use std::fmt;
+use std::marker::PhantomData;
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where D: Deserializer<'de>, T: Deserialize<'de>
+{
+    deserializer.deserialize_option(OptionVisitor(PhantomData))
+}
+
+struct OptionVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for OptionVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an optional value")
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Option<T>, E> { Ok(None) }
+    fn visit_unit<E: de::Error>(self) -> Result<Option<T>, E> { Ok(None) }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<T>, D::Error> {
+        deserializer.deserialize_any(ValueVisitor(PhantomData)).map(Some)
+    }
+}
+
+/**
+Hands whatever was read back to the `Deserialize` implementation of the value.
+*/
+struct ValueVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ValueVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a value")
+    }
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<T, E> { T::deserialize(value.into_deserializer()) }
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> { T::deserialize(value.into_deserializer()) }
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> { T::deserialize(value.into_deserializer()) }
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<T, E> { T::deserialize(value.into_deserializer()) }
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> { T::deserialize(value.into_deserializer()) }
+    fn visit_string<E: de::Error>(self, value: String) -> Result<T, E> { T::deserialize(value.into_deserializer()) }
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<T, A::Error> { T::deserialize(SeqDeserializer(seq)) }
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<T, A::Error> { T::deserialize(MapDeserializer(map)) }
+}
+
+/**
+An array, read as enum `[variant index, [arguments]]` if an enum is expected.
+*/
+struct SeqDeserializer<A>(A);
+
+impl<'de, A: SeqAccess<'de>> Deserializer<'de> for SeqDeserializer<A> {
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_seq(self.0)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct identifier ignored_any
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> EnumAccess<'de> for SeqDeserializer<A> {
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(mut self, seed: V) -> Result<(V::Value, Self), A::Error> {
+        let index: u32 = self.0.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &"a variant index"))?;
+        let variant = seed.deserialize(index.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> VariantAccess<'de> for SeqDeserializer<A> {
+    type Error = A::Error;
+
+    fn unit_variant(mut self) -> Result<(), A::Error> {
+        self.0.next_element::<IgnoredAny>().map(|_| ())
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(mut self, seed: S) -> Result<S::Value, A::Error> {
+        self.0.next_element_seed(Arguments(1, NewtypeVisitor(seed)))?
+            .ok_or_else(|| de::Error::invalid_length(1, &"the arguments of the variant"))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(mut self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.0.next_element_seed(Arguments(len, visitor))?
+            .ok_or_else(|| de::Error::invalid_length(1, &"the arguments of the variant"))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(mut self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, A::Error> {
+        self.0.next_element_seed(Arguments(fields.len(), visitor))?
+            .ok_or_else(|| de::Error::invalid_length(1, &"the arguments of the variant"))
+    }
+}
+
+/**
+A map, read as enum `{variant: arguments}` if an enum is expected.
+*/
+struct MapDeserializer<A>(A);
+
+impl<'de, A: MapAccess<'de>> Deserializer<'de> for MapDeserializer<A> {
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_map(self.0)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct identifier ignored_any
+    }
+}
+
+impl<'de, A: MapAccess<'de>> EnumAccess<'de> for MapDeserializer<A> {
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(mut self, seed: V) -> Result<(V::Value, Self), A::Error> {
+        let variant = self.0.next_key_seed(seed)?.ok_or_else(|| de::Error::invalid_length(0, &"a variant name"))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, A: MapAccess<'de>> VariantAccess<'de> for MapDeserializer<A> {
+    type Error = A::Error;
+
+    fn unit_variant(mut self) -> Result<(), A::Error> {
+        self.0.next_value::<IgnoredAny>().map(|_| ())
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(mut self, seed: S) -> Result<S::Value, A::Error> {
+        self.0.next_value_seed(seed)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(mut self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.0.next_value_seed(Arguments(len, visitor))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(mut self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, A::Error> {
+        self.0.next_value_seed(Arguments(fields.len(), visitor))
+    }
+}
+
+/**
+Reads the arguments of a variant as tuple of the given length.
+*/
+struct Arguments<V>(usize, V);
+
+impl<'de, V: Visitor<'de>> DeserializeSeed<'de> for Arguments<V> {
+    type Value = V::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        deserializer.deserialize_tuple(self.0, self.1)
+    }
+}
+
+/**
+Reads the single argument of a newtype variant, which MsgPack wraps in an array.
+*/
+struct NewtypeVisitor<S>(S);
+
+impl<'de, S: DeserializeSeed<'de>> Visitor<'de> for NewtypeVisitor<S> {
+    type Value = S::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the argument of a variant")
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<S::Value, A::Error> {
+        seq.next_element_seed(self.0)?.ok_or_else(|| de::Error::invalid_length(0, &"one argument"))
+    }
+}
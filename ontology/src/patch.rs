@@ -0,0 +1,383 @@
+/**
+Contains the delta updates of repositories, so that peers only exchange the entries
+which changed between two repository versions, and the merging of repositories
+which were extended independently.
+
+A patch lists per repository table the entries which were added, the IDs of the
+entries which were removed and the entries which were renamed, i.e. kept their ID
+but changed their value. Entries are identified by their ID, entries without ID
+are not used for compression and are left out.
+*/
+
+use std;
+use std::fmt::Debug;
+
+use basicElements::{BasicElement, FloatElement, StringElement, MagnitudeElement, GenerationElement};
+use basicElements::{DiskType, InstructionSet, Compressible, Version};
+use repository::Repository;
+use error::OntologyError;
+
+fn describe_version(version: &Option<Version>) -> String {
+    match *version {
+        Some(ref version) => version.to_string(),
+        None => "none".to_string(),
+    }
+}
+
+fn entry<E: Compressible>(table: &[E], id: i16) -> Option<&E> {
+    table.iter().find(|entry| *entry.get_repository_id() == Some(id))
+}
+
+/**
+The changes of a single repository table.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TablePatch<E> {
+    #[serde(default = "Vec::new")]
+    added: Vec<E>,
+    #[serde(default)]
+    removed: Vec<i16>,
+    #[serde(default = "Vec::new")]
+    renamed: Vec<E>,
+}
+
+impl<E> Default for TablePatch<E> {
+    fn default() -> TablePatch<E> {
+        TablePatch { added: Vec::new(), removed: Vec::new(), renamed: Vec::new() }
+    }
+}
+
+impl<E: Compressible + Clone> TablePatch<E> {
+    /**
+    Computes the changes turning the old table into the new one.
+    */
+    pub fn between(old: &[E], new: &[E]) -> TablePatch<E> {
+        let mut patch = TablePatch::default();
+        for old_entry in old {
+            if let Some(id) = *old_entry.get_repository_id() {
+                if entry(new, id).is_none() {
+                    patch.removed.push(id);
+                }
+            }
+        }
+        for new_entry in new {
+            if let Some(id) = *new_entry.get_repository_id() {
+                match entry(old, id) {
+                    None => patch.added.push(new_entry.clone()),
+                    Some(old_entry) if old_entry.get_repository_value() != new_entry.get_repository_value() =>
+                        patch.renamed.push(new_entry.clone()),
+                    Some(_) => (),
+                }
+            }
+        }
+        patch
+    }
+
+    pub fn get_added(&self) -> &Vec<E> { &self.added }
+    pub fn get_removed(&self) -> &Vec<i16> { &self.removed }
+    pub fn get_renamed(&self) -> &Vec<E> { &self.renamed }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.renamed.is_empty()
+    }
+
+    /**
+    Applies the changes to a table.
+    `Return`
+      Err(RepositoryConflict) if a removed or renamed ID does not exist
+      or an added ID already exists in the table
+    */
+    fn apply(&self, table: &mut Vec<E>, name: &str) -> Result<(), OntologyError> {
+        for id in &self.removed {
+            match table.iter().position(|entry| *entry.get_repository_id() == Some(*id)) {
+                Some(position) => { table.remove(position); }
+                None => return Err(OntologyError::RepositoryConflict(format!("{} has no ID {} to remove", name, id))),
+            }
+        }
+        for renamed in &self.renamed {
+            match table.iter_mut().find(|entry| entry.get_repository_id() == renamed.get_repository_id()) {
+                Some(entry) => *entry = renamed.clone(),
+                None => return Err(OntologyError::RepositoryConflict(format!("{} has no ID {:?} to rename", name, renamed.get_repository_id()))),
+            }
+        }
+        for added in &self.added {
+            if table.iter().any(|entry| entry.get_repository_id() == added.get_repository_id()) {
+                return Err(OntologyError::RepositoryConflict(format!("{} already has ID {:?}", name, added.get_repository_id())));
+            }
+            table.push(added.clone());
+        }
+        Ok(())
+    }
+}
+
+/**
+The changes between two versions of a repository.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RepositoryPatch {
+    from_version: Option<Version>,
+    to_version: Option<Version>,
+    #[serde(default)]
+    magnitude: TablePatch<MagnitudeElement>,
+    #[serde(default)]
+    system_type: TablePatch<StringElement>,
+    #[serde(default)]
+    manufacturer: TablePatch<StringElement>,
+    #[serde(default)]
+    frequency: TablePatch<FloatElement>,
+    #[serde(default)]
+    instruction_set: TablePatch<BasicElement<InstructionSet>>,
+    #[serde(default)]
+    size: TablePatch<FloatElement>,
+    #[serde(default)]
+    generation: TablePatch<GenerationElement>,
+    #[serde(default)]
+    disk_type: TablePatch<BasicElement<DiskType>>,
+    #[serde(default)]
+    virtualization_type: TablePatch<StringElement>,
+}
+
+impl RepositoryPatch {
+    /**
+    Computes the changes turning the old repository into the new one. The patch leads to the
+    version of the new repository if it is more recent, otherwise to the version following
+    the one of the old repository in its last component, e.g. 2.9 is followed by 2.10.
+    */
+    pub fn between(old: &Repository, new: &Repository) -> RepositoryPatch {
+        let to_version = match (old.get_version(), new.get_version()) {
+            (&Some(ref from), &Some(ref to)) if to > from => Some(to.clone()),
+            (&Some(ref from), _) => Some(from.next()),
+            (&None, to) => to.clone(),
+        };
+        RepositoryPatch {
+            from_version: old.get_version().clone(),
+            to_version: to_version,
+            magnitude: TablePatch::between(old.get_magnitude(), new.get_magnitude()),
+            system_type: TablePatch::between(old.get_operating_system().get_system_type(), new.get_operating_system().get_system_type()),
+            manufacturer: TablePatch::between(old.get_cpu().get_manufacturer(), new.get_cpu().get_manufacturer()),
+            frequency: TablePatch::between(old.get_cpu().get_frequency(), new.get_cpu().get_frequency()),
+            instruction_set: TablePatch::between(old.get_cpu().get_instruction_set(), new.get_cpu().get_instruction_set()),
+            size: TablePatch::between(old.get_memory().get_size(), new.get_memory().get_size()),
+            generation: TablePatch::between(old.get_memory().get_generation(), new.get_memory().get_generation()),
+            disk_type: TablePatch::between(old.get_disk().get_disk_type(), new.get_disk().get_disk_type()),
+            virtualization_type: TablePatch::between(old.get_virtualization().get_virtualization_type(), new.get_virtualization().get_virtualization_type()),
+        }
+    }
+
+    pub fn get_from_version(&self) -> &Option<Version> { &self.from_version }
+    pub fn get_to_version(&self) -> &Option<Version> { &self.to_version }
+
+    pub fn is_empty(&self) -> bool {
+        self.magnitude.is_empty() && self.system_type.is_empty() && self.manufacturer.is_empty()
+            && self.frequency.is_empty() && self.instruction_set.is_empty() && self.size.is_empty()
+            && self.generation.is_empty() && self.disk_type.is_empty() && self.virtualization_type.is_empty()
+    }
+
+    /**
+    Applies the changes to a repository and sets its version to the version of the patch.
+    The repository is left unchanged if the patch does not apply.
+    `Parameters`
+    * repo:&mut Repository: Repository with the version the patch starts from
+    `Return`
+      Err(RepositoryMismatch) if the repository has another version than the patch starts from,
+      Err(RepositoryConflict) if the entries of the repository do not fit the patch
+    */
+    pub fn apply(&self, repo: &mut Repository) -> Result<(), OntologyError> {
+        if *repo.get_version() != self.from_version {
+            return Err(OntologyError::RepositoryMismatch(format!("patch from version {} does not apply to version {}",
+                describe_version(&self.from_version), describe_version(repo.get_version()))));
+        }
+        let mut patched = repo.clone();
+        self.magnitude.apply(patched.get_magnitude_mut(), "magnitude")?;
+        self.system_type.apply(patched.get_operating_system_mut().get_system_type_mut(), "operating_system.system_type")?;
+        self.manufacturer.apply(patched.get_cpu_mut().get_manufacturer_mut(), "cpu.manufacturer")?;
+        self.frequency.apply(patched.get_cpu_mut().get_frequency_mut(), "cpu.frequency")?;
+        self.instruction_set.apply(patched.get_cpu_mut().get_instruction_set_mut(), "cpu.instruction_set")?;
+        self.size.apply(patched.get_memory_mut().get_size_mut(), "memory.size")?;
+        self.generation.apply(patched.get_memory_mut().get_generation_mut(), "memory.generation")?;
+        self.disk_type.apply(patched.get_disk_mut().get_disk_type_mut(), "disk.disk_type")?;
+        self.virtualization_type.apply(patched.get_virtualization_mut().get_virtualization_type_mut(), "virtualization.virtualization_type")?;
+        patched.set_version(self.to_version.clone());
+        *repo = patched;
+        Ok(())
+    }
+}
+
+/**
+Adds the entries of the other table which the table does not contain yet.
+Entries with the same ID but different values, and equal values with different IDs,
+are recorded as conflicts.
+`Return`
+  The number of added entries
+*/
+fn merge_table<E>(table: &mut Vec<E>, other: &[E], name: &str, conflicts: &mut Vec<String>) -> usize
+    where E: Compressible + Clone, E::Value: Debug
+{
+    let mut added = 0;
+    for other_entry in other {
+        let id = match *other_entry.get_repository_id() {
+            Some(id) => id,
+            None => continue,
+        };
+        match entry(table, id) {
+            Some(own) if own.get_repository_value() != other_entry.get_repository_value() => {
+                conflicts.push(format!("{}: ID {} is {:?} in one repository and {:?} in the other",
+                                       name, id, own.get_repository_value(), other_entry.get_repository_value()));
+                continue;
+            }
+            Some(_) => continue,
+            None => (),
+        }
+        match table.iter().find(|own| own.get_repository_value().is_some() && own.get_repository_value() == other_entry.get_repository_value()) {
+            Some(own) => conflicts.push(format!("{}: {:?} has ID {:?} in one repository and ID {} in the other",
+                                                name, other_entry.get_repository_value(), own.get_repository_id(), id)),
+            None => {
+                table.push(other_entry.clone());
+                added += 1;
+            }
+        }
+    }
+    added
+}
+
+/**
+Merges two repositories which were extended independently, e.g. from the same base version.
+The merged repository contains the entries of both and the version following the more recent
+one of both, unless the other repository adds no entries, in which case it equals the first one.
+`Return`
+  Ok(Repository):
+    The merged repository
+  Err(OntologyError):
+    RepositoryConflict listing every ID both repositories use for different entries
+    and every entry both repositories assign different IDs
+*/
+pub fn merge(repo: &Repository, other: &Repository) -> Result<Repository, OntologyError> {
+    let mut merged = repo.clone();
+    let mut conflicts = Vec::new();
+    let mut added = 0;
+    added += merge_table(merged.get_magnitude_mut(), other.get_magnitude(), "magnitude", &mut conflicts);
+    added += merge_table(merged.get_operating_system_mut().get_system_type_mut(), other.get_operating_system().get_system_type(), "operating_system.system_type", &mut conflicts);
+    added += merge_table(merged.get_cpu_mut().get_manufacturer_mut(), other.get_cpu().get_manufacturer(), "cpu.manufacturer", &mut conflicts);
+    added += merge_table(merged.get_cpu_mut().get_frequency_mut(), other.get_cpu().get_frequency(), "cpu.frequency", &mut conflicts);
+    added += merge_table(merged.get_cpu_mut().get_instruction_set_mut(), other.get_cpu().get_instruction_set(), "cpu.instruction_set", &mut conflicts);
+    added += merge_table(merged.get_memory_mut().get_size_mut(), other.get_memory().get_size(), "memory.size", &mut conflicts);
+    added += merge_table(merged.get_memory_mut().get_generation_mut(), other.get_memory().get_generation(), "memory.generation", &mut conflicts);
+    added += merge_table(merged.get_disk_mut().get_disk_type_mut(), other.get_disk().get_disk_type(), "disk.disk_type", &mut conflicts);
+    added += merge_table(merged.get_virtualization_mut().get_virtualization_type_mut(), other.get_virtualization().get_virtualization_type(), "virtualization.virtualization_type", &mut conflicts);
+
+    if !conflicts.is_empty() {
+        return Err(OntologyError::RepositoryConflict(conflicts.join("; ")));
+    }
+    if added > 0 {
+        let newest = match (repo.get_version(), other.get_version()) {
+            (&Some(ref version), &Some(ref other_version)) => Some(std::cmp::max(version, other_version)),
+            (version, other_version) => version.as_ref().or(other_version.as_ref()),
+        };
+        merged.set_version(newest.map(|version| version.next()));
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use basicElements::StringOperator;
+
+    fn repository() -> Repository {
+        Repository::from_json_file("repository.json").unwrap()
+    }
+
+    fn version(value: &str) -> Option<Version> {
+        Some(value.parse().unwrap())
+    }
+
+    fn manufacturer(name: &str, id: i16) -> StringElement {
+        let mut entry = StringElement::new(name, StringOperator::eq);
+        entry.set_id(id);
+        entry
+    }
+
+    #[test]
+    fn patches_turn_the_old_repository_into_the_new_one() {
+        let old = repository();
+        let mut new = old.clone();
+        new.get_cpu_mut().get_manufacturer_mut().push(manufacturer("Ampere", 4));
+        new.get_cpu_mut().get_manufacturer_mut()[0].set_value(Some("Intel Corporation".to_string()));
+        new.get_disk_mut().get_disk_type_mut().remove(0);
+
+        let patch = RepositoryPatch::between(&old, &new);
+        assert_eq!(patch.get_to_version(), &version("2.2"));
+        assert_eq!(patch.manufacturer.get_added().len(), 1);
+        assert_eq!(patch.manufacturer.get_renamed().len(), 1);
+        assert_eq!(patch.disk_type.get_removed(), &vec![0]);
+        assert!(patch.magnitude.is_empty());
+        assert!(RepositoryPatch::between(&old, &old).is_empty());
+
+        let patch: RepositoryPatch = serde_json::from_str(&serde_json::to_string(&patch).unwrap()).unwrap();
+        let mut patched = old.clone();
+        patch.apply(&mut patched).unwrap();
+        assert_eq!(patched.get_version(), &version("2.2"));
+        assert_eq!(patched.content_hash().unwrap(), new.content_hash().unwrap());
+
+        match patch.apply(&mut patched) {
+            Err(OntologyError::RepositoryMismatch(_)) => (),
+            other => panic!("expected a repository mismatch, got {:?}", other),
+        }
+        let mut unfit = old.clone();
+        unfit.get_cpu_mut().get_manufacturer_mut().push(manufacturer("Hygon", 4));
+        assert!(patch.apply(&mut unfit).is_err());
+        assert_eq!(unfit.get_version(), &version("2.1"));
+        assert_eq!(unfit.get_cpu().get_manufacturer().len(), old.get_cpu().get_manufacturer().len() + 1);
+    }
+
+    #[test]
+    fn versions_are_raised_in_their_last_component() {
+        let mut old = repository();
+        old.set_version(version("2.9"));
+        let mut new = old.clone();
+        new.get_cpu_mut().get_manufacturer_mut().push(manufacturer("Ampere", 4));
+
+        let patch = RepositoryPatch::between(&old, &new);
+        assert_eq!(patch.get_to_version(), &version("2.10"));
+        let mut patched = old.clone();
+        patch.apply(&mut patched).unwrap();
+        assert_eq!(patched.get_version(), &version("2.10"));
+        assert!(patched.get_version() > old.get_version());
+
+        let mut next = new.clone();
+        next.get_cpu_mut().get_manufacturer_mut().push(manufacturer("Hygon", 5));
+        let patch = RepositoryPatch::between(&patched, &next);
+        assert_eq!(patch.get_from_version(), &version("2.10"));
+        assert_eq!(patch.get_to_version(), &version("2.11"));
+        match patch.apply(&mut old) {
+            Err(OntologyError::RepositoryMismatch(ref reason)) =>
+                assert_eq!(reason, "patch from version 2.10 does not apply to version 2.9"),
+            other => panic!("expected a repository mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn merging_detects_id_collisions() {
+        let base = repository();
+        let mut ampere = base.clone();
+        ampere.get_cpu_mut().get_manufacturer_mut().push(manufacturer("Ampere", 4));
+        let mut hygon = base.clone();
+        hygon.get_cpu_mut().get_manufacturer_mut().push(manufacturer("Hygon", 4));
+
+        match merge(&ampere, &hygon) {
+            Err(OntologyError::RepositoryConflict(ref conflict)) => assert!(conflict.contains("cpu.manufacturer: ID 4")),
+            other => panic!("expected a conflict, got {:?}", other.map(|repo| repo.get_version().clone())),
+        }
+
+        hygon.get_cpu_mut().get_manufacturer_mut()[4].set_id(5);
+        let merged = merge(&ampere, &hygon).unwrap();
+        assert_eq!(merged.get_version(), &version("2.2"));
+        assert_eq!(merged.get_cpu().get_manufacturer().len(), base.get_cpu().get_manufacturer().len() + 2);
+        assert_eq!(merge(&merged, &ampere).unwrap().get_version(), &version("2.2"));
+
+        let mut renumbered = base.clone();
+        renumbered.get_cpu_mut().get_manufacturer_mut().push(manufacturer("Ampere", 6));
+        assert!(merge(&ampere, &renumbered).is_err());
+    }
+}
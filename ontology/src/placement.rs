@@ -0,0 +1,298 @@
+/**
+Contains a solver placing a batch of requests onto a pool of offers,
+respecting the capacity of offers which receive several requests.
+*/
+
+use std;
+
+use ontology::{Offers, Offer};
+use ranking::price_of;
+use reservation::CapacityLedger;
+use error::OntologyError;
+
+/**
+The quantity a placement minimizes. Ties are broken by the other quantity.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+    /// Total price, i.e. the sum of the prices of the offers every request is placed on.
+    price,
+    /// Number of distinct offers used.
+    hosts,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// Places the requests one after another, largest first, on the offer adding the least cost.
+    greedy,
+    /// Searches all assignments with branch and bound. Its running time grows
+    /// exponentially with the number of requests, so it is meant for small batches.
+    exact,
+}
+
+/**
+An assignment of every request to the position of an offer in the pool.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Placement {
+    assignment: Vec<usize>,
+    total_price: f64,
+    host_count: usize,
+}
+
+impl Placement {
+    /**
+    Returns the position of the offer assigned to each request, in the order of the requests.
+    */
+    pub fn get_assignment(&self) -> &Vec<usize> { &self.assignment }
+    pub fn get_total_price(&self) -> f64 { self.total_price }
+    pub fn get_host_count(&self) -> usize { self.host_count }
+
+    fn cost(&self, objective: Objective) -> (f64, f64) {
+        cost(objective, self.total_price, self.host_count)
+    }
+}
+
+fn cost(objective: Objective, total_price: f64, host_count: usize) -> (f64, f64) {
+    match objective {
+        Objective::price => (total_price, host_count as f64),
+        Objective::hosts => (host_count as f64, total_price),
+    }
+}
+
+/**
+Returns the price of placing a request on an offer. Offers without price are free.
+*/
+fn price(offer: &Offer) -> f64 {
+    price_of(offer).unwrap_or(0.0)
+}
+
+/**
+Returns the absolute CPU amount and memory size stated by an offer or request,
+used to place large requests first and to prefer roomy offers for the host objective.
+*/
+fn size(offer: &Offer) -> (f64, f64) {
+    let cpu_amount = offer.get_cpu().as_ref()
+        .and_then(|cpu| cpu.get_amount().as_ref())
+        .and_then(|amount| amount.get_absolute_value()).unwrap_or(0.0);
+    let memory_size = offer.get_memory().as_ref()
+        .and_then(|memory| memory.get_size().as_ref())
+        .and_then(|size| size.get_absolute_value()).unwrap_or(0.0);
+    (cpu_amount, memory_size)
+}
+
+fn compare_costs(a: (f64, f64), b: (f64, f64)) -> std::cmp::Ordering {
+    a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/**
+State of a partial placement while searching.
+*/
+struct Search<'a> {
+    requests: &'a Vec<Offer>,
+    order: Vec<usize>,
+    objective: Objective,
+    ledger: CapacityLedger,
+    assignment: Vec<usize>,
+    uses: Vec<usize>,
+    total_price: f64,
+    host_count: usize,
+}
+
+impl<'a> Search<'a> {
+    fn place(&mut self, request: usize, offer: usize) -> Result<u64, OntologyError> {
+        let reservation = self.ledger.reserve(&self.requests[request], offer)?;
+        self.assignment[request] = offer;
+        self.total_price += price(&self.ledger.get_offers()[offer]);
+        if self.uses[offer] == 0 {
+            self.host_count += 1;
+        }
+        self.uses[offer] += 1;
+        Ok(reservation)
+    }
+
+    fn unplace(&mut self, offer: usize, reservation: u64) -> Result<(), OntologyError> {
+        self.ledger.release(reservation)?;
+        self.total_price -= price(&self.ledger.get_offers()[offer]);
+        self.uses[offer] -= 1;
+        if self.uses[offer] == 0 {
+            self.host_count -= 1;
+        }
+        Ok(())
+    }
+
+    /**
+    Returns the cost of the partial placement after adding the request to the offer.
+    */
+    fn cost_with(&self, offer: usize) -> (f64, f64) {
+        let host_count = if self.uses[offer] == 0 { self.host_count + 1 } else { self.host_count };
+        cost(self.objective, self.total_price + price(&self.ledger.get_offers()[offer]), host_count)
+    }
+
+    fn placement(&self) -> Placement {
+        Placement { assignment: self.assignment.clone(), total_price: self.total_price, host_count: self.host_count }
+    }
+
+    /**
+    Returns the offers sufficient for the request with their remaining capacity, cheapest first.
+    For the host objective offers already in use come first, followed by the roomiest ones.
+    */
+    fn candidates(&self, request: usize) -> Vec<usize> {
+        let mut candidates = self.ledger.sufficient(&self.requests[request]);
+        candidates.sort_by(|&a, &b| {
+            let (cost_a, cost_b) = (self.cost_with(a), self.cost_with(b));
+            let roomier = || {
+                let size_a = self.ledger.remaining(a).map(|offer| size(&offer)).unwrap_or((0.0, 0.0));
+                let size_b = self.ledger.remaining(b).map(|offer| size(&offer)).unwrap_or((0.0, 0.0));
+                compare_costs(size_b, size_a)
+            };
+            match self.objective {
+                Objective::price => compare_costs(cost_a, cost_b),
+                Objective::hosts => cost_a.0.partial_cmp(&cost_b.0).unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(roomier)
+                    .then_with(|| compare_costs(cost_a, cost_b)),
+            }
+        });
+        candidates
+    }
+
+    fn greedy(&mut self) -> Result<Option<Placement>, OntologyError> {
+        for i in 0..self.order.len() {
+            let request = self.order[i];
+            match self.candidates(request).first() {
+                Some(&offer) => { self.place(request, offer)?; }
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(self.placement()))
+    }
+
+    /**
+    Places the requests from `depth` on, keeping the cheapest complete placement in `best`.
+    Branches whose partial cost already reaches the best cost are cut off, which is
+    valid since prices and host counts never decrease when adding requests.
+    */
+    fn exact(&mut self, depth: usize, best: &mut Option<Placement>) -> Result<(), OntologyError> {
+        if depth == self.order.len() {
+            let placement = self.placement();
+            let better = match *best {
+                Some(ref best) => compare_costs(placement.cost(self.objective), best.cost(self.objective)) == std::cmp::Ordering::Less,
+                None => true,
+            };
+            if better {
+                *best = Some(placement);
+            }
+            return Ok(());
+        }
+
+        let request = self.order[depth];
+        for offer in self.candidates(request) {
+            if let Some(ref best) = *best {
+                if compare_costs(self.cost_with(offer), best.cost(self.objective)) != std::cmp::Ordering::Less {
+                    continue;
+                }
+            }
+            let reservation = self.place(request, offer)?;
+            self.exact(depth + 1, best)?;
+            self.unplace(offer, reservation)?;
+        }
+        Ok(())
+    }
+}
+
+/**
+Places every request on an offer of the pool.
+`Parameters`
+* requests:&Vec<Offer>: Requests of the job
+* offers:&Offers: Pool of offers
+* objective:Objective: Quantity to minimize
+* strategy:Strategy: Greedy or exact search
+`Return`
+ Ok(Some(Placement)):
+    An assignment where `comparing` reports a match for every request and the
+    remaining capacity (CPU amount, memory size, disk size, network quota) of the
+    offer it is placed on, taking the other requests on that offer into account.
+ Ok(None):
+    If no such assignment was found. The greedy strategy may miss one which exists.
+*/
+pub fn placement(requests: &Vec<Offer>, offers: &Offers, objective: Objective, strategy: Strategy) -> Result<Option<Placement>, OntologyError> {
+    let mut order: Vec<usize> = (0..requests.len()).collect();
+    order.sort_by(|&a, &b| compare_costs(size(&requests[b]), size(&requests[a])));
+
+    let mut search = Search {
+        requests: requests,
+        order: order,
+        objective: objective,
+        ledger: CapacityLedger::new(offers.clone()),
+        assignment: vec![0; requests.len()],
+        uses: vec![0; offers.get_offers().len()],
+        total_price: 0.0,
+        host_count: 0,
+    };
+
+    match strategy {
+        Strategy::greedy => search.greedy(),
+        Strategy::exact => {
+            let mut best = None;
+            search.exact(0, &mut best)?;
+            Ok(best)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn offer(host: &str, cores: u32, price: u32) -> String {
+        format!(r#"{{
+            "host": {{"value": "{}"}},
+            "ontology_version": {{"value": 1.1}},
+            "repository_version": {{"value": 2.1}},
+            "timeout": {{"value": 1000}},
+            "cpu": {{"amount": {{"value": {}}}, "instruction_set": []}},
+            "payment": {{"price": {{"value": {}}}}}
+        }}"#, host, cores, price)
+    }
+
+    fn request(cores: u32) -> Offer {
+        serde_json::from_str(&format!(r#"{{
+            "host": {{"value": "host", "compareOperator": "prefix"}},
+            "ontology_version": {{"value": 1.1}},
+            "repository_version": {{"value": 2.1}},
+            "timeout": {{"value": 1000}},
+            "cpu": {{"amount": {{"value": {}, "compareOperator": "leq"}}, "instruction_set": []}}
+        }}"#, cores)).unwrap()
+    }
+
+    fn pool() -> Offers {
+        serde_json::from_str(&format!(r#"{{"offers": [{}, {}, {}]}}"#,
+                                      offer("host a", 8, 10), offer("host b", 4, 3), offer("host c", 3, 4))).unwrap()
+    }
+
+    #[test]
+    fn placements_respect_capacity_and_minimize_the_objective() {
+        let requests = vec![request(2), request(4), request(2)];
+        let offers = pool();
+
+        let cheapest = placement(&requests, &offers, Objective::price, Strategy::exact).unwrap().unwrap();
+        assert_eq!(cheapest.get_assignment(), &vec![1, 0, 1]);
+        assert_eq!(cheapest.get_total_price(), 16.0);
+
+        let greedy = placement(&requests, &offers, Objective::price, Strategy::greedy).unwrap().unwrap();
+        assert_eq!(greedy.get_total_price(), 17.0);
+        let mut ledger = CapacityLedger::new(offers.clone());
+        for (request, &offer) in requests.iter().zip(greedy.get_assignment()) {
+            ledger.reserve(request, offer).unwrap();
+        }
+
+        let fewest = placement(&requests, &offers, Objective::hosts, Strategy::exact).unwrap().unwrap();
+        assert_eq!(fewest.get_assignment(), &vec![0, 0, 0]);
+        assert_eq!(fewest.get_host_count(), 1);
+        let greedy = placement(&requests, &offers, Objective::hosts, Strategy::greedy).unwrap().unwrap();
+        assert_eq!(greedy.get_host_count(), 1);
+
+        assert_eq!(placement(&vec![request(8), request(4), request(4)], &offers, Objective::price, Strategy::exact).unwrap(), None);
+    }
+}
@@ -0,0 +1,638 @@
+/**
+Contains the matchmaking protocol with which a tenant submits a request to a
+node of the swarm and receives the offers of the node which match it.
+
+A client connects to a node via TCP and sends frames, each carrying the versions of
+the protocol, the ontology and the repository the sender uses, and one message:
+* `submitRequest`: the node compares the request with the remaining capacity of its
+  current offers using `comparing` and answers with a `candidateOffer` per matching
+  offer, or with a `reject` if no offer matches
+* `accept`: the node reserves the capacity the request claims on the candidate in its
+  `CapacityLedger` and confirms with an `accept`, or answers with a `reject` if other
+  accepted requests left too little capacity
+* `reject`: the client declines a candidate, releasing it if it was accepted before
+* `withdraw`: the client withdraws the request, releasing its accepted candidates,
+  and the node confirms with a `withdraw`
+
+The candidates a client accepted are released as well when it disconnects.
+
+Frames of a peer using another version are answered with a `reject`. Requests and offers
+are sent compressed with the repository, and frames are written as described in `frame`,
+as MsgPack preceded by their length.
+*/
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpListener, TcpStream, Shutdown};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+
+use basicElements::Version;
+use canonical::offer_id;
+use frame::{read_frame, write_frame};
+use ontology::{Offers, Offer, unix_time};
+use repository::Repository;
+use reservation::CapacityLedger;
+use error::OntologyError;
+
+/// Version of the frames and messages of the protocol.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/**
+Versions a peer uses, which have to be the same for both sides of a connection.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Header {
+    protocol_version: u32,
+    ontology_version: f64,
+    repository_version: Version,
+}
+
+impl Header {
+    pub fn new(ontology_version: f64, repository_version: Version) -> Header {
+        Header { protocol_version: PROTOCOL_VERSION, ontology_version: ontology_version, repository_version: repository_version }
+    }
+
+    /**
+    Returns the header of the versions the offer uses, None if a version is missing.
+    */
+    pub fn of(offer: &Offer) -> Option<Header> {
+        match (*offer.get_ontology_version().get_value(), offer.get_repository_version().get_version()) {
+            (Some(ontology_version), Some(Ok(repository_version))) => Some(Header::new(ontology_version, repository_version)),
+            (_, _) => None,
+        }
+    }
+
+    pub fn get_protocol_version(&self) -> u32 { self.protocol_version }
+    pub fn get_ontology_version(&self) -> f64 { self.ontology_version }
+    pub fn get_repository_version(&self) -> &Version { &self.repository_version }
+
+    /**
+    Checks that a peer sending the other header can be understood.
+    `Return`
+      Err with the reason if a version differs
+    */
+    pub fn check(&self, other: &Header) -> Result<(), String> {
+        if self.protocol_version != other.protocol_version {
+            Err(format!("protocol version {} is not supported, expected {}", other.protocol_version, self.protocol_version))
+        } else if self.ontology_version != other.ontology_version {
+            Err(format!("ontology version {} is not supported, expected {}", other.ontology_version, self.ontology_version))
+        } else if self.repository_version != other.repository_version {
+            Err(format!("repository version {} is not supported, expected {}", other.repository_version, self.repository_version))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Asks for the offers matching the request.
+    submitRequest { request_id: u64, request: Offer },
+    /// An offer matching the request, followed by `remaining` further candidates.
+    candidateOffer { request_id: u64, offer_id: String, offer: Offer, remaining: u32 },
+    /// Accepts a candidate of the request, or confirms the acceptance.
+    accept { request_id: u64, offer_id: String },
+    /// Declines a candidate or, without offer ID, the whole request.
+    reject { request_id: u64, offer_id: Option<String>, reason: String },
+    /// Withdraws the request, or confirms the withdrawal.
+    withdraw { request_id: u64 },
+}
+
+impl Message {
+    pub fn get_request_id(&self) -> u64 {
+        match *self {
+            Message::submitRequest { request_id, .. } => request_id,
+            Message::candidateOffer { request_id, .. } => request_id,
+            Message::accept { request_id, .. } => request_id,
+            Message::reject { request_id, .. } => request_id,
+            Message::withdraw { request_id } => request_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Frame {
+    header: Header,
+    message: Message,
+}
+
+impl Frame {
+    pub fn new(header: Header, message: Message) -> Frame {
+        Frame { header: header, message: message }
+    }
+
+    pub fn get_header(&self) -> &Header { &self.header }
+    pub fn get_message(&self) -> &Message { &self.message }
+}
+
+fn reject(request_id: u64, offer_id: Option<String>, reason: &str) -> Message {
+    Message::reject { request_id: request_id, offer_id: offer_id, reason: reason.to_string() }
+}
+
+/**
+The requests of a single client, the candidates sent for them
+and the reservations of the accepted candidates.
+*/
+#[derive(Default)]
+struct Session {
+    /// Decompressed requests and the IDs of their candidates by request ID
+    candidates: HashMap<u64, (Offer, Vec<String>)>,
+    /// Reservations in the ledger by request ID and offer ID
+    reservations: HashMap<(u64, String), u64>,
+}
+
+struct ServerState {
+    header: Header,
+    repository: Repository,
+    /// Current offers and the capacity reserved on them by accepted candidates
+    ledger: Mutex<CapacityLedger>,
+    /// Open client connections by their running number, shut down when the server is dropped
+    connections: Mutex<HashMap<u64, TcpStream>>,
+    running: AtomicBool,
+}
+
+impl ServerState {
+    /**
+    Releases the reservations of the session for the request, or only the one of the given offer.
+    Reservations dropped from the ledger with their offer are already released.
+    */
+    fn release(&self, session: &mut Session, request_id: u64, offer: Option<&String>) {
+        let released: Vec<(u64, String)> = session.reservations.keys()
+            .filter(|&&(request, ref id)| request == request_id && offer.map(|offer| offer == id).unwrap_or(true))
+            .cloned()
+            .collect();
+        let mut ledger = self.ledger.lock().unwrap();
+        for key in released {
+            if let Some(reservation) = session.reservations.remove(&key) {
+                let _ = ledger.release(reservation);
+            }
+        }
+    }
+
+    /**
+    Compares the request with the remaining capacity of every current offer.
+    */
+    fn candidates(&self, request: &Offer) -> Result<Vec<(String, Offer)>, OntologyError> {
+        let now = unix_time();
+        let ledger = self.ledger.lock().unwrap();
+        let mut candidates = Vec::new();
+        for position in ledger.sufficient(request) {
+            let offer = &ledger.get_offers()[position];
+            if !offer.is_expired(now) {
+                let mut compressed = offer.clone();
+                compressed.compress(&self.repository)?;
+                candidates.push((offer_id(offer)?, compressed));
+            }
+        }
+        Ok(candidates)
+    }
+
+    /**
+    Reserves the capacity the request claims on the offer with the given ID.
+    `Return`
+      The ID of the reservation, or the reason why the offer cannot be reserved
+    */
+    fn reserve(&self, request: &Offer, offer: &str) -> Result<u64, String> {
+        let mut ledger = self.ledger.lock().unwrap();
+        let position = ledger.get_offers().iter()
+            .position(|candidate| offer_id(candidate).map(|id| id == offer).unwrap_or(false));
+        match position {
+            Some(position) => ledger.reserve(request, position).map_err(|e| match e {
+                OntologyError::InsufficientCapacity(_) => "the remaining capacity of the offer does not satisfy the request".to_string(),
+                e => e.to_string(),
+            }),
+            None => Err("the offer is not offered anymore".to_string()),
+        }
+    }
+
+    fn handle(&self, session: &mut Session, frame: Frame) -> Vec<Message> {
+        let request_id = frame.message.get_request_id();
+        if let Err(reason) = self.header.check(&frame.header) {
+            return vec![reject(request_id, None, &reason)];
+        }
+        match frame.message {
+            Message::submitRequest { mut request, .. } => {
+                let found = request.decompress(&self.repository)
+                    .and_then(|_| {
+                        request.normalize();
+                        self.candidates(&request)
+                    });
+                match found {
+                    Ok(ref found) if found.is_empty() => vec![reject(request_id, None, "no offer matches the request")],
+                    Ok(found) => {
+                        session.candidates.insert(request_id, (request, found.iter().map(|&(ref id, _)| id.clone()).collect()));
+                        let count = found.len();
+                        found.into_iter().enumerate()
+                            .map(|(position, (id, offer))| Message::candidateOffer {
+                                request_id: request_id, offer_id: id, offer: offer, remaining: (count - position - 1) as u32 })
+                            .collect()
+                    }
+                    Err(e) => vec![reject(request_id, None, &e.to_string())],
+                }
+            }
+            Message::accept { offer_id, .. } => {
+                if session.reservations.contains_key(&(request_id, offer_id.clone())) {
+                    return vec![Message::accept { request_id: request_id, offer_id: offer_id }];
+                }
+                let reserved = match session.candidates.get(&request_id) {
+                    Some(&(ref request, ref ids)) if ids.contains(&offer_id) => self.reserve(request, &offer_id),
+                    _ => return vec![reject(request_id, Some(offer_id), "the offer is no candidate of the request")],
+                };
+                match reserved {
+                    Ok(reservation) => {
+                        session.reservations.insert((request_id, offer_id.clone()), reservation);
+                        vec![Message::accept { request_id: request_id, offer_id: offer_id }]
+                    }
+                    Err(reason) => vec![reject(request_id, Some(offer_id), &reason)],
+                }
+            }
+            Message::reject { offer_id: Some(offer_id), .. } => {
+                if let Some(&mut (_, ref mut ids)) = session.candidates.get_mut(&request_id) {
+                    ids.retain(|id| id != &offer_id);
+                }
+                self.release(session, request_id, Some(&offer_id));
+                vec![]
+            }
+            Message::reject { offer_id: None, .. } | Message::withdraw { .. } => {
+                session.candidates.remove(&request_id);
+                self.release(session, request_id, None);
+                vec![Message::withdraw { request_id: request_id }]
+            }
+            Message::candidateOffer { offer_id, .. } =>
+                vec![reject(request_id, Some(offer_id), "only nodes send candidates")],
+        }
+    }
+
+    fn exchange(&self, stream: &mut TcpStream, session: &mut Session) -> Result<(), OntologyError> {
+        loop {
+            let frame: Frame = read_frame(stream)?;
+            for message in self.handle(session, frame) {
+                write_frame(stream, &Frame::new(self.header.clone(), message))?;
+            }
+        }
+    }
+
+    /**
+    Answers the frames of a client until it disconnects, and then releases
+    the candidates the client accepted.
+    */
+    fn serve(&self, mut stream: TcpStream) -> Result<(), OntologyError> {
+        let mut session = Session::default();
+        let result = self.exchange(&mut stream, &mut session);
+        let requests: Vec<u64> = session.reservations.keys().map(|&(request, _)| request).collect();
+        for request_id in requests {
+            self.release(&mut session, request_id, None);
+        }
+        result
+    }
+}
+
+/**
+Reference implementation of a node answering the requests of tenants with its offers.
+The server stops accepting requests and closes all connections when it is dropped.
+*/
+pub struct MatchServer {
+    address: SocketAddr,
+    state: Arc<ServerState>,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl MatchServer {
+    /**
+    Creates a server listening on the given address.
+    `Parameters`
+    * address:&str: Address to listen on, e.g. "127.0.0.1:0" to pick a free port
+    * header:Header: Versions the server uses
+    * offers:Offers: Decompressed offers the requests are compared with
+    * repository:Repository: Repository requests and offers are compressed with on the wire
+    */
+    pub fn bind(address: &str, header: Header, offers: Offers, repository: Repository) -> Result<MatchServer, OntologyError> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(ServerState {
+            header: header,
+            repository: repository,
+            ledger: Mutex::new(CapacityLedger::new(offers)),
+            connections: Mutex::new(HashMap::new()),
+            running: AtomicBool::new(true),
+        });
+        let listening = state.clone();
+        let handle = thread::spawn(move || {
+            let mut next_connection: u64 = 0;
+            for stream in listener.incoming() {
+                if !listening.running.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let connection = next_connection;
+                    next_connection += 1;
+                    if let Ok(clone) = stream.try_clone() {
+                        listening.connections.lock().unwrap().insert(connection, clone);
+                    }
+                    let serving = listening.clone();
+                    thread::spawn(move || {
+                        let _ = serving.serve(stream);
+                        serving.connections.lock().unwrap().remove(&connection);
+                    });
+                }
+            }
+        });
+        Ok(MatchServer { address: address, state: state, listener: Some(handle) })
+    }
+
+    pub fn get_address(&self) -> &SocketAddr { &self.address }
+
+    /**
+    Replaces the offers of the server, e.g. with the current view of a `GossipNode`.
+    Accepted offers which are still offered stay accepted, see `CapacityLedger::set_offers`.
+    */
+    pub fn set_offers(&self, offers: Offers) {
+        self.state.ledger.lock().unwrap().set_offers(offers);
+    }
+
+    /**
+    Returns the IDs of the offers on which an accepted candidate reserved capacity.
+    */
+    pub fn get_accepted(&self) -> Vec<String> {
+        let ledger = self.state.ledger.lock().unwrap();
+        let mut accepted: Vec<String> = ledger.get_reservations().iter()
+            .filter_map(|reservation| offer_id(&ledger.get_offers()[reservation.get_offer()]).ok())
+            .collect();
+        accepted.sort();
+        accepted.dedup();
+        accepted
+    }
+}
+
+impl Drop for MatchServer {
+    fn drop(&mut self) {
+        self.state.running.store(false, Ordering::SeqCst);
+        // Wakes the listener up so that it notices it was stopped
+        let _ = TcpStream::connect(&self.address);
+        if let Some(handle) = self.listener.take() {
+            let _ = handle.join();
+        }
+        for (_, connection) in self.state.connections.lock().unwrap().drain() {
+            let _ = connection.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/**
+An offer a node sent as candidate for a request.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    offer_id: String,
+    offer: Offer,
+}
+
+impl Candidate {
+    pub fn get_offer_id(&self) -> &String { &self.offer_id }
+    pub fn get_offer(&self) -> &Offer { &self.offer }
+}
+
+/**
+Reference implementation of a tenant submitting requests to a node.
+*/
+pub struct MatchClient {
+    stream: TcpStream,
+    header: Header,
+    repository: Repository,
+}
+
+impl MatchClient {
+    /**
+    Connects to a node.
+    `Parameters`
+    * address:&SocketAddr: Address of the node
+    * header:Header: Versions the client uses
+    * repository:Repository: Repository requests and offers are compressed with on the wire
+    */
+    pub fn connect(address: &SocketAddr, header: Header, repository: Repository) -> Result<MatchClient, OntologyError> {
+        let stream = TcpStream::connect(address)?;
+        Ok(MatchClient { stream: stream, header: header, repository: repository })
+    }
+
+    fn send(&mut self, message: Message) -> Result<(), OntologyError> {
+        write_frame(&mut self.stream, &Frame::new(self.header.clone(), message))
+    }
+
+    /**
+    Receives the next message, turning a `reject` into an error.
+    */
+    fn receive(&mut self) -> Result<Message, OntologyError> {
+        let frame: Frame = read_frame(&mut self.stream)?;
+        match frame.message {
+            Message::reject { reason, .. } => Err(OntologyError::Rejected(reason)),
+            message => {
+                self.header.check(&frame.header).map_err(OntologyError::UnexpectedMessage)?;
+                Ok(message)
+            }
+        }
+    }
+
+    /**
+    Submits a request and waits for its candidates.
+    `Parameters`
+    * request_id:u64: ID chosen by the client to refer to the request later on
+    * request:&Offer: Decompressed request
+    `Return`
+      Ok with the decompressed candidates in the order the node sent them,
+      Err(Rejected) if no offer matches or the node does not support the versions of the client
+    */
+    pub fn submit(&mut self, request_id: u64, request: &Offer) -> Result<Vec<Candidate>, OntologyError> {
+        let mut request = request.clone();
+        request.compress(&self.repository)?;
+        self.send(Message::submitRequest { request_id: request_id, request: request })?;
+        let mut candidates = Vec::new();
+        loop {
+            match self.receive()? {
+                Message::candidateOffer { request_id: id, offer_id, mut offer, remaining } if id == request_id => {
+                    offer.decompress(&self.repository)?;
+                    offer.normalize();
+                    candidates.push(Candidate { offer_id: offer_id, offer: offer });
+                    if remaining == 0 {
+                        return Ok(candidates);
+                    }
+                }
+                _ => return Err(OntologyError::UnexpectedMessage("expected a candidate offer".to_string())),
+            }
+        }
+    }
+
+    /**
+    Accepts a candidate of the request.
+    `Return`
+      Err(Rejected) if the offer is no candidate of the request or was accepted by another request
+    */
+    pub fn accept(&mut self, request_id: u64, offer_id: &str) -> Result<(), OntologyError> {
+        self.send(Message::accept { request_id: request_id, offer_id: offer_id.to_string() })?;
+        match self.receive()? {
+            Message::accept { request_id: id, offer_id: ref accepted } if id == request_id && accepted == offer_id => Ok(()),
+            _ => Err(OntologyError::UnexpectedMessage("expected the acceptance to be confirmed".to_string())),
+        }
+    }
+
+    /**
+    Declines a candidate of the request, releasing it if it was accepted.
+    */
+    pub fn reject(&mut self, request_id: u64, offer_id: &str, reason: &str) -> Result<(), OntologyError> {
+        self.send(reject(request_id, Some(offer_id.to_string()), reason))
+    }
+
+    /**
+    Withdraws the request, releasing its accepted candidate.
+    */
+    pub fn withdraw(&mut self, request_id: u64) -> Result<(), OntologyError> {
+        self.send(Message::withdraw { request_id: request_id })?;
+        match self.receive()? {
+            Message::withdraw { request_id: id } if id == request_id => Ok(()),
+            _ => Err(OntologyError::UnexpectedMessage("expected the withdrawal to be confirmed".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use basicElements::{IntElement, IntOperator};
+    use ontology::comparing;
+
+    fn repository() -> Repository {
+        Repository::from_json_file("repository.json").unwrap()
+    }
+
+    fn server() -> (MatchServer, Offers) {
+        let offers = Offers::from_json_file("offerlist.json").unwrap();
+        let header = Header::of(&offers.get_offers()[0]).unwrap();
+        (MatchServer::bind("127.0.0.1:0", header, offers.clone(), repository()).unwrap(), offers)
+    }
+
+    #[test]
+    fn clients_receive_and_accept_candidates_over_loopback() {
+        let (server, offers) = server();
+        let request = offers.get_offers()[0].clone();
+        let header = Header::of(&request).unwrap();
+        let mut client = MatchClient::connect(server.get_address(), header.clone(), repository()).unwrap();
+        let mut other = MatchClient::connect(server.get_address(), header, repository()).unwrap();
+
+        let candidates = client.submit(1, &request).unwrap();
+        let expected: Vec<String> = offers.get_offers().iter()
+            .filter(|offer| comparing(&request, offer).is_match())
+            .map(|offer| offer_id(offer).unwrap())
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(candidates.iter().map(|candidate| candidate.get_offer_id().clone()).collect::<Vec<String>>(), expected);
+        assert_eq!(offer_id(candidates[0].get_offer()).unwrap(), expected[0]);
+
+        let chosen = candidates[0].get_offer_id().clone();
+        assert_eq!(other.submit(7, &request).unwrap().len(), expected.len());
+        client.accept(1, &chosen).unwrap();
+        assert_eq!(server.get_accepted(), vec![chosen.clone()]);
+        match other.accept(7, &chosen) {
+            Err(OntologyError::Rejected(_)) => (),
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+        assert!(other.submit(8, &request).unwrap().iter().all(|candidate| candidate.get_offer_id() != &chosen));
+
+        client.withdraw(1).unwrap();
+        assert!(server.get_accepted().is_empty());
+        other.accept(7, &chosen).unwrap();
+        other.reject(7, &chosen, "too expensive").unwrap();
+        other.withdraw(7).unwrap();
+        assert!(server.get_accepted().is_empty());
+    }
+
+    /**
+    Returns the first example offer as request for two of its four cores.
+    */
+    fn request_for_two_cores(offers: &Offers) -> Offer {
+        let mut request = offers.get_offers()[0].clone();
+        request.set_memory(None);
+        request.get_cpu_mut().as_mut().unwrap().set_amount(Some(IntElement::new(2, None, IntOperator::leq)));
+        request
+    }
+
+    #[test]
+    fn candidates_are_accepted_while_capacity_is_left() {
+        let (server, offers) = server();
+        let request = request_for_two_cores(&offers);
+        let header = Header::of(&request).unwrap();
+        let mut client = MatchClient::connect(server.get_address(), header.clone(), repository()).unwrap();
+        let mut other = MatchClient::connect(server.get_address(), header, repository()).unwrap();
+
+        let chosen = client.submit(1, &request).unwrap()[0].get_offer_id().clone();
+        assert!(other.submit(2, &request).unwrap().iter().any(|candidate| candidate.get_offer_id() == &chosen));
+        client.accept(1, &chosen).unwrap();
+        other.accept(2, &chosen).unwrap();
+        assert_eq!(server.get_accepted(), vec![chosen.clone()]);
+
+        let remaining = client.submit(3, &request).unwrap_or(Vec::new());
+        assert!(remaining.iter().all(|candidate| candidate.get_offer_id() != &chosen));
+        client.withdraw(1).unwrap();
+        client.submit(4, &request).unwrap();
+        client.accept(4, &chosen).unwrap();
+    }
+
+    #[test]
+    fn accepted_candidates_are_released_when_clients_disconnect() {
+        let (server, offers) = server();
+        let request = request_for_two_cores(&offers);
+        let header = Header::of(&request).unwrap();
+        let mut client = MatchClient::connect(server.get_address(), header.clone(), repository()).unwrap();
+        let chosen = client.submit(1, &request).unwrap()[0].get_offer_id().clone();
+        client.accept(1, &chosen).unwrap();
+        client.submit(2, &request).unwrap();
+        client.accept(2, &chosen).unwrap();
+        drop(client);
+
+        let start = Instant::now();
+        while !server.get_accepted().is_empty() {
+            assert!(start.elapsed() < Duration::from_secs(5), "the candidates of disconnected clients stay accepted");
+            thread::sleep(Duration::from_millis(10));
+        }
+        let mut all_cores = request.clone();
+        all_cores.get_cpu_mut().as_mut().unwrap().set_amount(Some(IntElement::new(4, None, IntOperator::leq)));
+        let mut other = MatchClient::connect(server.get_address(), header, repository()).unwrap();
+        other.submit(3, &all_cores).unwrap();
+        other.accept(3, &chosen).unwrap();
+    }
+
+    #[test]
+    fn connections_are_released_when_clients_disconnect() {
+        let (server, offers) = server();
+        let request = offers.get_offers()[0].clone();
+        let header = Header::of(&request).unwrap();
+        for request_id in 0..3 {
+            let mut client = MatchClient::connect(server.get_address(), header.clone(), repository()).unwrap();
+            client.submit(request_id, &request).unwrap();
+            assert!(!server.state.connections.lock().unwrap().is_empty());
+        }
+
+        let start = Instant::now();
+        while !server.state.connections.lock().unwrap().is_empty() {
+            assert!(start.elapsed() < Duration::from_secs(5), "the connections of disconnected clients are kept");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn frames_of_other_versions_are_rejected() {
+        let (server, offers) = server();
+        let request = offers.get_offers()[0].clone();
+        let header = Header::of(&request).unwrap();
+        let outdated = Header::new(header.get_ontology_version(), header.get_repository_version().next());
+        let mut client = MatchClient::connect(server.get_address(), outdated, repository()).unwrap();
+        match client.submit(1, &request) {
+            Err(OntologyError::Rejected(ref reason)) => assert!(reason.contains("repository version")),
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+
+        let frame = Frame::new(header, Message::withdraw { request_id: 3 });
+        let decoded: Frame = ::rmps::decode::from_slice(&::rmps::encode::to_vec(&frame).unwrap()).unwrap();
+        assert_eq!(decoded, frame);
+    }
+}
@@ -0,0 +1,244 @@
+/**
+Contains functions to rank the offers which are sufficient
+for a request by how well they fit it.
+*/
+
+use std;
+use std::collections::HashMap;
+
+use ontology::{Offers, Offer, comparing};
+use report::MatchReport;
+
+const PRICE_PATH: &'static str = "payment.price";
+
+/**
+Weights of the individual terms of the fitness score.
+Attributes are addressed by their path in the `MatchReport` without
+list indices, e.g. `cpu.frequency` or `disk.size`.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RankingWeights {
+    attributes: HashMap<String, f64>,
+    default: f64,
+    price: f64,
+}
+
+impl Default for RankingWeights {
+    fn default() -> RankingWeights {
+        RankingWeights { attributes: HashMap::new(), default: 1.0, price: 1.0 }
+    }
+}
+
+impl RankingWeights {
+    pub fn new() -> RankingWeights { RankingWeights::default() }
+
+    /**
+    Returns the weight of an attribute. Attributes without an explicit
+    weight use the default weight.
+    */
+    pub fn get_weight(&self, path: &str) -> f64 {
+        match self.attributes.get(&strip_indices(path)) {
+            Some(weight) => *weight,
+            None => self.default,
+        }
+    }
+    pub fn set_weight(&mut self, path: &str, weight: f64) {
+        self.attributes.insert(strip_indices(path), weight);
+    }
+
+    pub fn get_default_weight(&self) -> f64 { self.default }
+    pub fn set_default_weight(&mut self, weight: f64) { self.default = weight; }
+
+    pub fn get_price_weight(&self) -> f64 { self.price }
+    pub fn set_price_weight(&mut self, weight: f64) { self.price = weight; }
+}
+
+/**
+Removes list indices from an attribute path, e.g. `disk[1].size` becomes `disk.size`.
+*/
+fn strip_indices(path: &str) -> String {
+    let mut stripped = String::with_capacity(path.len());
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => in_index = true,
+            ']' => in_index = false,
+            _ if !in_index => stripped.push(c),
+            _ => (),
+        }
+    }
+    stripped
+}
+
+/**
+An offer which is sufficient for a request, together with its position
+in the ranked offers and its fitness score.
+*/
+#[derive(Debug, Clone)]
+pub struct RankedOffer<'a> {
+    offer: &'a Offer,
+    position: usize,
+    score: f64,
+    report: MatchReport,
+}
+
+impl<'a> RankedOffer<'a> {
+    pub fn get_offer(&self) -> &'a Offer { self.offer }
+    pub fn get_position(&self) -> usize { self.position }
+    pub fn get_score(&self) -> f64 { self.score }
+    pub fn get_report(&self) -> &MatchReport { &self.report }
+}
+
+/**
+Returns the price of an offer in absolute units, if it has one.
+*/
+pub(crate) fn price_of(offer: &Offer) -> Option<f64> {
+    match *offer.get_payment() {
+        Some(ref payment) => match *payment.get_price() {
+            Some(ref price) => price.get_absolute_value(),
+            None => None,
+        },
+        None => None,
+    }
+}
+
+/**
+Ranks all offers which are sufficient for a request by their fitness score.
+The score is the weighted mean of
+* the fitness of every compared quantity, i.e. how tightly the offered value
+  satisfies the requested one (1 for an exact fit), and
+* the price term, i.e. the ratio of the cheapest price among the sufficient
+  offers to the price of the offer (1 for the cheapest offer).
+Terms which cannot be determined, e.g. for compressed elements or offers
+without a price, are left out of the mean.
+`Parameters`
+* request:&Offer: Offer used as request
+* offers:&Offers: Offers which are compared to the request
+* weights:&RankingWeights: Weights of the individual terms
+`Return`
+ Vec<RankedOffer>:
+    All sufficient offers, best fit first. Offers with equal
+    scores keep their order in `offers`.
+*/
+pub fn ranking<'a>(request: &Offer, offers: &'a Offers, weights: &RankingWeights) -> Vec<RankedOffer<'a>> {
+    let mut sufficient: Vec<(usize, &'a Offer, MatchReport)> = Vec::new();
+    for (position, offer) in offers.get_offers().iter().enumerate() {
+        let report = comparing(request, offer);
+        if report.is_match() {
+            sufficient.push((position, offer, report));
+        }
+    }
+
+    let cheapest = sufficient.iter()
+        .filter_map(|&(_, offer, _)| price_of(offer))
+        .fold(None, |cheapest: Option<f64>, price| match cheapest {
+            Some(cheapest) if cheapest <= price => Some(cheapest),
+            _ => Some(price),
+        });
+
+    let mut ranked: Vec<RankedOffer<'a>> = sufficient.into_iter().map(|(position, offer, report)| {
+        let mut weighted_sum: f64 = 0.0;
+        let mut weight_sum: f64 = 0.0;
+
+        for attribute in report.get_attributes() {
+            if attribute.get_path() == PRICE_PATH {
+                continue;
+            }
+            if let Some(fitness) = *attribute.get_fitness() {
+                let weight = weights.get_weight(attribute.get_path());
+                weighted_sum += weight * fitness;
+                weight_sum += weight;
+            }
+        }
+
+        if let (Some(cheapest), Some(price)) = (cheapest, price_of(offer)) {
+            let cheapness = if price > 0.0 { cheapest / price } else { 1.0 };
+            weighted_sum += weights.get_price_weight() * cheapness;
+            weight_sum += weights.get_price_weight();
+        }
+
+        let score = if weight_sum > 0.0 { weighted_sum / weight_sum } else { 0.0 };
+        RankedOffer { offer: offer, position: position, score: score, report: report }
+    }).collect();
+
+    ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn offer(amount: u64, price: Option<u64>) -> Offer {
+        let payment = match price {
+            Some(price) => format!(r#", "payment": {{"price": {{"value": {}}}}}"#, price),
+            None => String::new(),
+        };
+        serde_json::from_str(&format!(r#"{{
+            "host": {{"value": "TU KL"}},
+            "ontology_version": {{"value": 1.1}},
+            "repository_version": {{"value": 2.1}},
+            "timeout": {{"value": 1000}},
+            "cpu": {{"amount": {{"value": {}, "compareOperator": "leq"}}, "instruction_set": []}}{}
+        }}"#, amount, payment)).unwrap()
+    }
+
+    /// Weights which only take the amount of CPUs and the price into account.
+    fn weights(amount: f64, price: f64) -> RankingWeights {
+        let mut weights = RankingWeights::new();
+        weights.set_default_weight(0.0);
+        weights.set_weight("cpu.amount", amount);
+        weights.set_price_weight(price);
+        weights
+    }
+
+    fn amounts(ranked: &[RankedOffer]) -> Vec<u64> {
+        ranked.iter()
+            .map(|ranked| ranked.get_offer().get_cpu().as_ref().unwrap().get_amount().as_ref().unwrap().get_value().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn weights_decide_between_fit_and_price() {
+        let request = offer(2, None);
+        // The exact fit costs twice as much as the offer with twice the CPUs.
+        let offers = Offers::new(vec![offer(2, Some(200)), offer(4, Some(100))]);
+
+        let ranked = ranking(&request, &offers, &weights(2.0, 1.0));
+        assert_eq!(amounts(&ranked), vec![2, 4]);
+        assert!(ranked[0].get_score() > ranked[1].get_score());
+
+        let ranked = ranking(&request, &offers, &weights(1.0, 2.0));
+        assert_eq!(amounts(&ranked), vec![4, 2]);
+        assert!(ranked[0].get_score() > ranked[1].get_score());
+    }
+
+    #[test]
+    fn offers_with_equal_scores_keep_their_order() {
+        let request = offer(2, None);
+        let offers = Offers::new(vec![offer(4, Some(100)), offer(2, Some(200))]);
+
+        let ranked = ranking(&request, &offers, &weights(1.0, 1.0));
+        assert_eq!(amounts(&ranked), vec![4, 2]);
+        assert_eq!(ranked[0].get_position(), 0);
+        assert_eq!(ranked[1].get_position(), 1);
+        assert_eq!(ranked[0].get_score(), ranked[1].get_score());
+    }
+
+    #[test]
+    fn offers_which_do_not_match_are_not_ranked() {
+        let request = offer(2, None);
+        // The cheapest offer has too few CPUs and must not set the price scale either.
+        let offers = Offers::new(vec![offer(1, Some(10)), offer(2, Some(100)), offer(8, Some(100))]);
+
+        let ranked = ranking(&request, &offers, &RankingWeights::new());
+        assert_eq!(amounts(&ranked), vec![2, 8]);
+        assert_eq!(ranked.iter().map(|ranked| ranked.get_position()).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(ranked.iter().all(|ranked| ranked.get_report().is_match()));
+
+        let ranked = ranking(&request, &offers, &weights(0.0, 1.0));
+        assert_eq!(ranked[0].get_score(), 1.0);
+        assert_eq!(ranked[1].get_score(), 1.0);
+    }
+}
@@ -0,0 +1,170 @@
+/**
+Contains the structures explaining the result of comparing
+a request with an offer, attribute by attribute.
+*/
+
+use std;
+
+/**
+The outcome of comparing a single attribute of a request with the
+corresponding attribute of an offer.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AttributeMatch {
+    path: String,
+    request_value: String,
+    offer_value: String,
+    operator: String,
+    passed: bool,
+    fitness: Option<f64>,
+}
+
+impl AttributeMatch {
+    pub fn get_path(&self) -> &String { &self.path }
+    pub fn get_request_value(&self) -> &String { &self.request_value }
+    pub fn get_offer_value(&self) -> &String { &self.offer_value }
+    pub fn get_operator(&self) -> &String { &self.operator }
+    pub fn passed(&self) -> bool { self.passed }
+    pub fn get_fitness(&self) -> &Option<f64> { &self.fitness }
+}
+
+impl std::fmt::Display for AttributeMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: request {} ({}) offer {} => {}",
+               self.path, self.request_value, self.operator, self.offer_value,
+               if self.passed { "passed" } else { "failed" })
+    }
+}
+
+/**
+Lists every attribute checked while comparing a request with an offer.
+The offer is sufficient for the request if every attribute passed.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MatchReport {
+    attributes: Vec<AttributeMatch>,
+}
+
+impl MatchReport {
+    pub fn new() -> MatchReport {
+        MatchReport { attributes: Vec::new() }
+    }
+
+    pub fn get_attributes(&self) -> &Vec<AttributeMatch> { &self.attributes }
+
+    /**
+    Adds the outcome of a single attribute comparison to the report.
+    `Parameters`
+    * path:String: Path of the attribute inside the offer, e.g. `cpu.frequency`
+    * request:&A: Value of the attribute in the request
+    * offer:&B: Value of the attribute in the offer
+    * operator:&O: Operator used to compare both values
+    * passed:bool: Result of the comparison
+    `Return`
+      The value of `passed`, so the caller can fold it into its own result.
+    */
+    pub fn record<A, B, O>(&mut self, path: String, request: &A, offer: &B, operator: &O, passed: bool) -> bool
+        where A: std::fmt::Display, B: std::fmt::Display, O: std::fmt::Display {
+        self.record_with_fitness(path, request, offer, operator, passed, None)
+    }
+
+    /**
+    Adds the outcome of a comparison between two quantities to the report,
+    together with how tightly the offered quantity fits the requested one.
+    `Parameters`
+    * fitness:Option<f64>: Fitness in [0, 1] as computed by `IntElement::fitness`
+      or `FloatElement::fitness`, None if it could not be determined
+    * all other parameters as in `record`
+    `Return`
+      The value of `passed`
+    */
+    pub fn record_with_fitness<A, B, O>(&mut self, path: String, request: &A, offer: &B, operator: &O, passed: bool,
+                                        fitness: Option<f64>) -> bool
+        where A: std::fmt::Display, B: std::fmt::Display, O: std::fmt::Display {
+        self.attributes.push(AttributeMatch {
+            path: path,
+            request_value: request.to_string(),
+            offer_value: offer.to_string(),
+            operator: operator.to_string(),
+            passed: passed,
+            fitness: fitness,
+        });
+        passed
+    }
+
+    /**
+    Moves all entries of another report into this one.
+    */
+    pub fn append(&mut self, other: MatchReport) {
+        self.attributes.extend(other.attributes);
+    }
+
+    /**
+    Returns true if every recorded attribute passed.
+    */
+    pub fn is_match(&self) -> bool {
+        self.attributes.iter().all(|attribute| attribute.passed)
+    }
+
+    /**
+    Returns the attributes which caused the offer to be rejected.
+    */
+    pub fn get_failures(&self) -> Vec<&AttributeMatch> {
+        self.attributes.iter().filter(|attribute| !attribute.passed).collect()
+    }
+
+    pub fn failure_count(&self) -> usize {
+        self.attributes.iter().filter(|attribute| !attribute.passed).count()
+    }
+}
+
+impl std::fmt::Display for MatchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for attribute in &self.attributes {
+            writeln!(f, "{}", attribute)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use basicElements::{IntElement, IntOperator};
+    use ontology::{Offers, comparing};
+
+    #[test]
+    fn reports_match_if_every_attribute_passed() {
+        let mut report = MatchReport::new();
+        assert!(report.is_match());
+        assert!(report.record("cpu.amount".to_string(), &2, &4, &"leq", true));
+        assert!(report.is_match());
+        assert!(!report.record("memory.size".to_string(), &8, &4, &"leq", false));
+        assert!(!report.is_match());
+
+        let failures = report.get_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].get_path(), "memory.size");
+        assert_eq!(failures[0].get_request_value(), "8");
+        assert_eq!(failures[0].get_offer_value(), "4");
+        assert_eq!(failures[0].to_string(), "memory.size: request 8 (leq) offer 4 => failed");
+        assert_eq!(report.failure_count(), 1);
+        assert_eq!(report.get_attributes().len(), 2);
+    }
+
+    #[test]
+    fn comparing_reports_the_paths_of_failed_attributes() {
+        let offers = Offers::from_json_file("offerlist.json").unwrap();
+        let offer = &offers.get_offers()[0];
+        assert!(comparing(offer, offer).is_match());
+
+        let mut request = offer.clone();
+        if let Some(ref mut cpu) = *request.get_cpu_mut() {
+            cpu.set_amount(Some(IntElement::new(1000, None, IntOperator::leq)));
+        }
+        let report = comparing(&request, offer);
+        assert!(!report.is_match());
+        let failures: Vec<&String> = report.get_failures().iter().map(|failure| failure.get_path()).collect();
+        assert_eq!(failures, vec!["cpu.amount"]);
+    }
+}
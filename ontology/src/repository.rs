@@ -12,15 +12,76 @@ use rmps::{Deserializer, Serializer};
 
 
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::cmp::Ordering;
 use std::str::FromStr;
 use std;
 
 use serde_json::{Value, Error};
+use sha2::{Digest, Sha256};
 
 use basicElements::{BasicElement,IntElement,FloatElement,StringElement,MagnitudeElement};
-use basicElements::{DiskType,InstructionSet,MemoryGeneration};
+use basicElements::{DiskType,InstructionSet,GenerationElement,Compressible,Version};
+use basicElements::{MAGNITUDES, INSTRUCTION_SETS, MEMORY_GENERATIONS, DISK_TYPES};
+use canonical::hex;
+use frame::{read_frame, write_frame};
+use error::OntologyError;
+
+/**
+Replaces the value of an element, and of its alternatives, with the ID of the equal entry
+in a repository table, see `Compressible::holds_value_of`. Elements without a value or without an equal entry in the table
+are left unchanged.
+`Parameters`
+* element:&mut E: The element to compress
+* table:&Vec<E>: The repository table holding the entries of the element type
+* table_name:&str: Name of the table, used in error messages
+`Return`
+  Err(MissingRepositoryId) if the equal entry has no ID
+*/
+pub fn compress_element<E: Compressible>(element: &mut E, table: &Vec<E>, table_name: &str) -> Result<(), OntologyError> {
+    for alternative in element.get_alternatives_mut() {
+        compress_element(alternative, table, table_name)?;
+    }
+    let id = match table.iter().find(|entry| element.holds_value_of(entry)) {
+        Some(entry) => match *entry.get_repository_id() {
+            Some(id) => id,
+            None => return Err(OntologyError::MissingRepositoryId(format!("an entry of {} has no ID", table_name))),
+        },
+        None => return Ok(()),
+    };
+    element.set_repository_id(Some(id));
+    element.set_repository_value(None);
+    Ok(())
+}
+
+/**
+Sets the value of a compressed element to the value of the repository entry with the same ID
+and removes the ID again, so that decompressing a compressed element restores the original.
+Elements without an ID are left unchanged.
+`Parameters`
+* element:&mut E: The element to decompress
+* table:&Vec<E>: The repository table holding the entries of the element type
+* table_name:&str: Name of the table, used in error messages
+`Return`
+  Err(MissingRepositoryId) if the table has no entry with the ID of the element
+*/
+pub fn decompress_element<E: Compressible>(element: &mut E, table: &Vec<E>, table_name: &str) -> Result<(), OntologyError> {
+    for alternative in element.get_alternatives_mut() {
+        decompress_element(alternative, table, table_name)?;
+    }
+    let id = match *element.get_repository_id() {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+    match table.iter().find(|entry| *entry.get_repository_id() == Some(id)) {
+        Some(entry) => {
+            element.restore_value_of(entry);
+            element.set_repository_id(None);
+            Ok(())
+        }
+        None => Err(OntologyError::MissingRepositoryId(format!("{} has no entry with ID {}", table_name, id))),
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OperatingSystemRepo {
@@ -67,7 +128,7 @@ impl CPURepo {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MemoryRepo {
     size: Vec<FloatElement>,
-    generation: Vec<BasicElement<MemoryGeneration>>
+    generation: Vec<GenerationElement>
 }
 
 impl MemoryRepo {
@@ -75,8 +136,8 @@ impl MemoryRepo {
     pub fn get_size(&self) -> &Vec<FloatElement> { &self.size }
     pub fn get_size_mut(&mut self) -> &mut Vec<FloatElement> { &mut self.size }
     
-    pub fn get_generation(&self) -> &Vec<BasicElement<MemoryGeneration>> { &self.generation }
-    pub fn get_generation_mut(&mut self) -> &mut Vec<BasicElement<MemoryGeneration>> { &mut self.generation }
+    pub fn get_generation(&self) -> &Vec<GenerationElement> { &self.generation }
+    pub fn get_generation_mut(&mut self) -> &mut Vec<GenerationElement> { &mut self.generation }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -97,7 +158,43 @@ pub struct Repository {
     cpu: CPURepo,
     memory: MemoryRepo,
     disk: DiskRepo,
-    virtualization: VirtualizationRepo
+    virtualization: VirtualizationRepo,
+    #[serde(default)]
+    version: Option<Version>,
+}
+
+/**
+Identifies a repository towards a peer by its version and the hash of its content.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RepositoryVersion {
+    version: Option<Version>,
+    hash: String,
+}
+
+impl RepositoryVersion {
+    pub fn get_version(&self) -> &Option<Version> { &self.version }
+    pub fn get_hash(&self) -> &String { &self.hash }
+}
+
+/**
+Compresses a list of magnitude elements, e.g. the magnitudes of a value and its range bounds.
+*/
+pub fn compress_magnitudes(magnitudes: Vec<&mut MagnitudeElement>, table: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+    for magnitude in magnitudes {
+        compress_element(magnitude, table, "magnitude")?;
+    }
+    Ok(())
+}
+
+/**
+Decompresses a list of magnitude elements, e.g. the magnitudes of a value and its range bounds.
+*/
+pub fn decompress_magnitudes(magnitudes: Vec<&mut MagnitudeElement>, table: &Vec<MagnitudeElement>) -> Result<(), OntologyError> {
+    for magnitude in magnitudes {
+        decompress_element(magnitude, table, "magnitude")?;
+    }
+    Ok(())
 }
 
 impl Repository {
@@ -118,6 +215,255 @@ impl Repository {
     
     pub fn get_virtualization(&self) -> &VirtualizationRepo { &self.virtualization }
     pub fn get_virtualization_mut(&mut self) -> &mut VirtualizationRepo { &mut self.virtualization }
+
+    /**
+    Returns the version offers compressed with the repository carry as `repository_version`,
+    None for a repository without version, which is used with offers of any version.
+    */
+    pub fn get_version(&self) -> &Option<Version> { &self.version }
+    pub fn set_version(&mut self, version: Option<Version>) { self.version = version; }
+
+    /**
+    Returns the hex encoded SHA-256 hash of the MsgPack encoded tables of the repository,
+    which does not depend on the version, so peers can check that their repositories
+    of the same version have the same entries.
+    */
+    pub fn content_hash(&self) -> Result<String, OntologyError> {
+        let mut value = serde_json::to_value(self)?;
+        if let Value::Object(ref mut object) = value {
+            object.remove("version");
+        }
+        Ok(hex(&Sha256::digest(&rmps::encode::to_vec(&value)?)))
+    }
+
+    pub fn get_repository_version(&self) -> Result<RepositoryVersion, OntologyError> {
+        Ok(RepositoryVersion { version: self.version.clone(), hash: self.content_hash()? })
+    }
+
+    /**
+    Checks that every table can be used to compress offers: each ID and each value is used
+    by a single entry, every entry has an ID, the IDs of a table leave no gaps below the
+    largest one, and the tables of enums, i.e. magnitudes, instruction sets, memory generations
+    and disk types, hold every variant.
+    `Return`
+      The issues found, empty if the repository is valid
+    */
+    pub fn validate(&self) -> Vec<RepositoryIssue> {
+        match serde_json::to_value(self) {
+            Ok(document) => check_document(&document),
+            Err(e) => vec![RepositoryIssue::unknownValue(String::new(), e.to_string())],
+        }
+    }
+
+    /**
+    Validates a repository given as JSON document, see `validate`. Other than loading the
+    repository, IDs outside the range of i16 and unknown enum values, e.g. `SSE4.1` instead
+    of `SSE4_1`, are reported as issues together with all other issues of the document.
+    `Parameters`
+      data:&str => The JSON document
+    `Return`
+      The issues found, or the JSON error if the document is no repository for other reasons
+    **/
+    pub fn validate_json(data: &str) -> Result<Vec<RepositoryIssue>, OntologyError> {
+        let document: Value = serde_json::from_str(data)?;
+        let issues = check_document(&document);
+        let loadable = issues.iter().all(|issue| match *issue {
+            RepositoryIssue::invalidId(_, _) | RepositoryIssue::unknownValue(_, _) => false,
+            _ => true,
+        });
+        if loadable {
+            serde_json::from_value::<Repository>(document)?;
+        }
+        Ok(issues)
+    }
+}
+
+/**
+A problem of a repository found while validating it, together with the table it was found in.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RepositoryIssue {
+    /// Several entries of the table have the ID.
+    duplicateId(String, i16),
+    /// Several entries of the table have the value.
+    duplicateValue(String, String),
+    /// The entry with the value has no ID.
+    missingId(String, String),
+    /// An ID is no integer within the range of i16.
+    invalidId(String, String),
+    /// No entry of the table has the ID, although a larger ID is used.
+    unusedId(String, i16),
+    /// The value of an entry does not name a variant of the enum the table holds.
+    unknownValue(String, String),
+    /// No entry of the table holds the enum variant.
+    missingEntry(String, String),
+}
+
+impl std::fmt::Display for RepositoryIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RepositoryIssue::duplicateId(ref table, id) => write!(f, "{}: ID {} is used by several entries", table, id),
+            RepositoryIssue::duplicateValue(ref table, ref value) => write!(f, "{}: {} has several entries", table, value),
+            RepositoryIssue::missingId(ref table, ref value) => write!(f, "{}: {} has no ID", table, value),
+            RepositoryIssue::invalidId(ref table, ref id) => write!(f, "{}: {} is no valid ID", table, id),
+            RepositoryIssue::unusedId(ref table, id) => write!(f, "{}: ID {} is skipped", table, id),
+            RepositoryIssue::unknownValue(ref table, ref value) => write!(f, "{}: {} is no known value", table, value),
+            RepositoryIssue::missingEntry(ref table, ref value) => write!(f, "{}: {} has no entry", table, value),
+        }
+    }
+}
+
+/**
+Serializes every variant of an enum the way it is written in the repository.
+*/
+fn variants<T: Serialize>(variants: &[T]) -> Option<Vec<Value>> {
+    Some(variants.iter().filter_map(|variant| serde_json::to_value(variant).ok()).collect())
+}
+
+/**
+Compares two values of a table, numbers by their value regardless of how they are written.
+*/
+fn same_value(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        (_, _) => a == b,
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match *value {
+        Value::String(ref value) => value.clone(),
+        ref value => value.to_string(),
+    }
+}
+
+/**
+Checks every table of a repository given as JSON value, see `Repository::validate`.
+*/
+fn check_document(document: &Value) -> Vec<RepositoryIssue> {
+    let tables = vec![
+        ("magnitude", "magnitude", variants(&MAGNITUDES)),
+        ("operating_system.system_type", "value", None),
+        ("cpu.manufacturer", "value", None),
+        ("cpu.frequency", "value", None),
+        ("cpu.instruction_set", "value", variants(&INSTRUCTION_SETS)),
+        ("memory.size", "value", None),
+        ("memory.generation", "value", variants(&MEMORY_GENERATIONS)),
+        ("disk.disk_type", "value", variants(&DISK_TYPES)),
+        ("virtualization.virtualization_type", "value", None),
+    ];
+    let mut issues = Vec::new();
+    for (name, field, variants) in tables {
+        match document.pointer(&format!("/{}", name.replace('.', "/"))) {
+            Some(&Value::Array(ref entries)) => check_table(entries, name, field, &variants, &mut issues),
+            _ => (),
+        }
+    }
+    issues
+}
+
+/**
+Reports the issues of a single table. `variants` holds every variant of the enum
+the table holds, or is None if the table holds arbitrary values.
+*/
+fn check_table(entries: &[Value], name: &str, field: &str, variants: &Option<Vec<Value>>, issues: &mut Vec<RepositoryIssue>) {
+    let mut ids: Vec<i16> = Vec::new();
+    let mut values: Vec<&Value> = Vec::new();
+    for entry in entries {
+        let value = entry.get(field).unwrap_or(&Value::Null);
+        match entry.get("id") {
+            None | Some(&Value::Null) => issues.push(RepositoryIssue::missingId(name.to_string(), describe(value))),
+            Some(id) => match id.as_i64() {
+                Some(number) if number >= i16::min_value() as i64 && number <= i16::max_value() as i64 => {
+                    let number = number as i16;
+                    if ids.iter().filter(|other| **other == number).count() == 1 {
+                        issues.push(RepositoryIssue::duplicateId(name.to_string(), number));
+                    }
+                    ids.push(number);
+                }
+                _ => issues.push(RepositoryIssue::invalidId(name.to_string(), id.to_string())),
+            },
+        }
+        if value.is_null() {
+            continue;
+        }
+        match *variants {
+            Some(ref variants) if !variants.contains(value) =>
+                issues.push(RepositoryIssue::unknownValue(name.to_string(), describe(value))),
+            _ => (),
+        }
+        if values.iter().filter(|other| same_value(other, value)).count() == 1 {
+            issues.push(RepositoryIssue::duplicateValue(name.to_string(), describe(value)));
+        }
+        values.push(value);
+    }
+
+    match ids.iter().max() {
+        Some(&largest) => for id in 0..largest {
+            if !ids.contains(&id) {
+                issues.push(RepositoryIssue::unusedId(name.to_string(), id));
+            }
+        },
+        None => (),
+    }
+    match *variants {
+        Some(ref variants) => for variant in variants {
+            if !values.contains(&variant) {
+                issues.push(RepositoryIssue::missingEntry(name.to_string(), describe(variant)));
+            }
+        },
+        None => (),
+    }
+}
+
+/**
+Selects the newest of the local repositories which the peer has as well,
+i.e. with the same version and the same content hash.
+Repositories without version are never selected.
+`Parameters`
+* local:&[Repository]: Repositories available locally
+* remote:&[RepositoryVersion]: Repository versions announced by the peer
+`Return`
+  Ok(Repository):
+    The newest common repository
+  Err(OntologyError):
+    RepositoryMismatch if the peers have no repository version in common
+*/
+pub fn common_repository<'a>(local: &'a [Repository], remote: &[RepositoryVersion]) -> Result<&'a Repository, OntologyError> {
+    let mut common: Option<(&Repository, &Version)> = None;
+    for repo in local {
+        if let Some(ref version) = repo.version {
+            let shared = remote.contains(&repo.get_repository_version()?);
+            if shared && common.map(|(_, newest)| version > newest).unwrap_or(true) {
+                common = Some((repo, version));
+            }
+        }
+    }
+    match common {
+        Some((repo, _)) => Ok(repo),
+        None => Err(OntologyError::RepositoryMismatch(format!("no common version among {:?}",
+            remote.iter().filter_map(|announced| announced.version.as_ref().map(|version| version.to_string())).collect::<Vec<String>>()))),
+    }
+}
+
+/**
+Announces the local repositories to a peer and agrees with it on the repository
+both use for the offers exchanged afterwards. Both peers call the function at the
+same time, e.g. right after connecting, and select the same repository.
+`Parameters`
+* stream:&mut S: Connection to the peer
+* local:&[Repository]: Repositories available locally
+`Return`
+  The newest common repository, see `common_repository`
+*/
+pub fn handshake<'a, S: Read + Write>(stream: &mut S, local: &'a [Repository]) -> Result<&'a Repository, OntologyError> {
+    let mut announced = Vec::new();
+    for repo in local {
+        announced.push(repo.get_repository_version()?);
+    }
+    write_frame(stream, &announced)?;
+    let remote: Vec<RepositoryVersion> = read_frame(stream)?;
+    common_repository(local, &remote)
 }
 
 impl Repository {
@@ -129,16 +475,17 @@ impl Repository {
     `Parameters`
       path:&str => Path to a file containing a repository
     `Return`
-      An instance of the Repository struct
+      An instance of the Repository struct, or the I/O or JSON error
+      which occurred while reading the file
     **/
-    pub fn from_json_file(path:&str) -> Repository {
-        let mut file = File::open(path).unwrap();
+    pub fn from_json_file(path:&str) -> Result<Repository, OntologyError> {
+        let mut file = File::open(path)?;
         let mut data = String::new();
-        file.read_to_string(&mut data).unwrap();
+        file.read_to_string(&mut data)?;
 
-        let rep: Repository = serde_json::from_str(&data).unwrap();
+        let rep: Repository = serde_json::from_str(&data)?;
 
-        rep
+        Ok(rep)
     }
 
     /**
@@ -147,27 +494,159 @@ impl Repository {
     `Parameters`
       path:&str => Path to a file containing a repository
     `Return`
-      An instance of the Repository struct
+      An instance of the Repository struct, or the I/O or MsgPack error
+      which occurred while reading the file
     **/
-    pub fn from_msgpack_file(path:&str) -> Repository {
+    pub fn from_msgpack_file(path:&str) -> Result<Repository, OntologyError> {
 
-        let mut msgpack_file: File = File::open(path).unwrap();
+        let mut msgpack_file: File = File::open(path)?;
         let mut msgpack_data = Vec::new();
 
-        msgpack_file.read_to_end(&mut msgpack_data).unwrap();
-        let rep: Repository = rmps::decode::from_slice(&msgpack_data).unwrap();
-        rep
+        msgpack_file.read_to_end(&mut msgpack_data)?;
+        let rep: Repository = rmps::decode::from_slice(&msgpack_data)?;
+        Ok(rep)
+    }
+
+    /**
+    This function writes an instance of the Repository struct
+    to a file, encoded as JSON dictionary.
+    `Parameters`
+      path:&str => Path of the file to create
+      repo:&Repository => The repository to write
+    `Return`
+      The I/O or JSON error which occurred while writing the file
+    **/
+    pub fn to_json_file(path:&str, repo:&Repository) -> Result<(), OntologyError> {
+        let file_new = File::create(path)?;
+        serde_json::to_writer_pretty(file_new, repo)?;
+        Ok(())
+    }
+
+    /**
+    This function writes an instance of the Repository struct
+    to a file, encoded as MsgPack dictionary.
+    `Parameters`
+      path:&str => Path of the file to create
+      repo:&Repository => The repository to write
+    `Return`
+      The I/O or MsgPack error which occurred while writing the file
+    **/
+    pub fn to_msgpack_file(path:&str, repo:&Repository) -> Result<(), OntologyError> {
+        let mut file_new = File::create(path)?;
+        let new_data = rmps::encode::to_vec(repo)?;
+        file_new.write_all(&new_data)?;
+        Ok(())
     }
 
-    pub fn to_msgpack_file(path:&str, mut repo:&Repository) {
-        let mut file_new = File::create(path).unwrap();
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+    use ontology::Offers;
+    use basicElements::Magnitude;
+
+    fn version(value: &str) -> Option<Version> {
+        Some(value.parse().unwrap())
+    }
 
-        let mut new_data = Vec::new();
+    fn repositories() -> (Repository, Repository) {
+        let repo = Repository::from_json_file("repository.json").unwrap();
+        let mut newer = repo.clone();
+        newer.set_version(version("2.2"));
+        for (position, generation) in newer.get_memory_mut().get_generation_mut().iter_mut().enumerate() {
+            generation.set_id(100 + position as i16);
+        }
+        (repo, newer)
+    }
+
+    #[test]
+    fn offers_are_only_decompressed_with_their_repository_version() {
+        let (repo, newer) = repositories();
+        let offer = Offers::from_json_file("offerlist.json").unwrap().get_offers()[1].clone();
+        let mut compressed = offer.clone();
+        compressed.compress(&repo).unwrap();
+        match compressed.clone().decompress(&newer) {
+            Err(OntologyError::RepositoryMismatch(_)) => (),
+            other => panic!("expected a repository mismatch, got {:?}", other),
+        }
+
+        let mut translated = compressed.clone();
+        translated.translate(&repo, &newer).unwrap();
+        assert_eq!(translated.get_repository_version().get_value(), &Some("2.2".to_string()));
+        assert_eq!(translated.get_memory().as_ref().unwrap().get_generation().as_ref().unwrap().get_id(), &Some(103));
+        assert!(translated.clone().decompress(&repo).is_err());
+        translated.decompress(&newer).unwrap();
+        assert_eq!(translated.get_memory(), offer.get_memory());
+
+        let mut relabeled = repo.clone();
+        relabeled.set_version(version("3.0"));
+        assert_eq!(relabeled.content_hash().unwrap(), repo.content_hash().unwrap());
+        assert!(newer.content_hash().unwrap() != repo.content_hash().unwrap());
+    }
 
-        new_data = rmps::encode::to_vec(&mut repo).unwrap();
+    #[test]
+    fn peers_agree_on_the_newest_common_repository() {
+        let (repo, newer) = repositories();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let remote = vec![repo.clone(), newer.clone()];
+        let peer = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            handshake(&mut stream, &remote).unwrap().get_version().clone()
+        });
+
+        let local = vec![repo.clone()];
+        let mut stream = TcpStream::connect(address).unwrap();
+        assert_eq!(handshake(&mut stream, &local).unwrap().get_version(), &version("2.1"));
+        assert_eq!(peer.join().unwrap(), version("2.1"));
+
+        let announced = vec![repo.get_repository_version().unwrap(), newer.get_repository_version().unwrap()];
+        assert_eq!(common_repository(&[repo.clone(), newer.clone()], &announced).unwrap().get_version(), &version("2.2"));
+
+        let mut modified = newer.clone();
+        modified.get_memory_mut().get_generation_mut().pop();
+        match common_repository(&[modified], &announced) {
+            Err(OntologyError::RepositoryMismatch(_)) => (),
+            other => panic!("expected a repository mismatch, got {:?}", other.map(|repo| repo.get_version().clone())),
+        }
+    }
 
-        use std::io::Write;
-        file_new.write_all(&new_data);
+    #[test]
+    fn the_shipped_repository_is_valid() {
+        let data = std::fs::read_to_string("repository.json").unwrap();
+        assert_eq!(Repository::validate_json(&data).unwrap(), vec![]);
+        assert_eq!(Repository::from_json_file("repository.json").unwrap().validate(), vec![]);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn repository_issues_are_reported() {
+        let mut repo = Repository::from_json_file("repository.json").unwrap();
+        let duplicate = repo.get_cpu().get_manufacturer()[0].clone();
+        repo.get_cpu_mut().get_manufacturer_mut().push(duplicate);
+        repo.get_magnitude_mut().retain(|entry| entry.get_repository_value() != &Some(Magnitude::none));
+        let issues = repo.validate();
+        assert!(issues.contains(&RepositoryIssue::duplicateId("cpu.manufacturer".to_string(), 0)));
+        assert!(issues.iter().any(|issue| match *issue {
+            RepositoryIssue::duplicateValue(ref table, _) => table == "cpu.manufacturer",
+            _ => false,
+        }));
+        assert!(issues.contains(&RepositoryIssue::missingEntry("magnitude".to_string(), "none".to_string())));
+        assert_eq!(issues.len(), 3);
+
+        let data = std::fs::read_to_string("repository.json").unwrap()
+            .replacen("\"id\": 0", "\"id\": 40000", 1)
+            .replace("\"AMD\"", "\"Intel\"")
+            .replace("\"SSE4_1\"", "\"SSE4.1\"");
+        let issues = Repository::validate_json(&data).unwrap();
+        let table = |name: &str| name.to_string();
+        assert_eq!(issues, vec![
+            RepositoryIssue::invalidId(table("magnitude"), "40000".to_string()),
+            RepositoryIssue::unusedId(table("magnitude"), 0),
+            RepositoryIssue::duplicateValue(table("cpu.manufacturer"), "Intel".to_string()),
+            RepositoryIssue::unknownValue(table("cpu.instruction_set"), "SSE4.1".to_string()),
+            RepositoryIssue::missingEntry(table("cpu.instruction_set"), "SSE4_1".to_string()),
+        ]);
+    }
+}
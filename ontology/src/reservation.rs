@@ -0,0 +1,452 @@
+/**
+Contains the accounting of capacity reserved on offers, so that
+several requests matching the same offer do not oversubscribe it.
+*/
+
+use std::collections::HashMap;
+
+use basicElements::{IntElement, IntOperator, FloatElement, FloatOperator};
+use ontology::{Offers, Offer, comparing};
+use error::OntologyError;
+
+/**
+Returns the quantity a requested IntElement claims: its value for the `leq`,
+`eq` and `le` operators, the lower bound of its range for `range`, and nothing
+for `geq` and `ge`, which only limit the offered value.
+*/
+fn claimed_int(element: &IntElement) -> f64 {
+    match *element.get_compare_operator() {
+        IntOperator::leq | IntOperator::eq | IntOperator::le => element.get_absolute_value().unwrap_or(0.0),
+        IntOperator::range => match *element.get_range() {
+            Some(ref range) => range.get_lower().as_ref().map(|lower| lower.get_absolute_value() * element.get_unit_factor()).unwrap_or(0.0),
+            None => 0.0,
+        },
+        IntOperator::geq | IntOperator::ge => 0.0,
+    }
+}
+
+/**
+Returns the quantity a requested FloatElement claims, see `claimed_int`.
+*/
+fn claimed_float(element: &FloatElement) -> f64 {
+    match *element.get_compare_operator() {
+        FloatOperator::leq | FloatOperator::eq | FloatOperator::le => element.get_absolute_value().unwrap_or(0.0),
+        FloatOperator::range => match *element.get_range() {
+            Some(ref range) => range.get_lower().as_ref().map(|lower| lower.get_absolute_value() * element.get_unit_factor()).unwrap_or(0.0),
+            None => 0.0,
+        },
+        FloatOperator::geq | FloatOperator::ge => 0.0,
+    }
+}
+
+/**
+The absolute quantities claimed on a single offer. Disks and network
+interfaces are addressed by their position in the offer.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Allocation {
+    cpu_amount: f64,
+    memory_size: f64,
+    disk_size: HashMap<usize, f64>,
+    quota_size: HashMap<usize, f64>,
+}
+
+impl Allocation {
+    pub fn get_cpu_amount(&self) -> f64 { self.cpu_amount }
+    pub fn get_memory_size(&self) -> f64 { self.memory_size }
+
+    /**
+    Returns the size claimed on the disk at the given position of the offer.
+    */
+    pub fn get_disk_size(&self, disk: usize) -> f64 {
+        *self.disk_size.get(&disk).unwrap_or(&0.0)
+    }
+
+    /**
+    Returns the quota claimed on the network interface at the given position of the offer.
+    */
+    pub fn get_quota_size(&self, interface: usize) -> f64 {
+        *self.quota_size.get(&interface).unwrap_or(&0.0)
+    }
+
+    fn add(&mut self, other: &Allocation) {
+        self.cpu_amount += other.cpu_amount;
+        self.memory_size += other.memory_size;
+        for (disk, size) in &other.disk_size {
+            *self.disk_size.entry(*disk).or_insert(0.0) += *size;
+        }
+        for (interface, size) in &other.quota_size {
+            *self.quota_size.entry(*interface).or_insert(0.0) += *size;
+        }
+    }
+
+    fn subtract(&mut self, other: &Allocation) {
+        self.cpu_amount -= other.cpu_amount;
+        self.memory_size -= other.memory_size;
+        for (disk, size) in &other.disk_size {
+            *self.disk_size.entry(*disk).or_insert(0.0) -= *size;
+        }
+        for (interface, size) in &other.quota_size {
+            *self.quota_size.entry(*interface).or_insert(0.0) -= *size;
+        }
+    }
+}
+
+/**
+Capacity claimed by one request on one offer.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Reservation {
+    id: u64,
+    offer: usize,
+    allocation: Allocation,
+}
+
+impl Reservation {
+    pub fn get_id(&self) -> u64 { self.id }
+    pub fn get_offer(&self) -> usize { self.offer }
+    pub fn get_allocation(&self) -> &Allocation { &self.allocation }
+}
+
+/**
+Keeps track of the capacity reserved on a list of offers.
+The CPU amount, memory size, disk sizes and network quotas of an offer are its
+total capacity; every reservation reduces the remaining capacity until it is released.
+The offers are kept in base units, so that the remaining capacity is not rounded to
+whole units of the magnitude the offer was given in, e.g. 900 giga of a 1 tera disk.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CapacityLedger {
+    offers: Vec<Offer>,
+    allocations: Vec<Allocation>,
+    reservations: HashMap<u64, Reservation>,
+    next_id: u64,
+}
+
+impl CapacityLedger {
+    pub fn new(mut offers: Offers) -> CapacityLedger {
+        offers.normalize();
+        let offers = offers.get_offers().clone();
+        let allocations = vec![Allocation::default(); offers.len()];
+        CapacityLedger { offers: offers, allocations: allocations, reservations: HashMap::new(), next_id: 0 }
+    }
+
+    /**
+    Returns the offers of the ledger, normalized to base units.
+    */
+    pub fn get_offers(&self) -> &Vec<Offer> { &self.offers }
+
+    /**
+    Replaces the offers of the ledger, e.g. with their renewed versions. Reservations on
+    offers which are still present, see `Offer::same_offer`, are kept; the others are dropped.
+    `Return`
+      The dropped reservations
+    */
+    pub fn set_offers(&mut self, mut offers: Offers) -> Vec<Reservation> {
+        offers.normalize();
+        let offers = offers.get_offers().clone();
+        let positions: Vec<Option<usize>> = self.offers.iter()
+            .map(|old| offers.iter().position(|offer| offer.same_offer(old)))
+            .collect();
+        let mut allocations = vec![Allocation::default(); offers.len()];
+        let mut dropped = Vec::new();
+        for (id, mut reservation) in self.reservations.drain().collect::<Vec<(u64, Reservation)>>() {
+            match positions[reservation.offer] {
+                Some(position) => {
+                    reservation.offer = position;
+                    allocations[position].add(&reservation.allocation);
+                    self.reservations.insert(id, reservation);
+                }
+                None => dropped.push(reservation),
+            }
+        }
+        self.offers = offers;
+        self.allocations = allocations;
+        dropped
+    }
+
+    pub fn get_reservation(&self, id: u64) -> Option<&Reservation> { self.reservations.get(&id) }
+    pub fn get_reservations(&self) -> Vec<&Reservation> { self.reservations.values().collect() }
+
+    /**
+    Returns the capacity reserved on the offer at the given position.
+    */
+    pub fn get_allocation(&self, offer: usize) -> Option<&Allocation> { self.allocations.get(offer) }
+
+    /**
+    Returns a copy of the offer at the given position, whose CPU amount, memory size,
+    disk sizes and network quotas are reduced to the remaining capacity.
+    */
+    pub fn remaining(&self, offer: usize) -> Option<Offer> {
+        let (offer, allocation) = match (self.offers.get(offer), self.allocations.get(offer)) {
+            (Some(offer), Some(allocation)) => (offer, allocation),
+            (_, _) => return None,
+        };
+        let mut remaining = offer.clone();
+
+        if let Some(ref mut cpu) = *remaining.get_cpu_mut() {
+            let amount = cpu.get_amount().as_ref()
+                .and_then(|amount| amount.get_absolute_value().map(|total| amount.with_absolute_value(total - allocation.cpu_amount)));
+            if amount.is_some() {
+                cpu.set_amount(amount);
+            }
+        }
+        if let Some(ref mut memory) = *remaining.get_memory_mut() {
+            let size = memory.get_size().as_ref()
+                .and_then(|size| size.get_absolute_value().map(|total| size.with_absolute_value(total - allocation.memory_size)));
+            if size.is_some() {
+                memory.set_size(size);
+            }
+        }
+        if let Some(ref mut disks) = *remaining.get_disk_mut() {
+            for (position, disk) in disks.iter_mut().enumerate() {
+                let size = disk.get_size().as_ref()
+                    .and_then(|size| size.get_absolute_value().map(|total| size.with_absolute_value(total - allocation.get_disk_size(position))));
+                if size.is_some() {
+                    disk.set_size(size);
+                }
+            }
+        }
+        if let Some(ref mut interfaces) = *remaining.get_network_interface_mut() {
+            for (position, interface) in interfaces.iter_mut().enumerate() {
+                if let Some(ref mut quota) = *interface.get_quota_mut() {
+                    let size = quota.get_size().as_ref()
+                        .and_then(|size| size.get_absolute_value().map(|total| size.with_absolute_value(total - allocation.get_quota_size(position))));
+                    if size.is_some() {
+                        quota.set_size(size);
+                    }
+                }
+            }
+        }
+        Some(remaining)
+    }
+
+    /**
+    Returns the positions of all offers whose remaining capacity is sufficient for the request.
+    */
+    pub fn sufficient(&self, request: &Offer) -> Vec<usize> {
+        let mut sufficient = Vec::new();
+        for position in 0..self.offers.len() {
+            if let Some(remaining) = self.remaining(position) {
+                if comparing(request, &remaining).is_match() {
+                    sufficient.push(position);
+                }
+            }
+        }
+        sufficient
+    }
+
+    /**
+    Reserves the quantities claimed by a request on an offer. Each requested disk and
+    network quota is taken from the first disk or network interface of the offer
+    whose remaining capacity still satisfies it.
+    `Parameters`
+    * request:&Offer: Request which is placed on the offer
+    * offer:usize: Position of the offer
+    `Return`
+      The ID of the reservation, or Err(InsufficientCapacity) listing the failed
+      attributes if the remaining capacity of the offer does not satisfy the request
+    */
+    pub fn reserve(&mut self, request: &Offer, offer: usize) -> Result<u64, OntologyError> {
+        let remaining = match self.remaining(offer) {
+            Some(remaining) => remaining,
+            None => return Err(OntologyError::UnknownOffer(offer)),
+        };
+        let report = comparing(request, &remaining);
+        if !report.is_match() {
+            let failures: Vec<String> = report.get_failures().iter().map(|failure| failure.to_string()).collect();
+            return Err(OntologyError::InsufficientCapacity(failures.join("; ")));
+        }
+
+        let mut allocation = Allocation::default();
+        if let (&Some(ref cpu_a), &Some(ref cpu_b)) = (request.get_cpu(), remaining.get_cpu()) {
+            if let (&Some(ref amount), &Some(_)) = (cpu_a.get_amount(), cpu_b.get_amount()) {
+                allocation.cpu_amount = claimed_int(amount);
+            }
+        }
+        if let (&Some(ref memory_a), &Some(ref memory_b)) = (request.get_memory(), remaining.get_memory()) {
+            if let (&Some(ref size), &Some(_)) = (memory_a.get_size(), memory_b.get_size()) {
+                allocation.memory_size = claimed_float(size);
+            }
+        }
+        if let (&Some(ref disks_a), &Some(ref disks_b)) = (request.get_disk(), remaining.get_disk()) {
+            for (i, disk_a) in disks_a.iter().enumerate() {
+                let size_a = match *disk_a.get_size() {
+                    Some(ref size_a) => size_a,
+                    None => continue,
+                };
+                let chosen = disks_b.iter().enumerate().position(|(position, disk_b)| {
+                    let type_matches = match (disk_a.get_disk_type(), disk_b.get_disk_type()) {
+                        (&Some(ref type_a), &Some(ref type_b)) => type_a.compare(type_b),
+                        (_, _) => true,
+                    };
+                    let size_matches = match (disk_b.get_size(), disk_b.get_size().as_ref().and_then(|size| size.get_absolute_value())) {
+                        (&Some(ref size_b), Some(total)) =>
+                            size_a.compare(&size_b.with_absolute_value(total - allocation.get_disk_size(position))),
+                        (_, _) => false,
+                    };
+                    type_matches && size_matches
+                });
+                match chosen {
+                    Some(position) => *allocation.disk_size.entry(position).or_insert(0.0) += claimed_int(size_a),
+                    None => return Err(OntologyError::InsufficientCapacity(format!("no disk left for disk[{}]", i))),
+                }
+            }
+        }
+        if let (&Some(ref interfaces_a), &Some(ref interfaces_b)) = (request.get_network_interface(), remaining.get_network_interface()) {
+            for (i, interface_a) in interfaces_a.iter().enumerate() {
+                let size_a = match interface_a.get_quota().as_ref().and_then(|quota| quota.get_size().as_ref()) {
+                    Some(size_a) => size_a,
+                    None => continue,
+                };
+                let chosen = interfaces_b.iter().enumerate().position(|(position, interface_b)| {
+                    match interface_b.get_quota().as_ref().and_then(|quota| quota.get_size().as_ref()) {
+                        Some(size_b) => match size_b.get_absolute_value() {
+                            Some(total) => size_a.compare(&size_b.with_absolute_value(total - allocation.get_quota_size(position))),
+                            None => false,
+                        },
+                        None => false,
+                    }
+                });
+                match chosen {
+                    Some(position) => *allocation.quota_size.entry(position).or_insert(0.0) += claimed_int(size_a),
+                    None => return Err(OntologyError::InsufficientCapacity(format!("no quota left for network_interface[{}]", i))),
+                }
+            }
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.allocations[offer].add(&allocation);
+        self.reservations.insert(id, Reservation { id: id, offer: offer, allocation: allocation });
+        Ok(id)
+    }
+
+    /**
+    Releases a reservation, so that its capacity becomes available again.
+    `Return`
+      The released reservation, or Err(UnknownReservation) if it does not exist
+    */
+    pub fn release(&mut self, id: u64) -> Result<Reservation, OntologyError> {
+        match self.reservations.remove(&id) {
+            Some(reservation) => {
+                self.allocations[reservation.offer].subtract(&reservation.allocation);
+                Ok(reservation)
+            }
+            None => Err(OntologyError::UnknownReservation(id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use basicElements::{StringElement, StringOperator};
+
+    fn offers() -> Offers {
+        serde_json::from_str(r#"{"offers": [{
+            "host": {"value": "TU KL"},
+            "ontology_version": {"value": 1.1},
+            "repository_version": {"value": 2.1},
+            "timeout": {"value": 1000},
+            "cpu": {"amount": {"value": 4}, "instruction_set": []},
+            "memory": {"size": {"value": 8, "magnitude": {"magnitude": "giga"}}},
+            "disk": [{"size": {"value": 500, "magnitude": {"magnitude": "giga"}}},
+                     {"size": {"value": 200, "magnitude": {"magnitude": "giga"}}}],
+            "network_interface": [{"quota": {"size": {"value": 100, "magnitude": {"magnitude": "giga"}}}}]
+        }]}"#).unwrap()
+    }
+
+    fn request(cores: u32, disks: &[u32]) -> Offer {
+        let disks: Vec<String> = disks.iter()
+            .map(|size| format!(r#"{{"size": {{"value": {}, "magnitude": {{"magnitude": "giga"}}, "compareOperator": "leq"}}}}"#, size))
+            .collect();
+        serde_json::from_str(&format!(r#"{{
+            "host": {{"value": "TU KL"}},
+            "ontology_version": {{"value": 1.1}},
+            "repository_version": {{"value": 2.1}},
+            "timeout": {{"value": 1000}},
+            "cpu": {{"amount": {{"value": {}, "compareOperator": "leq"}}, "instruction_set": []}},
+            "memory": {{"size": {{"value": 3.5, "magnitude": {{"magnitude": "giga"}}, "compareOperator": "leq"}}}},
+            "disk": [{}],
+            "network_interface": [{{"quota": {{"size": {{"value": 40, "magnitude": {{"magnitude": "giga"}}, "compareOperator": "leq"}}}}}}]
+        }}"#, cores, disks.join(", "))).unwrap()
+    }
+
+    #[test]
+    fn reservations_reduce_the_remaining_capacity_until_released() {
+        let mut ledger = CapacityLedger::new(offers());
+        let first = ledger.reserve(&request(3, &[300]), 0).unwrap();
+
+        let remaining = ledger.remaining(0).unwrap();
+        assert_eq!(remaining.get_cpu().as_ref().unwrap().get_amount().as_ref().unwrap().get_value(), &Some(1));
+        assert_eq!(remaining.get_memory().as_ref().unwrap().get_size().as_ref().unwrap().get_absolute_value(), Some(4.5e9));
+        assert_eq!(ledger.get_allocation(0).unwrap().get_disk_size(0), 300e9);
+        assert_eq!(ledger.get_allocation(0).unwrap().get_quota_size(0), 40e9);
+
+        assert_eq!(ledger.sufficient(&request(3, &[])), Vec::<usize>::new());
+        match ledger.reserve(&request(3, &[]), 0) {
+            Err(OntologyError::InsufficientCapacity(_)) => (),
+            other => panic!("expected insufficient capacity, got {:?}", other),
+        }
+        match ledger.reserve(&request(1, &[200, 200, 10]), 0) {
+            Err(OntologyError::InsufficientCapacity(_)) => (),
+            other => panic!("expected insufficient capacity, got {:?}", other),
+        }
+        let second = ledger.reserve(&request(1, &[150, 200]), 0).unwrap();
+        assert_eq!(ledger.get_allocation(0).unwrap().get_disk_size(1), 200e9);
+        assert_eq!(ledger.get_allocation(0).unwrap().get_disk_size(0), 450e9);
+
+        ledger.release(first).unwrap();
+        ledger.release(second).unwrap();
+        assert_eq!(ledger.get_allocation(0).unwrap().get_cpu_amount(), 0.0);
+        assert_eq!(ledger.sufficient(&request(3, &[])), vec![0]);
+        match ledger.release(first) {
+            Err(OntologyError::UnknownReservation(id)) => assert_eq!(id, first),
+            other => panic!("expected an unknown reservation, got {:?}", other),
+        }
+    }
+    #[test]
+    fn reservations_in_smaller_magnitudes_leave_the_rest_of_the_capacity() {
+        let mut offers = offers();
+        offers.get_offers_mut()[0].set_disk(Some(serde_json::from_str(
+            r#"[{"size": {"value": 1, "magnitude": {"magnitude": "tera"}}}]"#).unwrap()));
+        let mut ledger = CapacityLedger::new(offers);
+        let disk_size = |ledger: &CapacityLedger| ledger.remaining(0).unwrap().get_disk().as_ref().unwrap()[0]
+            .get_size().as_ref().unwrap().get_absolute_value();
+        let first = ledger.reserve(&request(1, &[100]), 0).unwrap();
+        assert_eq!(disk_size(&ledger), Some(900e9));
+        let second = ledger.reserve(&request(1, &[100]), 0).unwrap();
+        assert_eq!(disk_size(&ledger), Some(800e9));
+
+        ledger.release(first).unwrap();
+        ledger.release(second).unwrap();
+        match ledger.reserve(&request(1, &[1001]), 0) {
+            Err(OntologyError::InsufficientCapacity(_)) => (),
+            other => panic!("expected insufficient capacity, got {:?}", other),
+        }
+        ledger.reserve(&request(1, &[1000]), 0).unwrap();
+    }
+
+    #[test]
+    fn replacing_the_offers_keeps_the_reservations_of_remaining_offers() {
+        let mut ledger = CapacityLedger::new(offers());
+        let kept = ledger.reserve(&request(3, &[300]), 0).unwrap();
+
+        let mut offers = offers();
+        let mut other = offers.get_offers()[0].clone();
+        other.set_host(StringElement::new("TU Berlin", StringOperator::eq));
+        offers.get_offers_mut()[0].renew(500);
+        offers.get_offers_mut().insert(0, other);
+        assert!(ledger.set_offers(offers).is_empty());
+
+        assert_eq!(ledger.get_reservation(kept).unwrap().get_offer(), 1);
+        assert_eq!(ledger.get_allocation(1).unwrap().get_cpu_amount(), 3.0);
+        assert_eq!(ledger.get_allocation(0).unwrap().get_cpu_amount(), 0.0);
+
+        let dropped = ledger.set_offers(Offers::new(vec![ledger.get_offers()[0].clone()]));
+        assert_eq!(dropped.iter().map(|reservation| reservation.get_id()).collect::<Vec<u64>>(), vec![kept]);
+        assert!(ledger.get_reservations().is_empty());
+    }
+}
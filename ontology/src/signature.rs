@@ -0,0 +1,232 @@
+/**
+Contains the signing of offers by their host and the verification
+of these signatures against a set of known host keys.
+
+An offer is signed over its canonical encoding as defined in `canonical`.
+Changes of the representation which keep the canonical encoding, e.g.
+decompressing the offer or converting it between JSON and MsgPack, keep the
+signature valid. Every other change, including renewing the offer, invalidates
+it, so offers are verified before they are compressed or modified.
+*/
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde_json;
+
+use canonical::canonical_bytes;
+use ontology::{Offer, comparing};
+use report::MatchReport;
+use error::OntologyError;
+
+fn host_name(offer: &Offer) -> String {
+    match *offer.get_host().get_value() {
+        Some(ref host) => host.clone(),
+        None => String::new(),
+    }
+}
+
+/**
+Signs the offer with the key of its host, replacing any previous signature.
+`Parameters`
+* offer:&mut Offer: Offer to sign
+* key:&SigningKey: Private key of the host
+*/
+pub fn sign(offer: &mut Offer, key: &SigningKey) -> Result<(), OntologyError> {
+    let signature = key.sign(&canonical_bytes(offer)?);
+    offer.set_signature(Some(signature.to_bytes().to_vec()));
+    Ok(())
+}
+
+/**
+Maps host names to the public keys their offers are verified with.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HostKeys {
+    keys: HashMap<String, Vec<u8>>,
+}
+
+impl HostKeys {
+    pub fn new() -> HostKeys {
+        HostKeys { keys: HashMap::new() }
+    }
+
+    /**
+    Adds or replaces the public key of a host.
+    */
+    pub fn insert(&mut self, host: &str, key: &VerifyingKey) {
+        self.keys.insert(host.to_string(), key.to_bytes().to_vec());
+    }
+
+    pub fn with_key(mut self, host: &str, key: &VerifyingKey) -> HostKeys {
+        self.insert(host, key);
+        self
+    }
+
+    /**
+    Returns the public key of a host, None if it is unknown or not a valid Ed25519 key.
+    */
+    pub fn get(&self, host: &str) -> Option<VerifyingKey> {
+        self.keys.get(host)
+            .and_then(|bytes| if bytes.len() == 32 {
+                let mut key = [0u8; 32];
+                key.copy_from_slice(bytes);
+                Some(key)
+            } else {
+                None
+            })
+            .and_then(|key| VerifyingKey::from_bytes(&key).ok())
+    }
+
+    /**
+    This function reads a JSON file containing a set of host keys,
+    each key given as array of 32 bytes.
+    `Parameters`
+    * path:&str: Path to a JSON file
+    `Return`
+     Ok(HostKeys):
+        Returns the key set from the JSON file
+     Err(OntologyError):
+        If the file could not be read or parsed
+    */
+    pub fn from_json_file(path: &str) -> Result<HostKeys, OntologyError> {
+        let mut file = File::open(path)?;
+        let mut data = String::new();
+        file.read_to_string(&mut data)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    pub fn to_json_file(path: &str, keys: &HostKeys) -> Result<(), OntologyError> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, keys)?;
+        Ok(())
+    }
+}
+
+/**
+Checks that the offer was signed by its host.
+`Parameters`
+* offer:&Offer: Offer to verify
+* keys:&HostKeys: Public keys of the known hosts
+`Return`
+ Ok(()):
+    If the signature matches the content of the offer and the key of its host
+ Err(OntologyError):
+    UnsignedOffer if the offer carries no signature, UnknownHostKey if no key
+    is known for its host and InvalidSignature if the offer was tampered with
+*/
+pub fn verify(offer: &Offer, keys: &HostKeys) -> Result<(), OntologyError> {
+    let host = host_name(offer);
+    let signature = match *offer.get_signature() {
+        Some(ref signature) => Signature::from_slice(signature)
+            .map_err(|_| OntologyError::InvalidSignature(host.clone()))?,
+        None => return Err(OntologyError::UnsignedOffer(host)),
+    };
+    let key = match keys.get(&host) {
+        Some(key) => key,
+        None => return Err(OntologyError::UnknownHostKey(host)),
+    };
+    key.verify_strict(&canonical_bytes(offer)?, &signature)
+        .map_err(|_| OntologyError::InvalidSignature(host))
+}
+
+/**
+Compares a request with an offer like `comparing`, but additionally
+records the signature of the offer, so that unsigned or tampered
+offers and offers of unknown hosts are not sufficient.
+`Parameters`
+* a:&Offer: Offer used as request
+* b:&Offer: Offer which is compared to the request
+* keys:&HostKeys: Public keys of the known hosts
+*/
+pub fn comparing_signed(a: &Offer, b: &Offer, keys: &HostKeys) -> MatchReport {
+    let mut report = MatchReport::new();
+    let (offer_value, passed) = match verify(b, keys) {
+        Ok(()) => ("valid".to_string(), true),
+        Err(e) => (e.to_string(), false),
+    };
+    report.record("signature".to_string(), &"signed by host", &offer_value, &"verify", passed);
+    report.append(comparing(a, b));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmps;
+    use ontology::Offers;
+    use repository::Repository;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn offers() -> Offers {
+        Offers::from_json_file("offerlist.json").unwrap()
+    }
+
+    #[test]
+    fn signed_offers_are_verified_against_their_host_key() {
+        let mut offer = offers().get_offers()[0].clone();
+        let host = host_name(&offer);
+        let keys = HostKeys::new().with_key(&host, &key(1).verifying_key());
+
+        match verify(&offer, &keys) {
+            Err(OntologyError::UnsignedOffer(_)) => (),
+            other => panic!("expected an unsigned offer, got {:?}", other),
+        }
+
+        sign(&mut offer, &key(1)).unwrap();
+        verify(&offer, &keys).unwrap();
+
+        let encoded = rmps::encode::to_vec(&offer).unwrap();
+        let decoded: Offer = rmps::decode::from_slice(&encoded).unwrap();
+        assert_eq!(decoded.get_signature(), offer.get_signature());
+        assert_eq!(decoded.get_created(), offer.get_created());
+        verify(&decoded, &keys).unwrap();
+
+        let repo = Repository::from_json_file("repository.json").unwrap();
+        let mut compressed = offer.clone();
+        compressed.compress(&repo).unwrap();
+        let encoded = rmps::encode::to_vec(&compressed).unwrap();
+        let mut decoded: Offer = rmps::decode::from_slice(&encoded).unwrap();
+        decoded.decompress(&repo).unwrap();
+        verify(&decoded, &keys).unwrap();
+
+        let mut forged = offer.clone();
+        sign(&mut forged, &key(2)).unwrap();
+        match verify(&forged, &keys) {
+            Err(OntologyError::InvalidSignature(_)) => (),
+            other => panic!("expected an invalid signature, got {:?}", other),
+        }
+
+        let mut tampered = offer.clone();
+        tampered.renew(42);
+        match verify(&tampered, &keys) {
+            Err(OntologyError::InvalidSignature(_)) => (),
+            other => panic!("expected an invalid signature, got {:?}", other),
+        }
+
+        match verify(&offer, &HostKeys::new()) {
+            Err(OntologyError::UnknownHostKey(ref unknown)) => assert_eq!(unknown, &host),
+            other => panic!("expected an unknown host key, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn comparing_signed_rejects_unsigned_offers() {
+        let offer = offers().get_offers()[0].clone();
+        let keys = HostKeys::new().with_key(&host_name(&offer), &key(1).verifying_key());
+        assert!(comparing(&offer, &offer).is_match());
+
+        let report = comparing_signed(&offer, &offer, &keys);
+        assert!(!report.is_match());
+        assert_eq!(report.get_failures()[0].get_path(), "signature");
+
+        let mut signed = offer.clone();
+        sign(&mut signed, &key(1)).unwrap();
+        assert!(comparing_signed(&offer, &signed, &keys).is_match());
+    }
+}
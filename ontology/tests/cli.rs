@@ -0,0 +1,90 @@
+extern crate serde_json;
+extern crate swarmcloud_ontology;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use swarmcloud_ontology::ontology::Offers;
+
+/**
+Runs the command line tool in the crate directory, where the example
+offer list and repository are.
+*/
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_swarmcloud_ontology"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/**
+Returns a path in the temporary directory which is unique for the test.
+*/
+fn temp_path(name: &str) -> String {
+    let mut path = PathBuf::from(env::temp_dir());
+    path.push(format!("swarmcloud_ontology_{}_{}", std::process::id(), name));
+    path.to_str().unwrap().to_string()
+}
+
+fn write_request(name: &str, position: usize) -> String {
+    let offers = Offers::from_json_file("offerlist.json").unwrap();
+    let path = temp_path(name);
+    let mut file = File::create(&path).unwrap();
+    file.write_all(serde_json::to_string(&offers.get_offers()[position]).unwrap().as_bytes()).unwrap();
+    path
+}
+
+#[test]
+fn validate_accepts_the_example_offers() {
+    let output = run(&["validate", "offerlist.json", "--repository", "repository.json"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("offer 0 (TU KL): ok"));
+}
+
+#[test]
+fn validate_rejects_a_file_which_is_no_offer_list() {
+    let output = run(&["validate", "repository.json"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn compress_and_decompress_restore_the_offers() {
+    let compressed = temp_path("compressed.msgpack");
+    let decompressed = temp_path("decompressed.json");
+
+    let output = run(&["compress", "offerlist.json", "repository.json", &compressed, "--format", "msgpack"]);
+    assert_eq!(output.status.code(), Some(0));
+    let output = run(&["decompress", &compressed, "repository.json", &decompressed, "--format", "json"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    assert_eq!(Offers::from_json_file(&decompressed).unwrap(), Offers::from_json_file("offerlist.json").unwrap());
+    fs::remove_file(compressed).unwrap();
+    fs::remove_file(decompressed).unwrap();
+}
+
+#[test]
+fn compress_fails_without_a_repository() {
+    let output = run(&["compress", "offerlist.json", "missing.json", &temp_path("unwritten.json")]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn match_ranks_the_offer_at_its_position() {
+    let request = write_request("request.json", 1);
+    let output = run(&["match", &request, "offerlist.json"]);
+    fs::remove_file(request).unwrap();
+    assert_eq!(output.status.code(), Some(0));
+
+    let printed = stdout(&output);
+    assert!(printed.contains("1 of 3 offers are sufficient:"));
+    assert!(printed.contains("1. offer 1 (TU KL) score 1.000"));
+    assert!(printed.contains("offer 0 (TU KL) does not match:"));
+    assert!(printed.contains("- cpu.manufacturer: request AMD (eq) offer Intel => failed"));
+}